@@ -0,0 +1,674 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Lit, Meta, NestedMeta, Type};
+
+/// The variants of arcrs::gp::api::DataType which can be declared using data_type.
+const DATA_TYPES: &[&str] = &["DEFeatureClass", "GPFeatureLayer", "GPFeatureRecordSetLayer", "GPString", "GPDouble", "GPLong", "GPBoolean"];
+
+/// Declares a geoprocessing tool using an annotated parameters struct.
+/// Every named field represents one geoprocessing parameter.
+/// The derived implementation of `arcrs::gp::api::GpTool` declares the parameters,
+/// extracts the parameter values into a new instance of the struct
+/// and calls `arcrs::gp::api::GpToolRun::run` on this instance.
+/// Fields of derived output parameters are not extracted and are initialized using `Default`.
+/// The doc comments of the struct and its fields are used as summary and parameter explanations
+/// of the tool metadata.
+///
+/// # Examples
+///
+/// ```ignore
+/// /// Creates buffer polygons around input features.
+/// #[derive(Default, GpTool)]
+/// #[gptool(name = "BufferFeatures", label = "Buffer features", category = "Proximity",
+///     description = "Buffers features using a distance.",
+///     usage = "The distance is measured in meters.", tags("buffer", "proximity"),
+///     example(title = "Buffer (Python window)", code = "arcpy.rust.BufferFeatures('roads', 100)"))]
+/// pub struct BufferTool {
+///     /// The input features to be buffered.
+///     #[gpparam(display_name = "Input Features", data_type = "GPFeatureLayer")]
+///     in_features: CatalogPath,
+///
+///     #[gpparam(display_name = "Distance", range(0, 1000), default = 100)]
+///     distance: f64,
+///
+///     #[gpparam(display_name = "Method", value_list("PLANAR", "GEODESIC"))]
+///     method: Option<String>
+/// }
+/// ```
+#[proc_macro_derive(GpTool, attributes(gptool, gpparam))]
+pub fn derive_gptool(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    match expand_gptool(&derive_input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into()
+    }
+}
+
+
+
+/// Represents the tool attributes declared on the struct.
+struct ToolAttributes {
+    name: Option<String>,
+    label: Option<String>,
+    description: String,
+    summary: Option<String>,
+    category: Option<String>,
+    usage: Vec<String>,
+    tags: Vec<String>,
+    code_samples: Vec<TokenStream2>
+}
+
+/// Represents the parameter attributes declared on a field.
+struct ParameterAttributes {
+    display_name: Option<String>,
+    name: Option<String>,
+    data_type: Option<Ident>,
+    parameter_type: Option<Ident>,
+    direction: Option<Ident>,
+    filter: Option<TokenStream2>,
+    default_value: Option<String>,
+    explanation: Option<String>
+}
+
+fn expand_gptool(derive_input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let struct_name = &derive_input.ident;
+    let fields = match &derive_input.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(named_fields) => &named_fields.named,
+            _ => return Err(syn::Error::new_spanned(struct_name, "GpTool can only be derived for structs having named fields!"))
+        },
+        _ => return Err(syn::Error::new_spanned(struct_name, "GpTool can only be derived for structs!"))
+    };
+
+    let tool_attributes = parse_tool_attributes(derive_input)?;
+    let label = match tool_attributes.label {
+        Some(label) => label,
+        None => return Err(syn::Error::new_spanned(struct_name, "Missing #[gptool(label = \"...\")] attribute!"))
+    };
+    let description = tool_attributes.description;
+    let name_fn = tool_attributes.name.map(|name| quote! {
+        fn name(&self) -> ::std::string::String { #name.to_string() }
+    });
+    let category_fn = tool_attributes.category.map(|category| quote! {
+        fn category(&self) -> ::std::option::Option<&str> { ::std::option::Option::Some(#category) }
+    });
+    let summary = tool_attributes.summary.or_else(|| doc_comment(&derive_input.attrs)).unwrap_or_default();
+    let usage = tool_attributes.usage;
+    let tags = tool_attributes.tags;
+    let code_samples = tool_attributes.code_samples;
+
+    let mut parameter_builders = Vec::with_capacity(fields.len());
+    let mut field_extractions = Vec::with_capacity(fields.len());
+    let mut parameter_explanations = Vec::with_capacity(fields.len());
+    for field in fields {
+        let field_ident = field.ident.as_ref().unwrap();
+        let parameter_attributes = parse_parameter_attributes(field)?;
+        let name = parameter_attributes.name.unwrap_or_else(|| field_ident.to_string());
+        let display_name = parameter_attributes.display_name.unwrap_or_else(|| name.to_string());
+        if let Some(explanation) = parameter_attributes.explanation.or_else(|| doc_comment(&field.attrs)) {
+            parameter_explanations.push(quote! {
+                parameter_explanations.insert(#name.to_string(), #explanation.to_string());
+            });
+        }
+        let optional_type = unwrap_option(&field.ty);
+        let data_type = match parameter_attributes.data_type {
+            Some(data_type) => data_type,
+            None => infer_data_type(optional_type.unwrap_or(&field.ty))?
+        };
+        let parameter_type = match parameter_attributes.parameter_type {
+            Some(parameter_type) => parameter_type,
+            None if optional_type.is_some() => Ident::new("Optional", Span::call_site()),
+            None => Ident::new("Required", Span::call_site())
+        };
+        let direction = match parameter_attributes.direction {
+            Some(direction) => direction,
+            None if parameter_type == "Derived" => Ident::new("Output", Span::call_site()),
+            None => Ident::new("Input", Span::call_site())
+        };
+        if let Some(default_value) = &parameter_attributes.default_value {
+            validate_default_value(field, &data_type, default_value)?;
+        }
+        let with_filter = parameter_attributes.filter.map(|filter| quote! { .with_filter(#filter) });
+        let with_default_value = parameter_attributes.default_value.map(|default_value| quote! { .with_default_value(#default_value) });
+
+        parameter_builders.push(quote! {
+            ::arcrs::gp::api::GpParameterBuilder::new()
+                .with_display_name(#display_name)
+                .with_name(#name)
+                .with_data_type(::arcrs::gp::api::DataType::#data_type)
+                .with_parameter_type(::arcrs::gp::api::ParameterType::#parameter_type)
+                .with_direction(::arcrs::gp::api::Direction::#direction)
+                #with_filter
+                #with_default_value
+                .build()
+        });
+        if parameter_type == "Derived" {
+            // Derived parameters are set by the tool and have no value to be extracted
+            field_extractions.push(quote! {
+                #field_ident: ::std::default::Default::default()
+            });
+        } else {
+            field_extractions.push(quote! {
+                #field_ident: ::arcrs::gp::api::FromParameterValue::from_parameter_value(parameters.get(#name)?)?
+            });
+        }
+    }
+
+    let (impl_generics, type_generics, where_clause) = derive_input.generics.split_for_impl();
+    let tokens = quote! {
+        impl #impl_generics ::arcrs::gp::api::GpTool for #struct_name #type_generics #where_clause {
+
+            #name_fn
+
+            fn label(&self) -> &str { #label }
+
+            fn description(&self) -> &str { #description }
+
+            fn parameters(&self) -> ::std::vec::Vec<::arcrs::gp::api::GpParameter> {
+                vec![#(#parameter_builders),*]
+            }
+
+            fn execute(&self, py: ::arcrs::pyo3::Python, parameters: ::arcrs::gp::api::PyParameterValues, messages: ::arcrs::gp::api::PyGpMessages) -> ::arcrs::pyo3::PyResult<()> {
+                let tool_values = #struct_name {
+                    #(#field_extractions),*
+                };
+
+                ::arcrs::gp::api::GpToolRun::run(&tool_values, py, &parameters, &messages)
+            }
+
+            fn metadata(&self) -> ::arcrs::gp::api::GpToolMetadata {
+                let mut parameter_explanations = ::std::collections::HashMap::new();
+                #(#parameter_explanations)*
+
+                ::arcrs::gp::api::GpToolMetadata {
+                    summary: #summary.to_string(),
+                    usage: vec![#(#usage.to_string()),*],
+                    tags: vec![#(#tags.to_string()),*],
+                    parameter_explanations,
+                    code_samples: vec![#(#code_samples),*]
+                }
+            }
+
+            #category_fn
+        }
+    };
+
+    Ok(tokens)
+}
+
+/// Parses the #[gptool(...)] attribute of the struct.
+fn parse_tool_attributes(derive_input: &DeriveInput) -> syn::Result<ToolAttributes> {
+    let mut tool_attributes = ToolAttributes {
+        name: None,
+        label: None,
+        description: String::new(),
+        summary: None,
+        category: None,
+        usage: Vec::new(),
+        tags: Vec::new(),
+        code_samples: Vec::new()
+    };
+    for nested_meta in attribute_metas(&derive_input.attrs, "gptool")? {
+        match &nested_meta {
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("name") => {
+                tool_attributes.name = Some(lit_as_string(&name_value.lit)?);
+            },
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("category") => {
+                tool_attributes.category = Some(lit_as_string(&name_value.lit)?);
+            },
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("label") => {
+                tool_attributes.label = Some(lit_as_string(&name_value.lit)?);
+            },
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("description") => {
+                tool_attributes.description = lit_as_string(&name_value.lit)?;
+            },
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("summary") => {
+                tool_attributes.summary = Some(lit_as_string(&name_value.lit)?);
+            },
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("usage") => {
+                tool_attributes.usage.push(lit_as_string(&name_value.lit)?);
+            },
+            NestedMeta::Meta(Meta::List(meta_list)) if meta_list.path.is_ident("tags") => {
+                for nested_value in &meta_list.nested {
+                    match nested_value {
+                        NestedMeta::Lit(lit) => tool_attributes.tags.push(lit_as_string(lit)?),
+                        _ => return Err(syn::Error::new_spanned(nested_value, "Expected a string literal!"))
+                    }
+                }
+            },
+            NestedMeta::Meta(Meta::List(meta_list)) if meta_list.path.is_ident("example") => {
+                tool_attributes.code_samples.push(parse_code_sample(meta_list)?);
+            },
+            _ => return Err(syn::Error::new_spanned(nested_meta, "Unknown gptool attribute!"))
+        }
+    }
+
+    Ok(tool_attributes)
+}
+
+/// Parses the #[gpparam(...)] attribute of a field.
+fn parse_parameter_attributes(field: &syn::Field) -> syn::Result<ParameterAttributes> {
+    let mut parameter_attributes = ParameterAttributes {
+        display_name: None,
+        name: None,
+        data_type: None,
+        parameter_type: None,
+        direction: None,
+        filter: None,
+        default_value: None,
+        explanation: None
+    };
+    for nested_meta in attribute_metas(&field.attrs, "gpparam")? {
+        match &nested_meta {
+            NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                let key = name_value.path.get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+                match key.as_str() {
+                    "display_name" => parameter_attributes.display_name = Some(lit_as_string(&name_value.lit)?),
+                    "name" => parameter_attributes.name = Some(lit_as_string(&name_value.lit)?),
+                    "data_type" => parameter_attributes.data_type = Some(lit_as_variant(&name_value.lit, DATA_TYPES)?),
+                    "parameter_type" => parameter_attributes.parameter_type = Some(lit_as_variant(&name_value.lit, &["Required", "Optional", "Derived"])?),
+                    "direction" => parameter_attributes.direction = Some(lit_as_variant(&name_value.lit, &["Input", "Output"])?),
+                    "default" => parameter_attributes.default_value = Some(lit_as_value_string(&name_value.lit)?),
+                    "explanation" => parameter_attributes.explanation = Some(lit_as_string(&name_value.lit)?),
+                    _ => return Err(syn::Error::new_spanned(nested_meta, "Unknown gpparam attribute!"))
+                }
+            },
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("optional") => {
+                parameter_attributes.parameter_type = Some(Ident::new("Optional", Span::call_site()));
+            },
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("derived") => {
+                parameter_attributes.parameter_type = Some(Ident::new("Derived", Span::call_site()));
+            },
+            NestedMeta::Meta(Meta::List(meta_list)) if meta_list.path.is_ident("value_list") || meta_list.path.is_ident("file") => {
+                let mut values = Vec::with_capacity(meta_list.nested.len());
+                for nested_value in &meta_list.nested {
+                    match nested_value {
+                        NestedMeta::Lit(lit) => values.push(lit_as_value_string(lit)?),
+                        _ => return Err(syn::Error::new_spanned(nested_value, "Expected a literal value!"))
+                    }
+                }
+                let filter = if meta_list.path.is_ident("file") {
+                    quote! { ::arcrs::gp::api::GpFilter::File(vec![#(#values.to_string()),*]) }
+                } else {
+                    quote! { ::arcrs::gp::api::GpFilter::ValueList(vec![#(#values.to_string()),*]) }
+                };
+                parameter_attributes.filter = Some(filter);
+            },
+            NestedMeta::Meta(Meta::List(meta_list)) if meta_list.path.is_ident("range") => {
+                let mut bounds = Vec::with_capacity(2);
+                for nested_value in &meta_list.nested {
+                    match nested_value {
+                        NestedMeta::Lit(lit) => bounds.push(lit_as_f64(lit)?),
+                        _ => return Err(syn::Error::new_spanned(nested_value, "Expected a numeric value!"))
+                    }
+                }
+                if 2 != bounds.len() {
+                    return Err(syn::Error::new_spanned(meta_list, "A range needs a minimum and a maximum value!"));
+                }
+                let (min, max) = (bounds[0], bounds[1]);
+                parameter_attributes.filter = Some(quote! { ::arcrs::gp::api::GpFilter::Range(#min, #max) });
+            },
+            _ => return Err(syn::Error::new_spanned(nested_meta, "Unknown gpparam attribute!"))
+        }
+    }
+
+    Ok(parameter_attributes)
+}
+
+/// Parses a code sample like #[gptool(example(title = "...", description = "...", code = "..."))].
+fn parse_code_sample(meta_list: &syn::MetaList) -> syn::Result<TokenStream2> {
+    let mut title = String::new();
+    let mut description = String::new();
+    let mut code = String::new();
+    for nested_value in &meta_list.nested {
+        match nested_value {
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("title") => title = lit_as_string(&name_value.lit)?,
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("description") => description = lit_as_string(&name_value.lit)?,
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("code") => code = lit_as_string(&name_value.lit)?,
+            _ => return Err(syn::Error::new_spanned(nested_value, "Unknown example attribute!"))
+        }
+    }
+
+    Ok(quote! {
+        ::arcrs::gp::api::GpCodeSample {
+            title: #title.to_string(),
+            description: #description.to_string(),
+            code: #code.to_string()
+        }
+    })
+}
+
+/// Returns the doc comments as one text or None if there are no doc comments.
+fn doc_comment(attributes: &[syn::Attribute]) -> Option<String> {
+    let mut lines = Vec::new();
+    for attribute in attributes {
+        if !attribute.path.is_ident("doc") {
+            continue;
+        }
+        if let Ok(Meta::NameValue(name_value)) = attribute.parse_meta() {
+            if let Lit::Str(lit_str) = name_value.lit {
+                lines.push(lit_str.value().trim().to_string());
+            }
+        }
+    }
+    let text = lines.join(" ").trim().to_string();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Returns the nested metas of all attributes having the specified name.
+fn attribute_metas(attributes: &[syn::Attribute], attribute_name: &str) -> syn::Result<Vec<NestedMeta>> {
+    let mut nested_metas = Vec::new();
+    for attribute in attributes {
+        if !attribute.path.is_ident(attribute_name) {
+            continue;
+        }
+        match attribute.parse_meta()? {
+            Meta::List(meta_list) => nested_metas.extend(meta_list.nested),
+            meta => return Err(syn::Error::new_spanned(meta, format!("Expected #[{}(...)]!", attribute_name)))
+        }
+    }
+
+    Ok(nested_metas)
+}
+
+/// Returns the inner type if the specified type is an Option.
+fn unwrap_option(field_type: &Type) -> Option<&Type> {
+    if let Type::Path(type_path) = field_type {
+        if let Some(last_segment) = type_path.path.segments.last() {
+            if last_segment.ident == "Option" {
+                if let syn::PathArguments::AngleBracketed(arguments) = &last_segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner_type)) = arguments.args.first() {
+                        return Some(inner_type);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Validates that the default value can be parsed into the numeric or boolean data type.
+fn validate_default_value(field: &syn::Field, data_type: &Ident, default_value: &str) -> syn::Result<()> {
+    let is_valid = match data_type.to_string().as_str() {
+        "GPDouble" => default_value.parse::<f64>().is_ok(),
+        "GPLong" => default_value.parse::<i64>().is_ok(),
+        "GPBoolean" => default_value.parse::<bool>().is_ok(),
+        _ => true
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(field, format!("The default value '{}' is not a valid {}!", default_value, data_type)))
+    }
+}
+
+/// Infers the data type from primitive field types.
+fn infer_data_type(field_type: &Type) -> syn::Result<Ident> {
+    if let Type::Path(type_path) = field_type {
+        if let Some(last_segment) = type_path.path.segments.last() {
+            let data_type = match last_segment.ident.to_string().as_str() {
+                "String" => Some("GPString"),
+                "f64" | "f32" => Some("GPDouble"),
+                "i64" | "i32" | "i16" => Some("GPLong"),
+                "bool" => Some("GPBoolean"),
+                _ => None
+            };
+            if let Some(data_type) = data_type {
+                return Ok(Ident::new(data_type, Span::call_site()));
+            }
+        }
+    }
+
+    Err(syn::Error::new_spanned(field_type, "Cannot infer the data type, use #[gpparam(data_type = \"...\")]!"))
+}
+
+fn lit_as_string(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(lit_str) => Ok(lit_str.value()),
+        _ => Err(syn::Error::new_spanned(lit, "Expected a string literal!"))
+    }
+}
+
+fn lit_as_ident(lit: &Lit) -> syn::Result<Ident> {
+    match lit {
+        Lit::Str(lit_str) => lit_str.parse(),
+        _ => Err(syn::Error::new_spanned(lit, "Expected a string literal!"))
+    }
+}
+
+fn lit_as_variant(lit: &Lit, variants: &[&str]) -> syn::Result<Ident> {
+    let ident = lit_as_ident(lit)?;
+    if variants.iter().any(|variant| ident == variant) {
+        Ok(ident)
+    } else {
+        Err(syn::Error::new_spanned(lit, format!("Expected one of {}!", variants.join(", "))))
+    }
+}
+
+/// Returns the value of a string, numeric or boolean literal as text.
+fn lit_as_value_string(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(lit_str) => Ok(lit_str.value()),
+        Lit::Int(lit_int) => Ok(lit_int.base10_digits().to_string()),
+        Lit::Float(lit_float) => Ok(lit_float.base10_digits().to_string()),
+        Lit::Bool(lit_bool) => Ok(lit_bool.value.to_string()),
+        _ => Err(syn::Error::new_spanned(lit, "Expected a string, numeric or boolean literal!"))
+    }
+}
+
+fn lit_as_f64(lit: &Lit) -> syn::Result<f64> {
+    match lit {
+        Lit::Int(lit_int) => lit_int.base10_parse(),
+        Lit::Float(lit_float) => lit_float.base10_parse(),
+        Lit::Str(lit_str) => lit_str.value().parse().map_err(|_| syn::Error::new_spanned(lit, "Expected a numeric value!")),
+        _ => Err(syn::Error::new_spanned(lit, "Expected a numeric value!"))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn expand(tokens: TokenStream2) -> syn::Result<String> {
+        let derive_input: DeriveInput = syn::parse2(tokens)?;
+        expand_gptool(&derive_input).map(|tokens| tokens.to_string())
+    }
+
+    fn expand_error(tokens: TokenStream2) -> String {
+        match expand(tokens) {
+            Ok(_) => panic!("Expansion must fail!"),
+            Err(err) => err.to_string()
+        }
+    }
+
+    #[test]
+    fn infer_every_field_type() {
+        let expanded = expand(quote! {
+            #[gptool(label = "All types")]
+            struct AllTypesTool {
+                text: String,
+                double: f64,
+                single: f32,
+                long: i64,
+                integer: i32,
+                short: i16,
+                flag: bool,
+                optional_text: Option<String>,
+                #[gpparam(data_type = "GPFeatureLayer")]
+                in_features: CatalogPath
+            }
+        }).unwrap();
+        for (name, data_type) in &[("text", "GPString"), ("double", "GPDouble"), ("single", "GPDouble"), ("long", "GPLong"),
+            ("integer", "GPLong"), ("short", "GPLong"), ("flag", "GPBoolean"), ("optional_text", "GPString"), ("in_features", "GPFeatureLayer")] {
+            let builder = format!(". with_name (\"{}\") . with_data_type (:: arcrs :: gp :: api :: DataType :: {})", name, data_type);
+            assert!(expanded.contains(&builder), "Data type of {} is wrong!", name);
+        }
+        assert!(expanded.contains("with_name (\"optional_text\") . with_data_type (:: arcrs :: gp :: api :: DataType :: GPString) . with_parameter_type (:: arcrs :: gp :: api :: ParameterType :: Optional)"), "Option must be optional!");
+        assert!(expanded.contains("with_name (\"text\") . with_data_type (:: arcrs :: gp :: api :: DataType :: GPString) . with_parameter_type (:: arcrs :: gp :: api :: ParameterType :: Required)"), "Text must be required!");
+        assert!(expanded.contains("short : :: arcrs :: gp :: api :: FromParameterValue :: from_parameter_value (parameters . get (\"short\") ?) ?"), "Extraction is wrong!");
+
+        let err = expand_error(quote! {
+            #[gptool(label = "Unknown type")]
+            struct UnknownTypeTool {
+                count: u8
+            }
+        });
+        assert!(err.contains("Cannot infer the data type"), "Error is wrong!");
+    }
+
+    #[test]
+    fn expand_tool_attributes() {
+        let expanded = expand(quote! {
+            /// Buffers features.
+            #[gptool(name = "BufferFeatures", label = "Buffer features", category = "Proximity", description = "Buffers features using a distance.",
+                usage = "The distance is measured in meters.", tags("buffer", "proximity"),
+                example(title = "Buffer", description = "Buffers roads.", code = "arcpy.rust.BufferFeatures('roads', 100)"))]
+            struct BufferTool {
+                distance: f64
+            }
+        }).unwrap();
+        assert!(expanded.contains("fn name (& self) -> :: std :: string :: String { \"BufferFeatures\" . to_string () }"), "Name is wrong!");
+        assert!(expanded.contains("fn label (& self) -> & str { \"Buffer features\" }"), "Label is wrong!");
+        assert!(expanded.contains("fn description (& self) -> & str { \"Buffers features using a distance.\" }"), "Description is wrong!");
+        assert!(expanded.contains(":: std :: option :: Option :: Some (\"Proximity\")"), "Category is wrong!");
+        assert!(expanded.contains("summary : \"Buffers features.\" . to_string ()"), "Summary is wrong!");
+        assert!(expanded.contains("usage : vec ! [\"The distance is measured in meters.\" . to_string ()]"), "Usage is wrong!");
+        assert!(expanded.contains("tags : vec ! [\"buffer\" . to_string () , \"proximity\" . to_string ()]"), "Tags are wrong!");
+        assert!(expanded.contains("title : \"Buffer\" . to_string () , description : \"Buffers roads.\" . to_string () , code : \"arcpy.rust.BufferFeatures('roads', 100)\" . to_string ()"), "Code sample is wrong!");
+
+        let err = expand_error(quote! {
+            struct UnlabeledTool {
+                distance: f64
+            }
+        });
+        assert!(err.contains("Missing #[gptool(label"), "Error is wrong!");
+
+        let err = expand_error(quote! {
+            #[gptool(label = "Unknown", icon = "buffer.png")]
+            struct UnknownAttributeTool {
+                distance: f64
+            }
+        });
+        assert!(err.contains("Unknown gptool attribute"), "Error is wrong!");
+    }
+
+    #[test]
+    fn expand_parameter_attributes() {
+        let expanded = expand(quote! {
+            #[gptool(label = "Parameters")]
+            struct ParametersTool {
+                /// The distance in meters.
+                #[gpparam(display_name = "Distance", name = "buffer_distance", range(0, 1000), default = 100)]
+                distance: f64,
+
+                #[gpparam(value_list("PLANAR", "GEODESIC"), default = "PLANAR", explanation = "The method.")]
+                method: String,
+
+                #[gpparam(file("csv", "txt"), optional)]
+                in_file: Option<String>,
+
+                #[gpparam(data_type = "DEFeatureClass", parameter_type = "Required", direction = "Output")]
+                out_features: CatalogPath,
+
+                #[gpparam(derived, data_type = "GPLong")]
+                out_count: i64,
+
+                #[gpparam(default = true)]
+                overwrite: bool
+            }
+        }).unwrap();
+        assert!(expanded.contains(". with_display_name (\"Distance\") . with_name (\"buffer_distance\")"), "Name is wrong!");
+        assert!(expanded.contains(". with_filter (:: arcrs :: gp :: api :: GpFilter :: Range (0f64 , 1000f64)) . with_default_value (\"100\")"), "Range is wrong!");
+        assert!(expanded.contains("GpFilter :: ValueList (vec ! [\"PLANAR\" . to_string () , \"GEODESIC\" . to_string ()])) . with_default_value (\"PLANAR\")"), "Value list is wrong!");
+        assert!(expanded.contains("GpFilter :: File (vec ! [\"csv\" . to_string () , \"txt\" . to_string ()])"), "File filter is wrong!");
+        assert!(expanded.contains("with_name (\"in_file\") . with_data_type (:: arcrs :: gp :: api :: DataType :: GPString) . with_parameter_type (:: arcrs :: gp :: api :: ParameterType :: Optional)"), "Optional is wrong!");
+        assert!(expanded.contains("ParameterType :: Required) . with_direction (:: arcrs :: gp :: api :: Direction :: Output)"), "Direction is wrong!");
+        assert!(expanded.contains("ParameterType :: Derived) . with_direction (:: arcrs :: gp :: api :: Direction :: Output)"), "Derived must be an output!");
+        assert!(expanded.contains("out_count : :: std :: default :: Default :: default ()"), "Derived value must not be extracted!");
+        assert!(expanded.contains(". with_default_value (\"true\")"), "Boolean default is wrong!");
+        assert!(expanded.contains("parameter_explanations . insert (\"buffer_distance\" . to_string () , \"The distance in meters.\" . to_string ())"), "Doc comment is wrong!");
+        assert!(expanded.contains("parameter_explanations . insert (\"method\" . to_string () , \"The method.\" . to_string ())"), "Explanation is wrong!");
+    }
+
+    #[test]
+    fn reject_invalid_parameter_attributes() {
+        let err = expand_error(quote! {
+            #[gptool(label = "Invalid")]
+            struct InvalidTool {
+                #[gpparam(default = "far")]
+                distance: f64
+            }
+        });
+        assert!(err.contains("The default value 'far' is not a valid GPDouble"), "Error is wrong!");
+
+        let err = expand_error(quote! {
+            #[gptool(label = "Invalid")]
+            struct InvalidTool {
+                #[gpparam(default = 2.5)]
+                count: i32
+            }
+        });
+        assert!(err.contains("The default value '2.5' is not a valid GPLong"), "Error is wrong!");
+
+        let err = expand_error(quote! {
+            #[gptool(label = "Invalid")]
+            struct InvalidTool {
+                #[gpparam(direction = "Inout")]
+                count: i32
+            }
+        });
+        assert!(err.contains("Expected one of Input, Output"), "Error is wrong!");
+
+        let err = expand_error(quote! {
+            #[gptool(label = "Invalid")]
+            struct InvalidTool {
+                #[gpparam(data_type = "GPFeatureLayr")]
+                in_features: String
+            }
+        });
+        assert!(err.contains("Expected one of DEFeatureClass, GPFeatureLayer"), "Error is wrong!");
+
+        let err = expand_error(quote! {
+            #[gptool(label = "Invalid")]
+            struct InvalidTool {
+                #[gpparam(range(0))]
+                distance: f64
+            }
+        });
+        assert!(err.contains("A range needs a minimum and a maximum value"), "Error is wrong!");
+
+        let err = expand_error(quote! {
+            #[gptool(label = "Invalid")]
+            struct InvalidTool(f64);
+        });
+        assert!(err.contains("named fields"), "Error is wrong!");
+    }
+}
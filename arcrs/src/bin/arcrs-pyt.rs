@@ -0,0 +1,125 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Generates a Python toolbox (.pyt) exposing all tools of a native module.
+//! The toolbox and tool metadata (.pyt.xml) are generated next to the Python toolbox.
+//! The native module is imported using an embedded Python interpreter.
+//!
+//! arcrs-pyt <module> <toolbox label> <toolbox alias> <output.pyt> [--package <name>] [--path <dir>]
+
+use arcrs::gp::{metadata, pyt};
+use pyo3::prelude::*;
+use std::path::Path;
+use std::process;
+
+const USAGE: &str = "Usage: arcrs-pyt <module> <toolbox label> <toolbox alias> <output.pyt> [--package <name>] [--path <dir>]";
+
+/// Represents the command line arguments.
+struct PytArguments {
+    module_name: String,
+    label: String,
+    alias: String,
+    output_path: String,
+    package_name: Option<String>,
+    module_dir: Option<String>
+}
+
+fn parse_arguments(arguments: Vec<String>) -> Result<PytArguments, String> {
+    let mut positionals = Vec::with_capacity(4);
+    let mut package_name = None;
+    let mut module_dir = None;
+    let mut argument_iter = arguments.into_iter();
+    while let Some(argument) = argument_iter.next() {
+        match argument.as_str() {
+            "--package" => package_name = Some(argument_iter.next().ok_or("Missing value for --package!")?),
+            "--path" => module_dir = Some(argument_iter.next().ok_or("Missing value for --path!")?),
+            _ => positionals.push(argument)
+        }
+    }
+    if 4 != positionals.len() {
+        return Err(USAGE.to_string());
+    }
+
+    let mut positional_iter = positionals.into_iter();
+    Ok(PytArguments {
+        module_name: positional_iter.next().unwrap(),
+        label: positional_iter.next().unwrap(),
+        alias: positional_iter.next().unwrap(),
+        output_path: positional_iter.next().unwrap(),
+        package_name,
+        module_dir
+    })
+}
+
+/// Creates the toolbox using the native module and generates the Python toolbox and its metadata.
+/// Returns the path and content of every file.
+fn generate(py: Python, pyt_arguments: &PytArguments) -> PyResult<Vec<(String, String)>> {
+    if let Some(module_dir) = &pyt_arguments.module_dir {
+        let sys_path = py.import("sys")?.getattr("path")?;
+        sys_path.call_method1("insert", (0, module_dir))?;
+    }
+
+    let native_module = py.import(&pyt_arguments.module_name)?;
+    let py_toolbox = native_module.call1("create_toolbox", (&pyt_arguments.label, &pyt_arguments.alias))?;
+    let mut definition = pyt::PytToolboxDefinition::from_pytoolbox(&pyt_arguments.module_name, py_toolbox)?;
+    if let Some(package_name) = &pyt_arguments.package_name {
+        definition = definition.with_package_name(package_name);
+    }
+
+    // The metadata files are named by using the toolbox name and the tool names
+    let output_path = Path::new(&pyt_arguments.output_path);
+    let output_dir = output_path.parent().unwrap_or_else(|| Path::new(""));
+    let toolbox_name = output_path.file_stem().and_then(|file_stem| file_stem.to_str()).unwrap_or("toolbox");
+    let as_output_path = |file_name: String| output_dir.join(file_name).to_string_lossy().to_string();
+
+    let mut files = vec![(pyt_arguments.output_path.to_string(), pyt::generate_pyt(&definition))];
+    files.push((as_output_path(format!("{}.pyt.xml", toolbox_name)), metadata::generate_toolbox_xml(toolbox_name, &definition)));
+    for (tool_index, tool) in definition.tools.iter().enumerate() {
+        let tool_xml = py_toolbox.call_method1("tool_metadata_xml", (tool_index, &tool.name))?.extract()?;
+        files.push((as_output_path(format!("{}.{}.pyt.xml", toolbox_name, tool.name)), tool_xml));
+    }
+
+    Ok(files)
+}
+
+fn main() {
+    let pyt_arguments = match parse_arguments(std::env::args().skip(1).collect()) {
+        Ok(pyt_arguments) => pyt_arguments,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    };
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    match generate(py, &pyt_arguments) {
+        Ok(files) => {
+            for (file_path, content) in files {
+                if let Err(err) = std::fs::write(&file_path, content) {
+                    eprintln!("Failed to write {}: {}", file_path, err);
+                    process::exit(1);
+                }
+                println!("Generated {}", file_path);
+            }
+        },
+        Err(err) => {
+            err.print(py);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Executes the tools of a native module outside the tool dialog.
+//! The parameter values are read from a JSON or TOML file using the parameter names as keys.
+//! The native module and arcpy are imported using an embedded Python interpreter.
+//!
+//! arcrs-run <module> list [--path <dir>] [--mock-arcpy]
+//! arcrs-run <module> validate <tool name> <parameters.json|parameters.toml> [--path <dir>] [--mock-arcpy]
+//! arcrs-run <module> execute <tool name> <parameters.json|parameters.toml> [--path <dir>] [--mock-arcpy]
+//!
+//! The option --mock-arcpy uses the in-memory arcpy stand-in and needs the cargo feature `testing`.
+//! The exit code is 0 on success, 1 if the tool failed, 2 if the arguments or the parameter file are invalid
+//! and 3 if the parameter values are not valid for the tool.
+
+use arcrs::gp::runner::{self, ParameterFile, ToolRunner};
+use pyo3::prelude::*;
+use std::process;
+
+const USAGE: &str = "Usage: arcrs-run <module> list|validate|execute [<tool name> <parameter file>] [--path <dir>] [--mock-arcpy]";
+
+const EXIT_FAILED: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_INVALID: i32 = 3;
+
+/// Represents the command being executed.
+enum RunCommand {
+    List,
+    Validate(String, ParameterFile),
+    Execute(String, ParameterFile)
+}
+
+/// Represents the command line arguments.
+/// The parameter file is already loaded, so that an invalid file is reported before the interpreter starts.
+struct RunArguments {
+    module_name: String,
+    command: RunCommand,
+    module_dir: Option<String>,
+    mock_arcpy: bool
+}
+
+fn parse_arguments(arguments: Vec<String>) -> Result<RunArguments, String> {
+    let mut positionals = Vec::with_capacity(4);
+    let mut module_dir = None;
+    let mut mock_arcpy = false;
+    let mut argument_iter = arguments.into_iter();
+    while let Some(argument) = argument_iter.next() {
+        match argument.as_str() {
+            "--path" => module_dir = Some(argument_iter.next().ok_or("Missing value for --path!")?),
+            "--mock-arcpy" => mock_arcpy = true,
+            _ => positionals.push(argument)
+        }
+    }
+
+    let mut positional_iter = positionals.into_iter();
+    let module_name = positional_iter.next().ok_or(USAGE)?;
+    let command = match (positional_iter.next().as_deref(), positional_iter.next(), positional_iter.next(), positional_iter.next()) {
+        (Some("list"), None, None, None) => RunCommand::List,
+        (Some("validate"), Some(tool_name), Some(file_path), None) => RunCommand::Validate(tool_name, ParameterFile::load(file_path)?),
+        (Some("execute"), Some(tool_name), Some(file_path), None) => RunCommand::Execute(tool_name, ParameterFile::load(file_path)?),
+        _ => return Err(USAGE.to_string())
+    };
+
+    Ok(RunArguments {
+        module_name,
+        command,
+        module_dir,
+        mock_arcpy
+    })
+}
+
+/// Installs the in-memory arcpy stand-in.
+#[cfg(feature = "testing")]
+fn install_mock_arcpy(py: Python) -> PyResult<()> {
+    arcrs::gp::testing::MockArcpy::install(py)?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "testing"))]
+fn install_mock_arcpy(_py: Python) -> PyResult<()> {
+    Err(pyo3::exceptions::PyValueError::new_err("The runner must be built using the cargo feature 'testing' for --mock-arcpy!"))
+}
+
+/// Prints all tools and their parameters.
+fn list(tool_runner: &ToolRunner) -> PyResult<i32> {
+    for (tool_index, tool_name) in tool_runner.tool_names()?.iter().enumerate() {
+        println!("{} ({})", tool_name, tool_runner.tool_label(tool_index)?);
+        for gp_parameter in tool_runner.parameters(tool_index)? {
+            println!("    {}: {} {} {}", gp_parameter.name(), gp_parameter.data_type().as_str(),
+                gp_parameter.parameter_type().as_str(), gp_parameter.direction().as_str());
+        }
+    }
+
+    Ok(0)
+}
+
+/// Validates the parameter file and prints every error.
+fn validate(tool_runner: &ToolRunner, tool_name: &str, parameter_file: &ParameterFile) -> PyResult<i32> {
+    let tool_index = tool_runner.tool_index(tool_name)?;
+    let errors = tool_runner.validate(tool_index, parameter_file)?;
+    for error in &errors {
+        eprintln!("ERROR: {}", error);
+    }
+
+    Ok(if errors.is_empty() { 0 } else { EXIT_INVALID })
+}
+
+/// Validates the parameter file, executes the tool and prints its messages and outputs.
+fn execute(py: Python, tool_runner: &ToolRunner, tool_name: &str, parameter_file: &ParameterFile) -> PyResult<i32> {
+    let exit_code = validate(tool_runner, tool_name, parameter_file)?;
+    if 0 != exit_code {
+        return Ok(exit_code);
+    }
+
+    let run_result = tool_runner.execute(tool_runner.tool_index(tool_name)?, parameter_file)?;
+    for (severity, message) in &run_result.messages {
+        println!("{}: {}", runner::severity_as_str(*severity), message);
+    }
+    if let Some(err) = &run_result.error {
+        err.print(py);
+    }
+    for (name, value) in &run_result.outputs {
+        println!("OUTPUT {} = {}", name, value.as_deref().unwrap_or(""));
+    }
+
+    Ok(if run_result.is_ok() { 0 } else { EXIT_FAILED })
+}
+
+fn run(py: Python, run_arguments: &RunArguments) -> PyResult<i32> {
+    if let Some(module_dir) = &run_arguments.module_dir {
+        let sys_path = py.import("sys")?.getattr("path")?;
+        sys_path.call_method1("insert", (0, module_dir))?;
+    }
+    if run_arguments.mock_arcpy {
+        install_mock_arcpy(py)?;
+    }
+
+    let tool_runner = ToolRunner::from_module(py, &run_arguments.module_name)?;
+    match &run_arguments.command {
+        RunCommand::List => list(&tool_runner),
+        RunCommand::Validate(tool_name, parameter_file) => validate(&tool_runner, tool_name, parameter_file),
+        RunCommand::Execute(tool_name, parameter_file) => execute(py, &tool_runner, tool_name, parameter_file)
+    }
+}
+
+fn main() {
+    let run_arguments = match parse_arguments(std::env::args().skip(1).collect()) {
+        Ok(run_arguments) => run_arguments,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(EXIT_USAGE);
+        }
+    };
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    match run(py, &run_arguments) {
+        Ok(exit_code) => process::exit(exit_code),
+        Err(err) => {
+            err.print(py);
+            process::exit(EXIT_FAILED);
+        }
+    }
+}
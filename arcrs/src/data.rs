@@ -0,0 +1,743 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Offers data access being independent from arcpy.
+//! Tool logic being written against the DataSource trait runs inside ArcGIS Pro using the arcpy backend
+//! and in headless pipelines using a pure-Rust backend like the GeoJSON backend.
+
+pub mod arcpy;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+
+use crate::gp::api::{FieldToken, FieldType, GpField, ShapeType};
+use pyo3::exceptions::{PyOSError, PyValueError};
+use pyo3::{AsPyPointer, PyErr, PyObject, Python};
+#[cfg(feature = "geojson")]
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The field name token for reading the object ID, see `FieldToken::OID`.
+pub const OID_TOKEN: &str = FieldToken::OID.as_str();
+
+/// The field name token for reading and writing the geometry, see `FieldToken::Shape`.
+pub const SHAPE_TOKEN: &str = FieldToken::Shape.as_str();
+
+/// Represents a geometry using plain x and y coordinates.
+/// The coordinates use the spatial reference of the table, see `TableSchema::wkid`.
+/// Geometries having z-values, m-values or curves are represented by native values instead.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Geometry {
+    Point(f64, f64),
+    Multipoint(Vec<(f64, f64)>),
+    /// Represents the paths of a line.
+    Polyline(Vec<Vec<(f64, f64)>>),
+    /// Represents the rings of one or more polygons like arcpy does.
+    /// Exterior rings are clockwise and interior rings are counterclockwise.
+    Polygon(Vec<Vec<(f64, f64)>>)
+}
+
+impl Geometry {
+
+    /// Returns the shape type of this geometry.
+    pub fn shape_type(&self) -> ShapeType {
+        match self {
+            Geometry::Point(_, _) => ShapeType::Point,
+            Geometry::Multipoint(_) => ShapeType::Multipoint,
+            Geometry::Polyline(_) => ShapeType::Polyline,
+            Geometry::Polygon(_) => ShapeType::Polygon
+        }
+    }
+}
+
+
+
+/// Represents a value of a row.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Integer(i64),
+    Double(f64),
+    Text(String),
+    /// Represents a date, a time or a date and time using ISO 8601 like "2021-06-01T12:30:00".
+    Date(String),
+    Geometry(Geometry),
+    /// Represents a value of the data source having no plain counterpart like a geometry having z-values.
+    /// Native values are passed through unchanged when being written into the same kind of data source.
+    Native(NativeValue)
+}
+
+impl Value {
+
+    /// Returns whether or not this value is null.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Compares numbers, texts and dates, null, geometries and native values are not comparable.
+    #[cfg(feature = "geojson")]
+    fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Integer(value), Value::Integer(other_value)) => value.partial_cmp(other_value),
+            (Value::Integer(value), Value::Double(other_value)) => (*value as f64).partial_cmp(other_value),
+            (Value::Double(value), Value::Integer(other_value)) => value.partial_cmp(&(*other_value as f64)),
+            (Value::Double(value), Value::Double(other_value)) => value.partial_cmp(other_value),
+            (Value::Text(text), Value::Text(other_text)) => text.partial_cmp(other_text),
+            (Value::Date(date), Value::Date(other_date)) | (Value::Date(date), Value::Text(other_date)) => date.partial_cmp(other_date),
+            _ => None
+        }
+    }
+}
+
+impl From<&str> for Value {
+
+    fn from(text: &str) -> Value {
+        Value::Text(text.to_string())
+    }
+}
+
+impl From<String> for Value {
+
+    fn from(text: String) -> Value {
+        Value::Text(text)
+    }
+}
+
+impl From<i32> for Value {
+
+    fn from(value: i32) -> Value {
+        Value::Integer(value as i64)
+    }
+}
+
+impl From<i64> for Value {
+
+    fn from(value: i64) -> Value {
+        Value::Integer(value)
+    }
+}
+
+impl From<f64> for Value {
+
+    fn from(value: f64) -> Value {
+        Value::Double(value)
+    }
+}
+
+impl From<Geometry> for Value {
+
+    fn from(geometry: Geometry) -> Value {
+        Value::Geometry(geometry)
+    }
+}
+
+/// Represents an opaque Python object like an arcpy geometry.
+/// Native values are equal if they are the same object.
+#[derive(Clone)]
+pub struct NativeValue {
+    pyobject: PyObject
+}
+
+impl NativeValue {
+
+    pub fn new(pyobject: PyObject) -> NativeValue {
+        NativeValue {
+            pyobject
+        }
+    }
+
+    pub fn as_object(&self) -> &PyObject {
+        &self.pyobject
+    }
+}
+
+impl PartialEq for NativeValue {
+
+    fn eq(&self, other: &NativeValue) -> bool {
+        self.pyobject.as_ptr() == other.pyobject.as_ptr()
+    }
+}
+
+impl fmt::Debug for NativeValue {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NativeValue({:p})", self.pyobject.as_ptr())
+    }
+}
+
+/// Represents the values of a row ordered like the requested field names.
+/// Rows are owned and Send, so that they can be processed while the GIL is released.
+pub type Row = Vec<Value>;
+
+
+
+/// Represents the schema of a table or feature class.
+/// The object ID and the shape field are not part of the fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableSchema {
+    pub shape_type: Option<ShapeType>,
+    pub wkid: i32,
+    pub fields: Vec<GpField>
+}
+
+impl TableSchema {
+
+    /// Creates a new schema of a feature class.
+    pub fn features(shape_type: ShapeType, wkid: i32) -> TableSchema {
+        TableSchema {
+            shape_type: Some(shape_type),
+            wkid,
+            fields: Vec::new()
+        }
+    }
+
+    /// Creates a new schema of a table without a shape.
+    pub fn table() -> TableSchema {
+        TableSchema {
+            shape_type: None,
+            wkid: 0,
+            fields: Vec::new()
+        }
+    }
+
+    /// Adds a new field.
+    pub fn with_field(mut self, name: &str, field_type: FieldType) -> TableSchema {
+        self.fields.push(GpField {
+            name: name.to_string(),
+            field_type
+        });
+        self
+    }
+
+    /// Returns the field having the specified name.
+    pub fn field(&self, name: &str) -> Option<&GpField> {
+        self.fields.iter().find(|field| field.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the shape token followed by all field names.
+    pub fn field_names(&self) -> Vec<String> {
+        let mut field_names = Vec::with_capacity(self.fields.len() + 1);
+        if self.shape_type.is_some() {
+            field_names.push(SHAPE_TOKEN.to_string());
+        }
+        field_names.extend(self.fields.iter().map(|field| field.name.to_string()));
+
+        field_names
+    }
+}
+
+
+
+/// Represents the errors of a data source.
+#[derive(Debug)]
+pub enum DataError {
+    /// The Python environment like arcpy raised an error.
+    Python(PyErr),
+    Io(std::io::Error),
+    /// The table does not exist.
+    NotFound(String),
+    /// The request or the data is not valid.
+    Invalid(String)
+}
+
+impl fmt::Display for DataError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataError::Python(py_err) => write!(f, "{:?}", py_err),
+            DataError::Io(io_err) => write!(f, "{}", io_err),
+            DataError::NotFound(table) => write!(f, "Table '{}' does not exist!", table),
+            DataError::Invalid(message) => write!(f, "{}", message)
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+impl From<PyErr> for DataError {
+
+    fn from(py_err: PyErr) -> DataError {
+        DataError::Python(py_err)
+    }
+}
+
+impl From<std::io::Error> for DataError {
+
+    fn from(io_err: std::io::Error) -> DataError {
+        DataError::Io(io_err)
+    }
+}
+
+impl From<DataError> for PyErr {
+
+    fn from(data_err: DataError) -> PyErr {
+        match data_err {
+            DataError::Python(py_err) => py_err,
+            DataError::Io(io_err) => PyOSError::new_err(io_err.to_string()),
+            _ => PyValueError::new_err(data_err.to_string())
+        }
+    }
+}
+
+pub type DataResult<T> = Result<T, DataError>;
+
+/// Represents the rows being returned by a search.
+pub type RowIterator<'a> = Box<dyn Iterator<Item = DataResult<Row>> + 'a>;
+
+
+
+/// Offers access to tables and feature classes.
+/// The tables are identified like the catalog paths of arcpy.
+/// The field names support the tokens "OID@" and "SHAPE@".
+/// All methods borrow the data source immutably, so that rows can be streamed from a search
+/// into an insert cursor of the same data source without collecting them.
+/// # Examples
+///
+/// ```ignore
+/// fn copy_features(data_source: &dyn DataSource, in_table: &str, out_table: &str) -> DataResult<usize> {
+///     let schema = data_source.describe(in_table)?;
+///     data_source.create_table(out_table, &schema)?;
+///     let field_names = schema.field_names();
+///     let field_names: Vec<&str> = field_names.iter().map(|field_name| field_name.as_str()).collect();
+///     let mut inserter = data_source.insert_cursor(out_table, &field_names)?;
+///     let mut copy_count = 0;
+///     for row in data_source.search(in_table, &field_names, "")? {
+///         inserter.insert_row(row?)?;
+///         copy_count += 1;
+///     }
+///     inserter.finish()?;
+///     Ok(copy_count)
+/// }
+/// ```
+pub trait DataSource {
+
+    /// Checks whether or not the specified table exists.
+    fn exists(&self, table: &str) -> DataResult<bool>;
+
+    /// Returns the schema of the specified table.
+    fn describe(&self, table: &str) -> DataResult<TableSchema>;
+
+    /// Creates a new empty table using the specified schema.
+    fn create_table(&self, table: &str, schema: &TableSchema) -> DataResult<()>;
+
+    /// Returns all rows matching the where clause.
+    /// An empty where clause matches all rows.
+    /// The underlying cursor is released when the iterator is dropped.
+    fn search<'a>(&'a self, table: &str, field_names: &[&str], where_clause: &str) -> DataResult<RowIterator<'a>>;
+
+    /// Opens one insert cursor for writing rows in order.
+    /// The rows are only guaranteed to be written after the inserter was finished.
+    fn insert_cursor<'a>(&'a self, table: &str, field_names: &[&str]) -> DataResult<Box<dyn RowInserter + 'a>>;
+
+    /// Inserts the specified rows and returns their object IDs.
+    fn insert(&self, table: &str, field_names: &[&str], rows: Vec<Row>) -> DataResult<Vec<i64>> {
+        let mut inserter = self.insert_cursor(table, field_names)?;
+        let mut oids = Vec::with_capacity(rows.len());
+        for row in rows {
+            oids.push(inserter.insert_row(row)?);
+        }
+        inserter.finish()?;
+
+        Ok(oids)
+    }
+
+    /// Updates all rows matching the where clause.
+    /// The rows are only written back if the update function returns true.
+    /// Returns the number of updated rows.
+    fn update(&self, table: &str, field_names: &[&str], where_clause: &str, update_row: &mut dyn FnMut(&mut Row) -> bool) -> DataResult<usize>;
+}
+
+
+/// Represents an open insert cursor of a data source.
+/// An inserter being dropped without being finished releases the cursor and may discard pending rows.
+pub trait RowInserter {
+
+    /// Inserts the row and returns its object ID.
+    fn insert_row(&mut self, row: Row) -> DataResult<i64>;
+
+    /// Writes all pending rows and releases the cursor.
+    fn finish(self: Box<Self>) -> DataResult<()>;
+}
+
+
+/// Reads the matching rows into owned rows and runs the computation while the GIL is released.
+/// The computation cannot access Python, so that ArcGIS Pro and other Python threads keep running.
+/// The results are written using the data source afterwards while holding the GIL again.
+/// # Examples
+///
+/// ```ignore
+/// let buffers = data::compute_without_gil(py, &data_source, &in_features, &["SHAPE@"], "", |rows| {
+///     rows.into_iter().map(|row| vec![buffer(&row[0], 100.0)]).collect::<Vec<Row>>()
+/// })?;
+/// data_source.insert(&out_features, &["SHAPE@"], buffers)?;
+/// ```
+pub fn compute_without_gil<R, F>(py: Python, data_source: &dyn DataSource, table: &str, field_names: &[&str], where_clause: &str, compute: F) -> DataResult<R>
+where
+    R: Send,
+    F: Send + FnOnce(Vec<Row>) -> R
+{
+    let rows = data_source.search(table, field_names, where_clause)?.collect::<DataResult<Vec<Row>>>()?;
+
+    Ok(py.allow_threads(move || compute(rows)))
+}
+
+/// Reads the matching rows, updates the owned rows while the GIL is released and writes them back.
+/// The rows are matched by their object ID, so that the computation may reorder or remove rows.
+/// Removed rows are not written back.
+/// Returns the number of updated rows.
+pub fn update_without_gil<F>(py: Python, data_source: &dyn DataSource, table: &str, field_names: &[&str], where_clause: &str, compute: F) -> DataResult<usize>
+where
+    F: Send + FnOnce(&mut Vec<(i64, Row)>)
+{
+    let mut oid_field_names = Vec::with_capacity(field_names.len() + 1);
+    oid_field_names.push(OID_TOKEN);
+    oid_field_names.extend_from_slice(field_names);
+
+    let mut rows = Vec::new();
+    for row in data_source.search(table, &oid_field_names, where_clause)? {
+        let mut row = row?;
+        match row.remove(0) {
+            Value::Integer(oid) => rows.push((oid, row)),
+            oid => return Err(DataError::Invalid(format!("Object ID {:?} is not valid!", oid)))
+        }
+    }
+
+    let mut updated_rows: HashMap<i64, Row> = py.allow_threads(move || {
+        compute(&mut rows);
+        rows.into_iter().collect()
+    });
+    data_source.update(table, &oid_field_names, where_clause, &mut |row| {
+        let oid = match row.first() {
+            Some(Value::Integer(oid)) => *oid,
+            _ => return false
+        };
+        match updated_rows.remove(&oid) {
+            Some(updated_row) => {
+                row.truncate(1);
+                row.extend(updated_row);
+                true
+            },
+            None => false
+        }
+    })
+}
+
+
+
+/// Returns the field type being reported by arcpy.Describe.
+/// Single and SmallInteger are mapped to Double and Integer, all other arcpy field types have their own field type.
+pub(crate) fn field_type_from_arcpy(field_type_as_text: &str) -> Option<FieldType> {
+    match field_type_as_text {
+        "Single" => Some(FieldType::Double),
+        "SmallInteger" => Some(FieldType::Integer),
+        _ => field_type_as_text.parse().ok()
+    }
+}
+
+/// Splits a catalog path like "c:/data/test.gdb/roads" into workspace and name.
+pub(crate) fn split_catalog_path(catalog_path: &str) -> (&str, &str) {
+    match catalog_path.rfind(['/', '\\']) {
+        Some(separator_index) => (&catalog_path[..separator_index], &catalog_path[separator_index + 1..]),
+        None => ("", catalog_path)
+    }
+}
+
+
+
+/// Represents a comparison of a where clause.
+#[cfg(feature = "geojson")]
+#[derive(Copy, Clone)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    IsNull,
+    IsNotNull
+}
+
+/// Represents a simple where clause like "Name = 'Dessau' AND Population > 1000" for pure-Rust backends.
+/// Only comparisons of a field and a literal being combined by AND are supported.
+/// An empty where clause and "1=1" match all rows.
+#[cfg(feature = "geojson")]
+pub(crate) struct WhereClause {
+    conditions: Vec<(String, Comparison, Value)>
+}
+
+#[cfg(feature = "geojson")]
+impl WhereClause {
+
+    pub(crate) fn parse(where_clause: &str) -> DataResult<WhereClause> {
+        let mut conditions = Vec::new();
+        for condition in split_conditions(where_clause) {
+            let condition = condition.trim();
+            if condition.is_empty() || "1=1" == condition.replace(' ', "") {
+                continue;
+            }
+
+            let upper_condition = condition.to_ascii_uppercase();
+            if let Some(field_name) = upper_condition.strip_suffix(" IS NOT NULL") {
+                conditions.push((condition[..field_name.len()].trim().to_string(), Comparison::IsNotNull, Value::Null));
+                continue;
+            }
+            if let Some(field_name) = upper_condition.strip_suffix(" IS NULL") {
+                conditions.push((condition[..field_name.len()].trim().to_string(), Comparison::IsNull, Value::Null));
+                continue;
+            }
+
+            let operators = [("<>", Comparison::NotEqual), ("!=", Comparison::NotEqual), ("<=", Comparison::LessOrEqual),
+                (">=", Comparison::GreaterOrEqual), ("=", Comparison::Equal), ("<", Comparison::Less), (">", Comparison::Greater)];
+            let operator_position = operators.iter()
+                .filter_map(|(operator, _)| condition.find(operator).map(|position| (position, operator.len())))
+                .min_by_key(|(position, _)| *position);
+            let (position, operator_len) = match operator_position {
+                Some(operator_position) => operator_position,
+                None => return Err(DataError::Invalid(format!("Where clause '{}' is not supported!", where_clause)))
+            };
+            let operator = &condition[position..position + operator_len];
+            let comparison = operators.iter()
+                .find(|(known_operator, _)| *known_operator == operator)
+                .map(|(_, comparison)| *comparison)
+                .unwrap();
+            let literal = parse_literal(condition[position + operator_len..].trim())
+                .ok_or_else(|| DataError::Invalid(format!("Where clause '{}' is not supported!", where_clause)))?;
+            conditions.push((condition[..position].trim().to_string(), comparison, literal));
+        }
+
+        Ok(WhereClause {
+            conditions
+        })
+    }
+
+    /// Returns the names of all fields being compared.
+    pub(crate) fn field_names(&self) -> Vec<&str> {
+        self.conditions.iter().map(|(field_name, _, _)| field_name.as_str()).collect()
+    }
+
+    /// Checks whether or not the row matches using the specified field accessor.
+    pub(crate) fn matches<'a, F: Fn(&str) -> Option<&'a Value>>(&self, field_value: F) -> bool {
+        self.conditions.iter().all(|(field_name, comparison, literal)| {
+            let value = field_value(field_name).unwrap_or(&Value::Null);
+            match comparison {
+                Comparison::IsNull => value.is_null(),
+                Comparison::IsNotNull => !value.is_null(),
+                Comparison::Equal => Some(Ordering::Equal) == value.compare(literal),
+                Comparison::NotEqual => matches!(value.compare(literal), Some(Ordering::Less) | Some(Ordering::Greater)),
+                Comparison::Less => Some(Ordering::Less) == value.compare(literal),
+                Comparison::LessOrEqual => matches!(value.compare(literal), Some(Ordering::Less) | Some(Ordering::Equal)),
+                Comparison::Greater => Some(Ordering::Greater) == value.compare(literal),
+                Comparison::GreaterOrEqual => matches!(value.compare(literal), Some(Ordering::Greater) | Some(Ordering::Equal))
+            }
+        })
+    }
+}
+
+/// Splits the where clause into the conditions being combined by AND.
+/// Quoted literals like 'Dessau AND Roßlau' are not split.
+#[cfg(feature = "geojson")]
+fn split_conditions(where_clause: &str) -> Vec<&str> {
+    let bytes = where_clause.as_bytes();
+    let mut conditions = Vec::new();
+    let mut in_literal = false;
+    let mut start = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        if b'\'' == bytes[index] {
+            // An escaped quote like 'It''s' leaves and enters the literal again
+            in_literal = !in_literal;
+        } else if !in_literal && bytes[index..].len() >= 5 && bytes[index..index + 5].eq_ignore_ascii_case(b" AND ") {
+            conditions.push(&where_clause[start..index]);
+            index += 5;
+            start = index;
+            continue;
+        }
+        index += 1;
+    }
+    conditions.push(&where_clause[start..]);
+
+    conditions
+}
+
+/// Parses a literal like 'Dessau', 42 or 3.14.
+#[cfg(feature = "geojson")]
+fn parse_literal(literal: &str) -> Option<Value> {
+    if literal.len() >= 2 && literal.starts_with('\'') && literal.ends_with('\'') {
+        return Some(Value::Text(literal[1..literal.len() - 1].replace("''", "'")));
+    }
+    if let Ok(value) = literal.parse::<i64>() {
+        return Some(Value::Integer(value));
+    }
+
+    literal.parse::<f64>().ok().map(Value::Double)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn match_where_clause() {
+        let where_clause = WhereClause::parse("Name = 'Dessau' and Population >= 70000").unwrap();
+        let name = Value::from("Dessau");
+        let population = Value::from(75000.0);
+        let field_value = |field_name: &str| match field_name {
+            "Name" => Some(&name),
+            "Population" => Some(&population),
+            _ => None
+        };
+        assert!(where_clause.matches(field_value), "Row must match!");
+        assert!(!WhereClause::parse("Name <> 'Dessau'").unwrap().matches(field_value), "Row must not match!");
+        assert!(WhereClause::parse("1=1").unwrap().matches(field_value), "Row must match!");
+        assert!(WhereClause::parse("Name LIKE 'D%'").is_err(), "Where clause must not be supported!");
+    }
+
+    /// Represents a data source holding one table having the object ID and the "Population" field in memory.
+    /// Only empty where clauses are supported.
+    struct MemoryDataSource {
+        rows: std::cell::RefCell<Vec<(i64, Row)>>
+    }
+
+    impl MemoryDataSource {
+
+        fn check_where_clause(where_clause: &str) -> DataResult<()> {
+            if !where_clause.is_empty() {
+                return Err(DataError::Invalid(format!("Where clause '{}' is not supported!", where_clause)));
+            }
+
+            Ok(())
+        }
+
+        fn values(&self, oid: i64, row: &[Value], field_names: &[&str]) -> Row {
+            field_names.iter().map(|field_name| match *field_name {
+                OID_TOKEN => Value::Integer(oid),
+                _ => row[0].clone()
+            }).collect()
+        }
+    }
+
+    impl DataSource for MemoryDataSource {
+
+        fn exists(&self, _table: &str) -> DataResult<bool> {
+            Ok(true)
+        }
+
+        fn describe(&self, _table: &str) -> DataResult<TableSchema> {
+            Ok(TableSchema::table().with_field("Population", FieldType::Integer))
+        }
+
+        fn create_table(&self, table: &str, _schema: &TableSchema) -> DataResult<()> {
+            Err(DataError::Invalid(format!("Table '{}' already exists!", table)))
+        }
+
+        fn search<'a>(&'a self, _table: &str, field_names: &[&str], where_clause: &str) -> DataResult<RowIterator<'a>> {
+            MemoryDataSource::check_where_clause(where_clause)?;
+            let rows: Vec<DataResult<Row>> = self.rows.borrow().iter()
+                .map(|(oid, row)| Ok(self.values(*oid, row, field_names)))
+                .collect();
+
+            Ok(Box::new(rows.into_iter()))
+        }
+
+        fn insert_cursor<'a>(&'a self, table: &str, _field_names: &[&str]) -> DataResult<Box<dyn RowInserter + 'a>> {
+            Err(DataError::Invalid(format!("Table '{}' is read-only!", table)))
+        }
+
+        fn update(&self, _table: &str, field_names: &[&str], where_clause: &str, update_row: &mut dyn FnMut(&mut Row) -> bool) -> DataResult<usize> {
+            MemoryDataSource::check_where_clause(where_clause)?;
+            let mut update_count = 0;
+            for (oid, row) in self.rows.borrow_mut().iter_mut() {
+                let mut values = self.values(*oid, row, field_names);
+                if update_row(&mut values) {
+                    row[0] = values.pop().unwrap();
+                    update_count += 1;
+                }
+            }
+
+            Ok(update_count)
+        }
+    }
+
+    #[test]
+    fn compute_and_update_without_gil() {
+        let data_source = MemoryDataSource {
+            rows: std::cell::RefCell::new((1..=4).map(|oid| (oid, vec![Value::Integer(oid * 1000)])).collect())
+        };
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let total = compute_without_gil(py, &data_source, "cities", &["Population"], "", |rows| {
+            assert_eq!(0, unsafe { pyo3::ffi::PyGILState_Check() }, "GIL must be released!");
+            rows.iter().map(|row| match row[0] {
+                Value::Integer(population) => population,
+                _ => 0
+            }).sum::<i64>()
+        }).unwrap();
+        assert_eq!(10000, total, "Total is wrong!");
+
+        let update_count = update_without_gil(py, &data_source, "cities", &["Population"], "", |rows| {
+            assert_eq!(0, unsafe { pyo3::ffi::PyGILState_Check() }, "GIL must be released!");
+            rows.retain(|(oid, _)| 2 != *oid);
+            rows.reverse();
+            for (oid, row) in rows.iter_mut() {
+                row[0] = Value::Integer(*oid * 2000);
+            }
+        }).unwrap();
+        assert_eq!(3, update_count, "Update count is wrong!");
+        let populations: Vec<Value> = data_source.rows.borrow().iter().map(|(_, row)| row[0].clone()).collect();
+        assert_eq!(vec![Value::Integer(2000), Value::Integer(2000), Value::Integer(6000), Value::Integer(8000)], populations, "Removed rows must not be written back!");
+    }
+
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn split_where_clause_outside_of_literals() {
+        assert_eq!(vec!["Name = 'Dessau'", "Population >= 70000"], split_conditions("Name = 'Dessau' AND Population >= 70000"), "Conditions are wrong!");
+        assert_eq!(vec!["Name = 'Dessau AND Roßlau'"], split_conditions("Name = 'Dessau AND Roßlau'"), "Quoted AND must not split!");
+        assert_eq!(vec!["Name = 'It''s and'", "Kind = 'x'"], split_conditions("Name = 'It''s and' and Kind = 'x'"), "Escaped quotes are wrong!");
+        assert_eq!(vec!["Band = 'AND'", "Population IS NOT NULL"], split_conditions("Band = 'AND' AND Population IS NOT NULL"), "Conditions are wrong!");
+        assert_eq!(vec!["Name = 'Dessau'"], split_conditions("Name = 'Dessau'"), "Single condition is wrong!");
+        assert_eq!(vec![""], split_conditions(""), "Empty where clause is wrong!");
+    }
+
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn match_quoted_literals() {
+        let name = Value::from("Dessau AND Roßlau");
+        let kind = Value::from("It's a city");
+        let population = Value::Null;
+        let founded = Value::Date(String::from("1213-01-01"));
+        let field_value = |field_name: &str| match field_name {
+            "Name" => Some(&name),
+            "Kind" => Some(&kind),
+            "Population" => Some(&population),
+            "Founded" => Some(&founded),
+            _ => None
+        };
+        assert!(WhereClause::parse("Name = 'Dessau AND Roßlau'").unwrap().matches(field_value), "Quoted AND must match!");
+        assert!(WhereClause::parse("Kind = 'It''s a city' AND Population IS NULL").unwrap().matches(field_value), "Escaped quote must match!");
+        assert!(!WhereClause::parse("Name = 'Dessau' AND Population IS NULL").unwrap().matches(field_value), "Row must not match!");
+        assert!(WhereClause::parse("Name <> 'x=y' AND Population is null").unwrap().matches(field_value), "Operator in literal must be ignored!");
+        assert!(WhereClause::parse("Founded < '1300-01-01'").unwrap().matches(field_value), "Dates must be comparable!");
+        assert!(!WhereClause::parse("Unknown = 1").unwrap().matches(field_value), "Unknown fields must not match!");
+        assert!(WhereClause::parse("Name = ").is_err(), "Missing literal must not be supported!");
+    }
+}
@@ -0,0 +1,442 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{field_type_from_arcpy, split_catalog_path};
+use super::{DataError, DataResult, DataSource, Geometry, NativeValue, Row, RowInserter, RowIterator, TableSchema, Value};
+use crate::gp::api::{FieldType, ShapeType};
+use crate::gp::tools::{self, GpToolExecute};
+use pyo3::prelude::*;
+use pyo3::PyNativeType;
+use pyo3::types::{PyDate, PyIterator, PyList, PyTime};
+use std::str::FromStr;
+
+/// Represents the data source using arcpy and arcpy.da.
+/// The tables are identified by their catalog path.
+/// Geometries having z-values, m-values or curves and values of other Python types like GUIDs
+/// being returned as text are passed through unchanged.
+pub struct ArcpyDataSource<'py> {
+    py: Python<'py>
+}
+
+impl<'py> ArcpyDataSource<'py> {
+
+    pub fn new(py: Python<'py>) -> ArcpyDataSource<'py> {
+        ArcpyDataSource {
+            py
+        }
+    }
+
+    /// Converts the specified value into an arcpy value.
+    fn to_object(&self, value: &Value) -> PyResult<PyObject> {
+        let py_value = match value {
+            Value::Null => self.py.None(),
+            Value::Integer(value) => value.to_object(self.py),
+            Value::Double(value) => value.to_object(self.py),
+            Value::Text(text) => text.to_object(self.py),
+            Value::Date(date) => date_to_object(self.py, date)?,
+            Value::Geometry(geometry) => geometry_to_object(self.py, geometry, None)?.to_object(self.py),
+            Value::Native(native_value) => native_value.as_object().clone_ref(self.py)
+        };
+
+        Ok(py_value)
+    }
+}
+
+/// Creates an arcpy geometry like arcpy.PointGeometry or arcpy.Polygon.
+/// The geometry has no spatial reference unless one is specified.
+pub(crate) fn geometry_to_object<'py>(py: Python<'py>, geometry: &Geometry, spatial_reference: Option<&PyAny>) -> PyResult<&'py PyAny> {
+    let arcpy = py.import("arcpy")?;
+    let as_points = |coordinates: &[(f64, f64)]| -> PyResult<&PyAny> {
+        let mut points = Vec::with_capacity(coordinates.len());
+        for (x, y) in coordinates {
+            points.push(arcpy.call1("Point", (*x, *y))?);
+        }
+
+        arcpy.call1("Array", (PyList::new(py, points),))
+    };
+    let as_parts = |parts: &[Vec<(f64, f64)>]| -> PyResult<&PyAny> {
+        let mut arrays = Vec::with_capacity(parts.len());
+        for part in parts {
+            arrays.push(as_points(part)?);
+        }
+
+        arcpy.call1("Array", (PyList::new(py, arrays),))
+    };
+
+    match geometry {
+        Geometry::Point(x, y) => arcpy.call1("PointGeometry", (arcpy.call1("Point", (*x, *y))?, spatial_reference)),
+        Geometry::Multipoint(points) => arcpy.call1("Multipoint", (as_points(points)?, spatial_reference)),
+        Geometry::Polyline(paths) => arcpy.call1("Polyline", (as_parts(paths)?, spatial_reference)),
+        Geometry::Polygon(rings) => arcpy.call1("Polygon", (as_parts(rings)?, spatial_reference))
+    }
+}
+
+/// Creates a datetime.datetime, datetime.date or datetime.time from ISO 8601 text.
+fn date_to_object(py: Python, date: &str) -> PyResult<PyObject> {
+    let datetime = py.import("datetime")?;
+    let date_type = if date.contains('T') {
+        "datetime"
+    } else if date.contains(':') {
+        "time"
+    } else {
+        "date"
+    };
+
+    Ok(datetime.getattr(date_type)?.call_method1("fromisoformat", (date,))?.to_object(py))
+}
+
+/// Converts an arcpy value like a row value into a value.
+/// Geometries which cannot be converted without losing z-values, m-values or curves are returned as native values.
+fn from_object(py_value: &PyAny) -> DataResult<Value> {
+    if py_value.is_none() {
+        return Ok(Value::Null);
+    }
+    if let Ok(value) = py_value.extract::<i64>() {
+        return Ok(Value::Integer(value));
+    }
+    if let Ok(value) = py_value.extract::<f64>() {
+        return Ok(Value::Double(value));
+    }
+    if let Ok(text) = py_value.extract::<String>() {
+        return Ok(Value::Text(text));
+    }
+    if py_value.downcast::<PyDate>().is_ok() || py_value.downcast::<PyTime>().is_ok() {
+        return Ok(Value::Date(py_value.call_method0("isoformat")?.extract()?));
+    }
+    if let Ok((x, y)) = py_value.extract::<(f64, f64)>() {
+        return Ok(Value::Geometry(Geometry::Point(x, y)));
+    }
+    if py_value.hasattr("getPart")? {
+        if is_plain_geometry(py_value)? {
+            return Ok(Value::Geometry(geometry_from_object(py_value)?));
+        }
+
+        return Ok(Value::Native(NativeValue::new(py_value.to_object(py_value.py()))));
+    }
+
+    Err(DataError::Invalid(format!("Value type '{}' is not supported!", py_value.get_type().name())))
+}
+
+/// Checks whether or not the arcpy geometry has neither curves nor z-values nor m-values.
+pub(crate) fn is_plain_geometry(py_geometry: &PyAny) -> DataResult<bool> {
+    if py_geometry.hasattr("hasCurves")? && py_geometry.getattr("hasCurves")?.is_true()? {
+        return Ok(false);
+    }
+    let geometry_type: String = py_geometry.getattr("type")?.extract()?;
+    let mut py_points = Vec::new();
+    if "point" == geometry_type {
+        py_points.push(py_geometry.getattr("firstPoint")?);
+    } else {
+        for py_part in py_geometry.call_method0("getPart")?.iter()? {
+            let py_part = py_part?;
+            if py_part.hasattr("X")? {
+                py_points.push(py_part);
+            } else {
+                for py_point in py_part.iter()? {
+                    py_points.push(py_point?);
+                }
+            }
+        }
+    }
+    for py_point in py_points {
+        if !py_point.is_none() && (!py_point.getattr("Z")?.is_none() || !py_point.getattr("M")?.is_none()) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Converts an arcpy geometry into a geometry.
+/// The parts of polygons contain None between the exterior and the interior rings.
+/// The z-values, m-values and curves are not converted, see `from_object` for a lossless conversion.
+pub(crate) fn geometry_from_object(py_geometry: &PyAny) -> DataResult<Geometry> {
+    let geometry_type: String = py_geometry.getattr("type")?.extract()?;
+    if "point" == geometry_type {
+        let first_point = py_geometry.getattr("firstPoint")?;
+        return Ok(Geometry::Point(first_point.getattr("X")?.extract()?, first_point.getattr("Y")?.extract()?));
+    }
+
+    let mut parts = Vec::new();
+    for py_part in py_geometry.call_method0("getPart")?.iter()? {
+        let mut part = Vec::new();
+        for py_point in py_part?.iter()? {
+            let py_point = py_point?;
+            if py_point.is_none() {
+                parts.push(std::mem::take(&mut part));
+                continue;
+            }
+            part.push((py_point.getattr("X")?.extract()?, py_point.getattr("Y")?.extract()?));
+        }
+        parts.push(part);
+    }
+
+    match geometry_type.as_str() {
+        "multipoint" => Ok(Geometry::Multipoint(parts.into_iter().flatten().collect())),
+        "polyline" => Ok(Geometry::Polyline(parts)),
+        "polygon" => Ok(Geometry::Polygon(parts)),
+        _ => Err(DataError::Invalid(format!("Geometry type '{}' is not supported!", geometry_type)))
+    }
+}
+
+/// Represents an open arcpy.da cursor being released like a with statement does.
+/// The cursor is also released if it is dropped because of an error.
+struct ArcpyCursor<'py> {
+    pycursor: &'py PyAny,
+    released: bool
+}
+
+impl<'py> ArcpyCursor<'py> {
+
+    fn new(pycursor: &'py PyAny) -> ArcpyCursor<'py> {
+        ArcpyCursor {
+            pycursor,
+            released: false
+        }
+    }
+
+    /// Releases the cursor and its schema lock.
+    fn release(&mut self) -> PyResult<()> {
+        if self.released {
+            return Ok(());
+        }
+
+        self.released = true;
+        if self.pycursor.hasattr("__exit__")? {
+            let py = self.pycursor.py();
+            self.pycursor.call_method1("__exit__", (py.None(), py.None(), py.None()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for ArcpyCursor<'_> {
+
+    fn drop(&mut self) {
+        if let Err(err) = self.release() {
+            err.print(self.pycursor.py());
+        }
+    }
+}
+
+/// Represents the rows of an arcpy.da.SearchCursor.
+struct ArcpyRows<'py> {
+    cursor: ArcpyCursor<'py>,
+    pyrows: PyIterator<'py>
+}
+
+impl Iterator for ArcpyRows<'_> {
+
+    type Item = DataResult<Row>;
+
+    fn next(&mut self) -> Option<DataResult<Row>> {
+        let py_row = match self.pyrows.next()? {
+            Ok(py_row) => py_row,
+            Err(err) => return Some(Err(err.into()))
+        };
+        let row = row_from_object(py_row);
+        if row.is_err() {
+            // Releases the schema lock even if the caller keeps the iterator
+            if let Err(err) = self.cursor.release() {
+                err.print(py_row.py());
+            }
+        }
+
+        Some(row)
+    }
+}
+
+fn row_from_object(py_row: &PyAny) -> DataResult<Row> {
+    let mut row = Vec::new();
+    for py_value in py_row.iter()? {
+        row.push(from_object(py_value?)?);
+    }
+
+    Ok(row)
+}
+
+/// Represents an arcpy.da.InsertCursor.
+struct ArcpyInserter<'a, 'py> {
+    data_source: &'a ArcpyDataSource<'py>,
+    cursor: ArcpyCursor<'py>
+}
+
+impl RowInserter for ArcpyInserter<'_, '_> {
+
+    fn insert_row(&mut self, row: Row) -> DataResult<i64> {
+        let mut py_values = Vec::with_capacity(row.len());
+        for value in &row {
+            py_values.push(self.data_source.to_object(value)?);
+        }
+
+        Ok(self.cursor.pycursor.call_method1("insertRow", (py_values,))?.extract()?)
+    }
+
+    fn finish(mut self: Box<Self>) -> DataResult<()> {
+        Ok(self.cursor.release()?)
+    }
+}
+
+impl DataSource for ArcpyDataSource<'_> {
+
+    fn exists(&self, table: &str) -> DataResult<bool> {
+        let arcpy = self.py.import("arcpy")?;
+
+        Ok(arcpy.call1("Exists", (table,))?.extract()?)
+    }
+
+    fn describe(&self, table: &str) -> DataResult<TableSchema> {
+        if !self.exists(table)? {
+            return Err(DataError::NotFound(table.to_string()));
+        }
+
+        let arcpy = self.py.import("arcpy")?;
+        let pydescribe = arcpy.call1("Describe", (table,))?;
+        let mut schema = if pydescribe.hasattr("shapeType")? {
+            let shape_type_as_text: String = pydescribe.getattr("shapeType")?.extract()?;
+            let shape_type = ShapeType::from_str(&shape_type_as_text)
+                .map_err(|_| DataError::Invalid(format!("Shape type '{}' is not supported!", shape_type_as_text)))?;
+            let wkid = pydescribe.getattr("spatialReference")?.getattr("factoryCode")?.extract()?;
+            TableSchema::features(shape_type, wkid)
+        } else {
+            TableSchema::table()
+        };
+
+        let pyfields: Vec<&PyAny> = pydescribe.getattr("fields")?.extract()?;
+        for pyfield in pyfields {
+            let field_name: String = pyfield.getattr("name")?.extract()?;
+            let field_type_as_text: String = pyfield.getattr("type")?.extract()?;
+            let field_type = field_type_from_arcpy(&field_type_as_text)
+                .ok_or_else(|| DataError::Invalid(format!("Field type '{}' is not supported!", field_type_as_text)))?;
+            match field_type {
+                // The workspace maintains the object IDs and GlobalIDs and the shape is accessed using "SHAPE@".
+                // Blob and raster values have no value counterpart, so that the fields are skipped.
+                FieldType::OID | FieldType::Geometry | FieldType::GlobalID | FieldType::Blob | FieldType::Raster => continue,
+                _ => schema = schema.with_field(&field_name, field_type)
+            }
+        }
+
+        Ok(schema)
+    }
+
+    fn create_table(&self, table: &str, schema: &TableSchema) -> DataResult<()> {
+        let (out_path, out_name) = split_catalog_path(table);
+        let catalog_path = match schema.shape_type {
+            Some(shape_type) => {
+                let create_tool = tools::GpCreateFeatureClassTool::new(out_path.to_string(), out_name.to_string(), shape_type, schema.wkid);
+                create_tool.execute(self.py)?.first_as_str(self.py)?
+            },
+            None => {
+                let pyresult = tools::execute_tool(self.py, "arcpy.management", "CreateTable", (out_path, out_name))?;
+                pyresult.as_vecstr().into_iter().next().unwrap_or_else(|| table.to_string())
+            }
+        };
+        if !schema.fields.is_empty() {
+            let fields_tool = tools::GpAddFieldsTool::new(catalog_path, schema.fields.clone());
+            fields_tool.execute(self.py)?;
+        }
+
+        Ok(())
+    }
+
+    fn search<'a>(&'a self, table: &str, field_names: &[&str], where_clause: &str) -> DataResult<RowIterator<'a>> {
+        let arcpy_da = self.py.import("arcpy.da")?;
+        let pycursor = arcpy_da.call1("SearchCursor", (table, field_names.to_vec(), where_clause))?;
+        let cursor = ArcpyCursor::new(pycursor);
+        let pyrows = pycursor.iter()?;
+
+        Ok(Box::new(ArcpyRows {
+            cursor,
+            pyrows
+        }))
+    }
+
+    fn insert_cursor<'a>(&'a self, table: &str, field_names: &[&str]) -> DataResult<Box<dyn RowInserter + 'a>> {
+        let arcpy_da = self.py.import("arcpy.da")?;
+        let pycursor = arcpy_da.call1("InsertCursor", (table, field_names.to_vec()))?;
+
+        Ok(Box::new(ArcpyInserter {
+            data_source: self,
+            cursor: ArcpyCursor::new(pycursor)
+        }))
+    }
+
+    fn update(&self, table: &str, field_names: &[&str], where_clause: &str, update_row: &mut dyn FnMut(&mut Row) -> bool) -> DataResult<usize> {
+        let arcpy_da = self.py.import("arcpy.da")?;
+        let mut cursor = ArcpyCursor::new(arcpy_da.call1("UpdateCursor", (table, field_names.to_vec(), where_clause))?);
+        let mut update_count = 0;
+        for py_row in cursor.pycursor.iter()? {
+            let mut row = row_from_object(py_row?)?;
+            if update_row(&mut row) {
+                let mut py_values = Vec::with_capacity(row.len());
+                for value in &row {
+                    py_values.push(self.to_object(value)?);
+                }
+                cursor.pycursor.call_method1("updateRow", (py_values,))?;
+                update_count += 1;
+            }
+        }
+        cursor.release()?;
+
+        Ok(update_count)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn read_dates_and_native_geometries() {
+        use crate::gp::api::GpField;
+        use crate::gp::testing::MockArcpy;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        arcpy.create_feature_class("memory/data_wells", ShapeType::Point, 4326, &[
+            GpField { name: String::from("Drilled"), field_type: FieldType::Date }
+        ]).unwrap();
+        arcpy.module().call1("AddFields", ("memory/data_wells", vec![vec!["WellID", "GLOBALID"]])).unwrap();
+        let data_source = ArcpyDataSource::new(py);
+        let schema = data_source.describe("memory/data_wells").unwrap();
+        assert_eq!(vec![String::from("SHAPE@"), String::from("Drilled")], schema.field_names(), "GlobalID fields must be skipped!");
+
+        let pypoint = arcpy.module().call1("Point", (7.0, 51.0, 120.0)).unwrap();
+        let pygeometry = arcpy.module().call1("PointGeometry", (pypoint,)).unwrap();
+        let mut inserter = data_source.insert_cursor("memory/data_wells", &["SHAPE@", "Drilled"]).unwrap();
+        inserter.insert_row(vec![Value::Native(NativeValue::new(pygeometry.to_object(py))), Value::Date(String::from("2021-03-01T12:30:00"))]).unwrap();
+        inserter.insert_row(vec![Geometry::Point(8.0, 52.0).into(), Value::Date(String::from("2021-03-02"))]).unwrap();
+        inserter.finish().unwrap();
+
+        let rows = data_source.search("memory/data_wells", &["SHAPE@", "Drilled"], "").unwrap()
+            .collect::<DataResult<Vec<Row>>>()
+            .unwrap();
+        match &rows[0][0] {
+            Value::Native(native_value) => assert_eq!(120.0, native_value.as_object().as_ref(py).getattr("firstPoint").unwrap().getattr("Z").unwrap().extract::<f64>().unwrap(), "Z-value is wrong!"),
+            value => panic!("Geometry having z-values must be native but is {:?}!", value)
+        }
+        assert_eq!(Value::Date(String::from("2021-03-01T12:30:00")), rows[0][1], "Date is wrong!");
+        assert_eq!(Value::Geometry(Geometry::Point(8.0, 52.0)), rows[1][0], "Geometry is wrong!");
+        assert_eq!(Value::Date(String::from("2021-03-02")), rows[1][1], "Date is wrong!");
+
+        let pyunknown = py.eval("object()", None, None).unwrap();
+        assert!(matches!(from_object(pyunknown), Err(DataError::Invalid(_))), "Unknown value types must be rejected!");
+    }
+}
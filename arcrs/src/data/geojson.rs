@@ -0,0 +1,599 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{DataError, DataResult, DataSource, Geometry, Row, RowInserter, RowIterator, TableSchema, Value, WhereClause, OID_TOKEN, SHAPE_TOKEN};
+use crate::gp::api::{FieldType, ShapeType};
+use serde_json::{json, Map, Value as JsonValue};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The name of the object ID field.
+const OID_FIELD_NAME: &str = "OBJECTID";
+
+/// Represents the data source using GeoJSON files in a directory.
+/// Every table is stored as "<table>.geojson" file containing a feature collection.
+/// The schema is stored as "schema" member of the feature collection.
+/// Files without a schema are supported by inferring the schema from the first feature.
+pub struct GeoJsonDataSource {
+    directory: PathBuf
+}
+
+/// Represents a loaded GeoJSON file.
+struct GeoJsonTable {
+    schema: TableSchema,
+    /// The object ID and the values of every field and the geometry as last value.
+    rows: Vec<(i64, Row)>
+}
+
+impl GeoJsonDataSource {
+
+    pub fn new<P: AsRef<Path>>(directory: P) -> GeoJsonDataSource {
+        GeoJsonDataSource {
+            directory: directory.as_ref().to_path_buf()
+        }
+    }
+
+    /// Returns the file path of the specified table.
+    pub fn table_path(&self, table: &str) -> PathBuf {
+        let table_path = self.directory.join(table);
+        match table_path.extension().and_then(|extension| extension.to_str()) {
+            Some("geojson") | Some("json") => table_path,
+            _ => self.directory.join(format!("{}.geojson", table))
+        }
+    }
+
+    fn load(&self, table: &str) -> DataResult<GeoJsonTable> {
+        let table_path = self.table_path(table);
+        if !table_path.exists() {
+            return Err(DataError::NotFound(table.to_string()));
+        }
+        let content = std::fs::read_to_string(&table_path)?;
+        let feature_collection: JsonValue = serde_json::from_str(&content)
+            .map_err(|err| DataError::Invalid(format!("GeoJSON file '{}' is not valid: {}", table_path.display(), err)))?;
+        let features = match feature_collection.get("features").and_then(|features| features.as_array()) {
+            Some(features) => features.as_slice(),
+            None => &[]
+        };
+        let schema = match feature_collection.get("schema") {
+            Some(json_schema) => schema_from_json(json_schema)?,
+            None => infer_schema(features)
+        };
+
+        let mut rows = Vec::with_capacity(features.len());
+        for (feature_index, feature) in features.iter().enumerate() {
+            let oid = feature.get("id").and_then(|id| id.as_i64()).unwrap_or(feature_index as i64 + 1);
+            let mut row = Vec::with_capacity(schema.fields.len() + 1);
+            for field in &schema.fields {
+                let json_value = feature.get("properties").and_then(|properties| properties.get(&field.name));
+                row.push(value_from_json(json_value, field.field_type));
+            }
+            row.push(match feature.get("geometry") {
+                Some(json_geometry) if !json_geometry.is_null() => Value::Geometry(geometry_from_json(json_geometry)?),
+                _ => Value::Null
+            });
+            rows.push((oid, row));
+        }
+
+        Ok(GeoJsonTable {
+            schema,
+            rows
+        })
+    }
+
+    fn save(&self, table: &str, geojson_table: &GeoJsonTable) -> DataResult<()> {
+        let mut features = Vec::with_capacity(geojson_table.rows.len());
+        for (oid, row) in &geojson_table.rows {
+            let mut properties = Map::new();
+            for (field, value) in geojson_table.schema.fields.iter().zip(row) {
+                properties.insert(field.name.to_string(), value_to_json(value)?);
+            }
+            let geometry = match row.last() {
+                Some(Value::Null) | None => JsonValue::Null,
+                Some(value) => value_to_json(value)?
+            };
+            features.push(json!({
+                "type": "Feature",
+                "id": oid,
+                "geometry": geometry,
+                "properties": properties
+            }));
+        }
+
+        let feature_collection = json!({
+            "type": "FeatureCollection",
+            "schema": schema_to_json(&geojson_table.schema),
+            "features": features
+        });
+        let content = serde_json::to_string_pretty(&feature_collection)
+            .map_err(|err| DataError::Invalid(err.to_string()))?;
+        std::fs::write(self.table_path(table), content)?;
+
+        Ok(())
+    }
+}
+
+impl GeoJsonTable {
+
+    /// Returns the row index of the specified field name or token.
+    fn value_index(&self, field_name: &str) -> DataResult<Option<usize>> {
+        if field_name.eq_ignore_ascii_case(OID_TOKEN) || field_name.eq_ignore_ascii_case(OID_FIELD_NAME) {
+            return Ok(None);
+        }
+        if field_name.eq_ignore_ascii_case(SHAPE_TOKEN) || field_name.eq_ignore_ascii_case("Shape") {
+            return Ok(Some(self.schema.fields.len()));
+        }
+
+        match self.schema.fields.iter().position(|field| field.name.eq_ignore_ascii_case(field_name)) {
+            Some(field_index) => Ok(Some(field_index)),
+            None => Err(DataError::Invalid(format!("Field '{}' does not exist!", field_name)))
+        }
+    }
+
+    fn value_indices(&self, field_names: &[&str]) -> DataResult<Vec<Option<usize>>> {
+        field_names.iter().map(|field_name| self.value_index(field_name)).collect()
+    }
+
+    /// Returns the indices of all rows matching the where clause.
+    fn matching_rows(&self, where_clause: &str) -> DataResult<Vec<usize>> {
+        let where_clause = WhereClause::parse(where_clause)?;
+        let condition_indices = self.value_indices(&where_clause.field_names())?;
+        let oid_values: Vec<Value> = self.rows.iter().map(|(oid, _)| Value::Integer(*oid)).collect();
+        let mut row_indices = Vec::new();
+        for (row_index, (_, row)) in self.rows.iter().enumerate() {
+            let field_value = |field_name: &str| {
+                let condition_index = where_clause.field_names().iter().position(|known_name| *known_name == field_name)?;
+                match condition_indices[condition_index] {
+                    Some(value_index) => row.get(value_index),
+                    None => Some(&oid_values[row_index])
+                }
+            };
+            if where_clause.matches(field_value) {
+                row_indices.push(row_index);
+            }
+        }
+
+        Ok(row_indices)
+    }
+}
+
+/// Represents an insert cursor appending rows to a loaded GeoJSON file.
+/// The file is written when the inserter is finished.
+struct GeoJsonInserter<'a> {
+    data_source: &'a GeoJsonDataSource,
+    table: String,
+    geojson_table: GeoJsonTable,
+    value_indices: Vec<Option<usize>>,
+    next_oid: i64
+}
+
+impl<'a> GeoJsonInserter<'a> {
+
+    fn new(data_source: &'a GeoJsonDataSource, table: &str, field_names: &[&str]) -> DataResult<GeoJsonInserter<'a>> {
+        let geojson_table = data_source.load(table)?;
+        let value_indices = geojson_table.value_indices(field_names)?;
+        let next_oid = geojson_table.rows.iter().map(|(oid, _)| *oid).max().unwrap_or(0) + 1;
+
+        Ok(GeoJsonInserter {
+            data_source,
+            table: table.to_string(),
+            geojson_table,
+            value_indices,
+            next_oid
+        })
+    }
+}
+
+impl RowInserter for GeoJsonInserter<'_> {
+
+    fn insert_row(&mut self, values: Row) -> DataResult<i64> {
+        if values.len() != self.value_indices.len() {
+            return Err(DataError::Invalid(String::from("Row does not match the fields!")));
+        }
+        let mut row = vec![Value::Null; self.geojson_table.schema.fields.len() + 1];
+        for (value_index, value) in self.value_indices.iter().zip(values) {
+            if let Some(value_index) = value_index {
+                row[*value_index] = value;
+            }
+        }
+
+        let oid = self.next_oid;
+        self.geojson_table.rows.push((oid, row));
+        self.next_oid += 1;
+
+        Ok(oid)
+    }
+
+    fn finish(self: Box<Self>) -> DataResult<()> {
+        self.data_source.save(&self.table, &self.geojson_table)
+    }
+}
+
+impl DataSource for GeoJsonDataSource {
+
+    fn exists(&self, table: &str) -> DataResult<bool> {
+        Ok(self.table_path(table).exists())
+    }
+
+    fn describe(&self, table: &str) -> DataResult<TableSchema> {
+        Ok(self.load(table)?.schema)
+    }
+
+    fn create_table(&self, table: &str, schema: &TableSchema) -> DataResult<()> {
+        if self.exists(table)? {
+            return Err(DataError::Invalid(format!("Table '{}' already exists!", table)));
+        }
+
+        self.save(table, &GeoJsonTable {
+            schema: schema.clone(),
+            rows: Vec::new()
+        })
+    }
+
+    fn search<'a>(&'a self, table: &str, field_names: &[&str], where_clause: &str) -> DataResult<RowIterator<'a>> {
+        let geojson_table = self.load(table)?;
+        let value_indices = geojson_table.value_indices(field_names)?;
+        let row_indices = geojson_table.matching_rows(where_clause)?;
+        let rows: Vec<DataResult<Row>> = row_indices.into_iter().map(|row_index| {
+            let (oid, row) = &geojson_table.rows[row_index];
+            Ok(value_indices.iter().map(|value_index| match value_index {
+                Some(value_index) => row[*value_index].clone(),
+                None => Value::Integer(*oid)
+            }).collect())
+        }).collect();
+
+        Ok(Box::new(rows.into_iter()))
+    }
+
+    fn insert_cursor<'a>(&'a self, table: &str, field_names: &[&str]) -> DataResult<Box<dyn RowInserter + 'a>> {
+        Ok(Box::new(GeoJsonInserter::new(self, table, field_names)?))
+    }
+
+    fn update(&self, table: &str, field_names: &[&str], where_clause: &str, update_row: &mut dyn FnMut(&mut Row) -> bool) -> DataResult<usize> {
+        let mut geojson_table = self.load(table)?;
+        let value_indices = geojson_table.value_indices(field_names)?;
+        let row_indices = geojson_table.matching_rows(where_clause)?;
+        let mut update_count = 0;
+        for row_index in row_indices {
+            let (oid, row) = &mut geojson_table.rows[row_index];
+            let mut values: Row = value_indices.iter().map(|value_index| match value_index {
+                Some(value_index) => row[*value_index].clone(),
+                None => Value::Integer(*oid)
+            }).collect();
+            if update_row(&mut values) {
+                for (value_index, value) in value_indices.iter().zip(values) {
+                    if let Some(value_index) = value_index {
+                        row[*value_index] = value;
+                    }
+                }
+                update_count += 1;
+            }
+        }
+        if 0 < update_count {
+            self.save(table, &geojson_table)?;
+        }
+
+        Ok(update_count)
+    }
+}
+
+
+
+fn schema_to_json(schema: &TableSchema) -> JsonValue {
+    let fields: Vec<JsonValue> = schema.fields.iter()
+        .map(|field| json!({ "name": field.name, "type": field.field_type.as_str() }))
+        .collect();
+
+    json!({
+        "shapeType": schema.shape_type.map(|shape_type| shape_type.as_str()),
+        "wkid": schema.wkid,
+        "fields": fields
+    })
+}
+
+fn schema_from_json(json_schema: &JsonValue) -> DataResult<TableSchema> {
+    let mut schema = match json_schema.get("shapeType").and_then(|shape_type| shape_type.as_str()) {
+        Some(shape_type_as_text) => {
+            let shape_type = ShapeType::from_str(shape_type_as_text)
+                .map_err(|_| DataError::Invalid(format!("Shape type '{}' is not supported!", shape_type_as_text)))?;
+            let wkid = json_schema.get("wkid").and_then(|wkid| wkid.as_i64()).unwrap_or(4326);
+            TableSchema::features(shape_type, wkid as i32)
+        },
+        None => TableSchema::table()
+    };
+    if let Some(json_fields) = json_schema.get("fields").and_then(|fields| fields.as_array()) {
+        for json_field in json_fields {
+            let field_name = json_field.get("name").and_then(|name| name.as_str()).unwrap_or_default();
+            let field_type_as_text = json_field.get("type").and_then(|field_type| field_type.as_str()).unwrap_or_default();
+            let field_type = FieldType::from_str(field_type_as_text)
+                .map_err(|_| DataError::Invalid(format!("Field type '{}' is not supported!", field_type_as_text)))?;
+            schema = schema.with_field(field_name, field_type);
+        }
+    }
+
+    Ok(schema)
+}
+
+/// Infers the schema from the first feature of a GeoJSON file having no schema.
+/// GeoJSON uses WGS84 as spatial reference.
+fn infer_schema(features: &[JsonValue]) -> TableSchema {
+    let first_feature = match features.first() {
+        Some(first_feature) => first_feature,
+        None => return TableSchema::table()
+    };
+    let mut schema = match first_feature.get("geometry").map(geometry_from_json) {
+        Some(Ok(geometry)) => TableSchema::features(geometry.shape_type(), 4326),
+        _ => TableSchema::table()
+    };
+    if let Some(properties) = first_feature.get("properties").and_then(|properties| properties.as_object()) {
+        for (name, json_value) in properties {
+            let field_type = match json_value {
+                JsonValue::Number(number) if number.is_i64() => FieldType::Integer,
+                JsonValue::Number(_) => FieldType::Double,
+                _ => FieldType::String
+            };
+            schema = schema.with_field(name, field_type);
+        }
+    }
+
+    schema
+}
+
+/// Converts a value, dates are written as ISO 8601 text and native values are not supported.
+fn value_to_json(value: &Value) -> DataResult<JsonValue> {
+    match value {
+        Value::Null => Ok(JsonValue::Null),
+        Value::Integer(value) => Ok(json!(value)),
+        Value::Double(value) => Ok(json!(value)),
+        Value::Text(text) | Value::Date(text) => Ok(json!(text)),
+        Value::Geometry(geometry) => Ok(geometry_to_json(geometry)),
+        Value::Native(_) => Err(DataError::Invalid(String::from("Native values are not supported by GeoJSON!")))
+    }
+}
+
+fn value_from_json(json_value: Option<&JsonValue>, field_type: FieldType) -> Value {
+    match json_value {
+        None | Some(JsonValue::Null) => Value::Null,
+        Some(JsonValue::Number(number)) => match (field_type, number.as_i64()) {
+            (FieldType::Integer, Some(value)) | (FieldType::BigInteger, Some(value)) | (FieldType::OID, Some(value)) => Value::Integer(value),
+            _ => Value::Double(number.as_f64().unwrap_or_default())
+        },
+        Some(JsonValue::String(text)) if is_date_type(field_type) => Value::Date(text.to_string()),
+        Some(JsonValue::String(text)) => Value::Text(text.to_string()),
+        Some(json_value) => Value::Text(json_value.to_string())
+    }
+}
+
+fn is_date_type(field_type: FieldType) -> bool {
+    matches!(field_type, FieldType::Date | FieldType::DateOnly | FieldType::TimeOnly | FieldType::TimestampOffset)
+}
+
+fn as_coordinates(points: &[(f64, f64)]) -> Vec<[f64; 2]> {
+    points.iter().map(|(x, y)| [*x, *y]).collect()
+}
+
+fn geometry_to_json(geometry: &Geometry) -> JsonValue {
+    match geometry {
+        Geometry::Point(x, y) => json!({ "type": "Point", "coordinates": [x, y] }),
+        Geometry::Multipoint(points) => json!({ "type": "MultiPoint", "coordinates": as_coordinates(points) }),
+        Geometry::Polyline(paths) => {
+            let coordinates: Vec<Vec<[f64; 2]>> = paths.iter().map(|path| as_coordinates(path)).collect();
+            json!({ "type": "MultiLineString", "coordinates": coordinates })
+        },
+        Geometry::Polygon(rings) => {
+            let mut polygons: Vec<Vec<Vec<[f64; 2]>>> = group_rings(rings).into_iter()
+                .map(|polygon| polygon.into_iter().enumerate().map(|(ring_index, ring)| {
+                    as_coordinates(&oriented_ring(ring, 0 == ring_index))
+                }).collect())
+                .collect();
+            if 1 == polygons.len() {
+                json!({ "type": "Polygon", "coordinates": polygons.remove(0) })
+            } else {
+                json!({ "type": "MultiPolygon", "coordinates": polygons })
+            }
+        }
+    }
+}
+
+/// Returns twice the signed area of a ring, which is positive for counterclockwise rings.
+fn signed_area(ring: &[(f64, f64)]) -> f64 {
+    ring.iter().zip(ring.iter().cycle().skip(1))
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum()
+}
+
+/// Returns the ring being counterclockwise if it is an exterior ring and clockwise if it is an interior ring.
+/// GeoJSON uses this orientation (RFC 7946) while arcpy uses the opposite one.
+fn oriented_ring(ring: &[(f64, f64)], counterclockwise: bool) -> Vec<(f64, f64)> {
+    let mut oriented_ring = ring.to_vec();
+    if (0.0 < signed_area(ring)) != counterclockwise {
+        oriented_ring.reverse();
+    }
+
+    oriented_ring
+}
+
+fn contains_point(ring: &[(f64, f64)], (x, y): (f64, f64)) -> bool {
+    let mut inside = false;
+    for ((x1, y1), (x2, y2)) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+        if (*y1 > y) != (*y2 > y) && x < x1 + (y - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Groups the rings of a polygon into polygons having the exterior ring as first ring.
+/// A ring is an exterior ring if it is nested in an even number of rings.
+fn group_rings(rings: &[Vec<(f64, f64)>]) -> Vec<Vec<&[(f64, f64)]>> {
+    let rings: Vec<&[(f64, f64)]> = rings.iter().filter(|ring| !ring.is_empty()).map(|ring| ring.as_slice()).collect();
+    let containing_rings: Vec<Vec<usize>> = rings.iter().enumerate()
+        .map(|(ring_index, ring)| (0..rings.len())
+            .filter(|other_index| *other_index != ring_index && contains_point(rings[*other_index], ring[0]))
+            .collect())
+        .collect();
+
+    let mut polygon_indices = vec![None; rings.len()];
+    let mut polygons: Vec<Vec<&[(f64, f64)]>> = Vec::new();
+    for (ring_index, ring) in rings.iter().enumerate() {
+        if 0 == containing_rings[ring_index].len() & 1 {
+            polygon_indices[ring_index] = Some(polygons.len());
+            polygons.push(vec![ring]);
+        }
+    }
+    for (ring_index, ring) in rings.iter().enumerate() {
+        let depth = containing_rings[ring_index].len();
+        if 1 == depth & 1 {
+            let exterior_index = containing_rings[ring_index].iter()
+                .find(|other_index| containing_rings[**other_index].len() + 1 == depth);
+            match exterior_index.and_then(|exterior_index| polygon_indices[*exterior_index]) {
+                Some(polygon_index) => polygons[polygon_index].push(ring),
+                None => polygons.push(vec![ring])
+            }
+        }
+    }
+
+    polygons
+}
+
+/// Converts the rings of a GeoJSON polygon into arcpy orientation.
+fn rings_from_json(json_polygon: &JsonValue) -> DataResult<Vec<Vec<(f64, f64)>>> {
+    Ok(parts_from_json(json_polygon)?.iter().enumerate()
+        .map(|(ring_index, ring)| oriented_ring(ring, 0 != ring_index))
+        .collect())
+}
+
+fn point_from_json(json_point: &JsonValue) -> DataResult<(f64, f64)> {
+    match json_point.as_array().map(|coordinates| coordinates.as_slice()) {
+        Some([x, y, ..]) => Ok((x.as_f64().unwrap_or_default(), y.as_f64().unwrap_or_default())),
+        _ => Err(DataError::Invalid(format!("Position '{}' is not valid!", json_point)))
+    }
+}
+
+fn points_from_json(json_points: &JsonValue) -> DataResult<Vec<(f64, f64)>> {
+    match json_points.as_array() {
+        Some(json_points) => json_points.iter().map(point_from_json).collect(),
+        None => Err(DataError::Invalid(format!("Positions '{}' are not valid!", json_points)))
+    }
+}
+
+fn parts_from_json(json_parts: &JsonValue) -> DataResult<Vec<Vec<(f64, f64)>>> {
+    match json_parts.as_array() {
+        Some(json_parts) => json_parts.iter().map(points_from_json).collect(),
+        None => Err(DataError::Invalid(format!("Parts '{}' are not valid!", json_parts)))
+    }
+}
+
+/// Converts a GeoJSON geometry, multi polygons are converted into one polygon having all rings.
+/// The exterior rings are clockwise and the interior rings are counterclockwise like arcpy expects them.
+fn geometry_from_json(json_geometry: &JsonValue) -> DataResult<Geometry> {
+    let coordinates = &json_geometry["coordinates"];
+    match json_geometry["type"].as_str() {
+        Some("Point") => {
+            let (x, y) = point_from_json(coordinates)?;
+            Ok(Geometry::Point(x, y))
+        },
+        Some("MultiPoint") => Ok(Geometry::Multipoint(points_from_json(coordinates)?)),
+        Some("LineString") => Ok(Geometry::Polyline(vec![points_from_json(coordinates)?])),
+        Some("MultiLineString") => Ok(Geometry::Polyline(parts_from_json(coordinates)?)),
+        Some("Polygon") => Ok(Geometry::Polygon(rings_from_json(coordinates)?)),
+        Some("MultiPolygon") => {
+            let mut rings = Vec::new();
+            for json_polygon in coordinates.as_array().map(|polygons| polygons.as_slice()).unwrap_or_default() {
+                rings.append(&mut rings_from_json(json_polygon)?);
+            }
+            Ok(Geometry::Polygon(rings))
+        },
+        _ => Err(DataError::Invalid(format!("Geometry '{}' is not supported!", json_geometry)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn insert_search_and_update() {
+        let directory = std::env::temp_dir().join(format!("arcrs_geojson_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let data_source = GeoJsonDataSource::new(&directory);
+        let schema = TableSchema::features(ShapeType::Point, 4326)
+            .with_field("Name", FieldType::String)
+            .with_field("Population", FieldType::Integer);
+        data_source.create_table("cities", &schema).unwrap();
+        assert_eq!(schema, data_source.describe("cities").unwrap(), "Schema is wrong!");
+
+        let field_names = ["SHAPE@", "Name", "Population"];
+        let oids = data_source.insert("cities", &field_names, vec![
+            vec![Geometry::Point(12.24555, 51.83864).into(), "Dessau".into(), 75000.into()],
+            vec![Geometry::Point(13.40495, 52.52001).into(), "Berlin".into(), 3645000.into()]
+        ]).unwrap();
+        assert_eq!(vec![1, 2], oids, "Object IDs are wrong!");
+
+        let rows: Vec<Row> = data_source.search("cities", &["OID@", "Name"], "Population > 100000").unwrap()
+            .collect::<DataResult<Vec<Row>>>()
+            .unwrap();
+        assert_eq!(vec![vec![Value::Integer(2), Value::from("Berlin")]], rows, "Rows are wrong!");
+
+        let update_count = data_source.update("cities", &["Population"], "Name = 'Dessau'", &mut |row| {
+            row[0] = Value::Integer(76000);
+            true
+        }).unwrap();
+        assert_eq!(1, update_count, "Update count is wrong!");
+
+        let gil = pyo3::Python::acquire_gil();
+        let update_count = crate::data::update_without_gil(gil.python(), &data_source, "cities", &["Population"], "", |rows| {
+            for (_, row) in rows.iter_mut() {
+                if let Value::Integer(population) = row[0] {
+                    row[0] = Value::Integer(population * 2);
+                }
+            }
+        }).unwrap();
+        assert_eq!(2, update_count, "Update count is wrong!");
+        let populations = crate::data::compute_without_gil(gil.python(), &data_source, "cities", &["Population"], "", |rows| {
+            rows.into_iter().map(|row| row[0].clone()).collect::<Vec<Value>>()
+        }).unwrap();
+        assert_eq!(vec![Value::Integer(152000), Value::Integer(7290000)], populations, "Populations are wrong!");
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn write_and_read_multipart_polygons() {
+        let square = |x: f64, y: f64, size: f64| vec![(x, y), (x, y + size), (x + size, y + size), (x + size, y), (x, y)];
+        let hole: Vec<(f64, f64)> = square(1.0, 1.0, 1.0).into_iter().rev().collect();
+        let polygon = Geometry::Polygon(vec![square(0.0, 0.0, 4.0), square(10.0, 0.0, 2.0), hole.clone()]);
+
+        let json_geometry = geometry_to_json(&polygon);
+        assert_eq!("MultiPolygon", json_geometry["type"], "Geometry type is wrong!");
+        let json_polygons = json_geometry["coordinates"].as_array().unwrap();
+        assert_eq!(2, json_polygons.len(), "Polygon count is wrong!");
+        assert_eq!(2, json_polygons[0].as_array().unwrap().len(), "Ring count of the first polygon is wrong!");
+        assert_eq!(1, json_polygons[1].as_array().unwrap().len(), "Ring count of the second polygon is wrong!");
+        let exterior_ring = points_from_json(&json_polygons[0][0]).unwrap();
+        assert!(0.0 < signed_area(&exterior_ring), "Exterior ring must be counterclockwise!");
+        let interior_ring = points_from_json(&json_polygons[0][1]).unwrap();
+        assert!(signed_area(&interior_ring) < 0.0, "Interior ring must be clockwise!");
+
+        assert_eq!(Geometry::Polygon(vec![square(0.0, 0.0, 4.0), hole, square(10.0, 0.0, 2.0)]), geometry_from_json(&json_geometry).unwrap(), "Polygon is wrong!");
+
+        let single_polygon = Geometry::Polygon(vec![square(0.0, 0.0, 4.0)]);
+        let json_geometry = geometry_to_json(&single_polygon);
+        assert_eq!("Polygon", json_geometry["type"], "Geometry type is wrong!");
+        assert_eq!(single_polygon, geometry_from_json(&json_geometry).unwrap(), "Polygon is wrong!");
+    }
+}
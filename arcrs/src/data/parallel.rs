@@ -0,0 +1,225 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{DataError, DataResult, DataSource, Row, RowInserter};
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use std::sync::OnceLock;
+
+/// The default number of rows being processed as one batch.
+const DEFAULT_BATCH_SIZE: usize = 10000;
+
+/// Represents a pipeline reading rows in batches and processing every batch in parallel.
+/// The batches are processed while the GIL is released.
+/// The results are written in the order of the input rows using one insert cursor.
+/// A pipeline having a thread count builds its thread pool once and reuses it for every run,
+/// otherwise the global thread pool is used.
+/// # Examples
+///
+/// ```ignore
+/// let pipeline = ParallelPipeline::new(&in_features, &["SHAPE@", "Name"])
+///     .with_parallel_processing_factor(py)?;
+/// let source = data::arcpy::ArcpyDataSource::new(py);
+/// let mut inserter = source.insert_cursor(&out_features, &["SHAPE@", "Name"])?;
+/// let count = pipeline.run(py, &source, inserter.as_mut(), |row| Some(vec![simplify(&row[0]), row[1].clone()]))?;
+/// inserter.finish()?;
+/// ```
+pub struct ParallelPipeline {
+    table: String,
+    field_names: Vec<String>,
+    where_clause: String,
+    batch_size: usize,
+    thread_count: Option<usize>,
+    thread_pool: OnceLock<ThreadPool>
+}
+
+impl ParallelPipeline {
+
+    /// Creates a new pipeline reading the specified fields of all rows.
+    pub fn new(table: &str, field_names: &[&str]) -> ParallelPipeline {
+        ParallelPipeline {
+            table: table.to_string(),
+            field_names: field_names.iter().map(|field_name| field_name.to_string()).collect(),
+            where_clause: String::new(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            thread_count: None,
+            thread_pool: OnceLock::new()
+        }
+    }
+
+    /// Only reads the rows matching the where clause.
+    pub fn with_where_clause(mut self, where_clause: &str) -> ParallelPipeline {
+        self.where_clause = where_clause.to_string();
+        self
+    }
+
+    /// Sets the number of rows being held in memory and processed as one batch.
+    pub fn with_batch_size(mut self, batch_size: usize) -> ParallelPipeline {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the number of threads processing a batch.
+    /// All cores are used by default.
+    pub fn with_thread_count(mut self, thread_count: usize) -> ParallelPipeline {
+        self.thread_count = Some(thread_count.max(1));
+        self.thread_pool = OnceLock::new();
+        self
+    }
+
+    /// Sets the number of threads using arcpy.env.parallelProcessingFactor.
+    /// All cores are used if the environment setting is not set.
+    pub fn with_parallel_processing_factor(mut self, py: Python) -> PyResult<ParallelPipeline> {
+        let arcpy = py.import("arcpy")?;
+        let factor: Option<String> = match arcpy.getattr("env")?.getattr("parallelProcessingFactor")? {
+            py_factor if py_factor.is_none() => None,
+            py_factor => Some(py_factor.str()?.to_string())
+        };
+        if let Some(thread_count) = factor.and_then(|factor| thread_count_from_factor(&factor, rayon::current_num_threads())) {
+            self = self.with_thread_count(thread_count);
+        }
+
+        Ok(self)
+    }
+
+    /// Returns the number of threads processing a batch.
+    pub fn thread_count(&self) -> usize {
+        self.thread_count.unwrap_or_else(rayon::current_num_threads)
+    }
+
+    /// Reads the rows in batches, processes every row in parallel and inserts the processed rows in order.
+    /// Rows are skipped if the process function returns None.
+    /// Returns the number of inserted rows.
+    pub fn run<F>(&self, py: Python, source: &dyn DataSource, inserter: &mut dyn RowInserter, process: F) -> DataResult<usize>
+    where
+        F: Fn(Row) -> Option<Row> + Send + Sync
+    {
+        let thread_pool = self.thread_pool()?;
+        let field_names: Vec<&str> = self.field_names.iter().map(|field_name| field_name.as_str()).collect();
+        let mut rows = source.search(&self.table, &field_names, &self.where_clause)?;
+
+        let mut insert_count = 0;
+        loop {
+            let batch = rows.by_ref().take(self.batch_size).collect::<DataResult<Vec<Row>>>()?;
+            if batch.is_empty() {
+                break;
+            }
+
+            let processed_rows: Vec<Option<Row>> = py.allow_threads(|| {
+                let process_batch = || batch.into_par_iter().map(&process).collect();
+                match thread_pool {
+                    Some(thread_pool) => thread_pool.install(process_batch),
+                    None => process_batch()
+                }
+            });
+            for processed_row in processed_rows.into_iter().flatten() {
+                inserter.insert_row(processed_row)?;
+                insert_count += 1;
+            }
+        }
+
+        Ok(insert_count)
+    }
+
+    /// Returns the thread pool of this pipeline being built by the first run.
+    /// Returns None if the global thread pool is used.
+    fn thread_pool(&self) -> DataResult<Option<&ThreadPool>> {
+        let thread_count = match self.thread_count {
+            Some(thread_count) => thread_count,
+            None => return Ok(None)
+        };
+        if let Some(thread_pool) = self.thread_pool.get() {
+            return Ok(Some(thread_pool));
+        }
+
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(thread_count)
+            .build()
+            .map_err(|err| DataError::Invalid(err.to_string()))?;
+        Ok(Some(self.thread_pool.get_or_init(|| thread_pool)))
+    }
+}
+
+/// Returns the number of threads for the value of arcpy.env.parallelProcessingFactor.
+/// The factor is a number of processes like "4" or a percentage of the cores like "50%".
+/// A factor of zero disables parallel processing and an empty factor returns None.
+pub fn thread_count_from_factor(factor: &str, core_count: usize) -> Option<usize> {
+    let factor = factor.trim();
+    if let Some(percentage) = factor.strip_suffix('%') {
+        let percentage: f64 = percentage.trim().parse().ok()?;
+        return Some(((core_count as f64 * percentage / 100.0).round() as usize).max(1));
+    }
+
+    let thread_count: f64 = factor.parse().ok()?;
+    Some((thread_count.round() as usize).max(1))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn thread_count_from_parallel_processing_factor() {
+        assert_eq!(Some(4), thread_count_from_factor("4", 8), "Thread count is wrong!");
+        assert_eq!(Some(4), thread_count_from_factor("50%", 8), "Thread count is wrong!");
+        assert_eq!(Some(1), thread_count_from_factor("0", 8), "Thread count is wrong!");
+        assert_eq!(Some(12), thread_count_from_factor("150%", 8), "Thread count is wrong!");
+        assert_eq!(None, thread_count_from_factor("", 8), "Thread count is wrong!");
+    }
+
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn process_batches_in_order() {
+        use crate::data::geojson::GeoJsonDataSource;
+        use crate::data::{TableSchema, Value};
+        use crate::gp::api::FieldType;
+
+        let directory = std::env::temp_dir().join(format!("arcrs_parallel_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let sink = GeoJsonDataSource::new(&directory);
+        let schema = TableSchema::table().with_field("Value", FieldType::Integer);
+        sink.create_table("numbers", &schema).unwrap();
+        sink.create_table("squares", &schema).unwrap();
+        sink.insert("numbers", &["Value"], (1..=100).map(|value| vec![Value::Integer(value)]).collect()).unwrap();
+
+        let gil = Python::acquire_gil();
+        let source = GeoJsonDataSource::new(&directory);
+        let pipeline = ParallelPipeline::new("numbers", &["Value"])
+            .with_batch_size(7)
+            .with_thread_count(4);
+        let mut inserter = sink.insert_cursor("squares", &["Value"]).unwrap();
+        let insert_count = pipeline.run(gil.python(), &source, inserter.as_mut(), |row| match row[0] {
+            Value::Integer(value) if 0 == value % 2 => Some(vec![Value::Integer(value * value)]),
+            _ => None
+        }).unwrap();
+        inserter.finish().unwrap();
+        assert_eq!(50, insert_count, "Insert count is wrong!");
+        let thread_pool = pipeline.thread_pool().unwrap().unwrap() as *const ThreadPool;
+        assert_eq!(thread_pool, pipeline.thread_pool().unwrap().unwrap() as *const ThreadPool, "Thread pool must be reused!");
+        assert_eq!(4, pipeline.thread_pool().unwrap().unwrap().current_num_threads(), "Thread count is wrong!");
+        assert!(ParallelPipeline::new("numbers", &["Value"]).thread_pool().unwrap().is_none(), "Global thread pool must be used!");
+
+        let squares: Vec<Row> = source.search("squares", &["Value"], "").unwrap().collect::<DataResult<Vec<Row>>>().unwrap();
+        let expected: Vec<Row> = (1..=50).map(|value| vec![Value::Integer(4 * value * value)]).collect();
+        assert_eq!(expected, squares, "Squares are wrong!");
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -16,7 +16,24 @@
 //   along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 pub mod api;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "columnar")]
+pub mod columnar;
+#[cfg(feature = "chrono")]
+pub mod dates;
+pub mod describe;
+pub mod edit;
+pub mod geometry;
+pub mod metadata;
+pub mod projection;
+pub mod pyt;
+#[cfg(feature = "runner")]
+pub mod runner;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod tools;
+pub mod workspace;
 
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
@@ -31,30 +48,169 @@ pub struct PyToolboxFactory {
 impl PyToolboxFactory {
 
     /// Creates a new toolbox using the specified geoprocessing tools.
+    /// Use boxed tools or the toolbox builder for offering different tool types.
     pub fn create_toolbox<T: 'static + api::GpTool + Send>(&self, label: &str, alias: &str,
         gp_tools: impl IntoIterator<Item = T>) -> PyResult<PyToolbox> {
-        let mut py_tools = Vec::new();
+        let mut toolbox_builder = PyToolboxBuilder::new(label, alias);
         for gp_tool in gp_tools {
-            let py_tool = PyTool {
-                label: gp_tool.label().to_string(),
-                description: gp_tool.description().to_string(),
-                tool_impl: Box::new(gp_tool)
-            };
-            py_tools.push(py_tool);
-        }
-
-        let py_toolbox = PyToolbox { 
-            label: label.to_string(),
-            alias: alias.to_string(),
-            py_tools: py_tools
-        };
+            toolbox_builder = toolbox_builder.add_tool(gp_tool);
+        }
+
+        toolbox_builder.build()
+    }
+}
+
+
+
+/// Represents a builder for a toolbox offering different tool types.
+/// # Examples
+///
+/// ```ignore
+/// let py_toolbox = gp::PyToolboxBuilder::new("Rust Tools", "rust_tools")
+///     .add_tool(CopyFeaturesTool::default())
+///     .add_tool(DummyGpTool {})
+///     .build()?;
+/// ```
+pub struct PyToolboxBuilder {
+    label: String,
+    alias: String,
+    py_tools: Vec<PyTool>
+}
+
+impl PyToolboxBuilder {
+
+    pub fn new(label: &str, alias: &str) -> PyToolboxBuilder {
+        PyToolboxBuilder {
+            label: label.to_owned(),
+            alias: alias.to_owned(),
+            py_tools: Vec::new()
+        }
+    }
+
+    /// Adds the specified geoprocessing tool.
+    pub fn add_tool<T: 'static + api::GpTool + Send>(mut self, gp_tool: T) -> PyToolboxBuilder {
+        self.py_tools.push(PyTool::new(gp_tool));
+        self
+    }
+
+    /// Adds the specified boxed geoprocessing tool.
+    pub fn add_boxed_tool(mut self, gp_tool: Box<dyn api::GpTool + Send>) -> PyToolboxBuilder {
+        self.py_tools.push(PyTool::from_boxed(gp_tool));
+        self
+    }
 
-        Ok(py_toolbox)
+    /// Builds the toolbox and validates the tools.
+    /// The stable names must be unique Python identifiers, because scripts call the tools like arcpy.alias.ToolName
+    /// and the generated Python toolbox uses the names as class names.
+    /// Categories must not differ only by case, because ArcGIS Pro would show two toolsets.
+    pub fn build(self) -> PyResult<PyToolbox> {
+        for (tool_index, py_tool) in self.py_tools.iter().enumerate() {
+            if py_tool.name.is_empty() {
+                return Err(PyValueError::new_err(format!("The tool '{}' has no name!", py_tool.label)));
+            }
+            if !is_python_identifier(&py_tool.name) {
+                return Err(PyValueError::new_err(format!("The name '{}' of the tool '{}' is not a valid Python identifier!", py_tool.name, py_tool.label)));
+            }
+            if "Toolbox" == py_tool.name {
+                return Err(PyValueError::new_err(format!("The name 'Toolbox' of the tool '{}' is reserved for the Python toolbox!", py_tool.label)));
+            }
+
+            for other_tool in &self.py_tools[..tool_index] {
+                if other_tool.name == py_tool.name {
+                    return Err(PyValueError::new_err(format!("The tools '{}' and '{}' share the name '{}'!", other_tool.label, py_tool.label, py_tool.name)));
+                }
+                if let (Some(category), Some(other_category)) = (&py_tool.category, &other_tool.category) {
+                    if category != other_category && category.eq_ignore_ascii_case(other_category) {
+                        return Err(PyValueError::new_err(format!("The categories '{}' and '{}' differ only by case!", other_category, category)));
+                    }
+                }
+            }
+        }
+
+        Ok(PyToolbox {
+            label: self.label,
+            alias: self.alias,
+            py_tools: self.py_tools
+        })
+    }
+}
+
+/// Checks whether or not the name can be used as Python class name.
+/// Only ASCII identifiers are accepted and Python keywords are rejected.
+pub(crate) fn is_python_identifier(name: &str) -> bool {
+    const PYTHON_KEYWORDS: [&str; 35] = ["False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+        "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda",
+        "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield"];
+
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first_char) if first_char.is_ascii_alphabetic() || '_' == first_char => {
+            chars.all(|c| c.is_ascii_alphanumeric() || '_' == c) && !PYTHON_KEYWORDS.contains(&name)
+        },
+        _ => false
     }
 }
 
 
 
+/// Declares the standard entry points of a native module offering a toolbox.
+/// Every module being declared by this macro can be discovered by the arcrust.ToolRegistry.
+/// The module offers the following entry points:
+/// - `create_toolbox(label=None, alias=None)` creates the toolbox using the default label and alias
+/// - `arcrs_manifest()` returns the module name, toolbox label, toolbox alias, tool labels and the arc-rs version
+/// # Examples
+///
+/// ```ignore
+/// fn build_toolbox(toolbox_builder: gp::PyToolboxBuilder) -> gp::PyToolboxBuilder {
+///     toolbox_builder
+///         .add_tool(CopyFeaturesTool::default())
+///         .add_tool(DummyGpTool {})
+/// }
+///
+/// arcrs::toolbox_module!(copyfeatures, "Rust Copy Tools", "rust_copy_tools", build_toolbox);
+/// ```
+#[macro_export]
+macro_rules! toolbox_module {
+    ($module_name:ident, $label:expr, $alias:expr, $build_toolbox:path) => {
+        /// Creates a new toolbox using the specified or the default label and alias.
+        #[$crate::pyo3::prelude::pyfunction]
+        fn create_toolbox(label: Option<&str>, alias: Option<&str>) -> $crate::pyo3::PyResult<$crate::gp::PyToolbox> {
+            let toolbox_builder = $crate::gp::PyToolboxBuilder::new(label.unwrap_or($label), alias.unwrap_or($alias));
+
+            $build_toolbox(toolbox_builder).build()
+        }
+
+        /// Returns the manifest describing this module.
+        #[$crate::pyo3::prelude::pyfunction]
+        fn arcrs_manifest(py: $crate::pyo3::Python) -> $crate::pyo3::PyResult<$crate::pyo3::PyObject> {
+            use $crate::pyo3::ToPyObject;
+            let py_toolbox = $build_toolbox($crate::gp::PyToolboxBuilder::new($label, $alias)).build()?;
+            let tool_labels: Vec<String> = py_toolbox.py_tools.iter().map(|py_tool| py_tool.label.to_string()).collect();
+
+            let manifest = $crate::pyo3::types::PyDict::new(py);
+            manifest.set_item("module", stringify!($module_name))?;
+            manifest.set_item("label", $label)?;
+            manifest.set_item("alias", $alias)?;
+            manifest.set_item("tools", tool_labels)?;
+            manifest.set_item("arcrs_version", $crate::VERSION)?;
+
+            Ok(manifest.to_object(py))
+        }
+
+        /// This module allows the implementation of Geoprocessing Tools using Rust.
+        #[$crate::pyo3::prelude::pymodule]
+        fn $module_name(_py: $crate::pyo3::Python, module: &$crate::pyo3::types::PyModule) -> $crate::pyo3::PyResult<()> {
+            module.add_class::<$crate::gp::PyToolbox>()?;
+            module.add_function($crate::pyo3::wrap_pyfunction!(create_toolbox, module)?)?;
+            module.add_function($crate::pyo3::wrap_pyfunction!(arcrs_manifest, module)?)?;
+
+            Ok(())
+        }
+    };
+}
+
+
+
 /// Represents a python toolbox offering geoprocessing tools.
 #[pyclass]
 pub struct PyToolbox {
@@ -102,7 +258,87 @@ impl PyToolbox {
         }
     }
 
-    /// Returns all tool names of this toolbox.
+    /// Returns whether or not the specified tool is licensed to execute.
+    fn tool_is_licensed(&self, py: Python, tool_index: usize) -> PyResult<bool> {
+        match self.py_tools.get(tool_index) {
+            Some(py_tool) => py_tool.is_licensed(py),
+            _ => Err(PyValueError::new_err("Tool index is invalid!"))
+        }
+    }
+
+    /// Updates the parameters of the specified tool before internal validation.
+    fn tool_update_parameters(&self, py: Python, tool_index: usize, py_parameters: Vec<PyObject>) -> PyResult<()> {
+        match self.py_tools.get(tool_index) {
+            Some(py_tool) => py_tool.update_parameters(py, py_parameters),
+            _ => Err(PyValueError::new_err("Tool index is invalid!"))
+        }
+    }
+
+    /// Updates the messages of the specified tool after internal validation.
+    fn tool_update_messages(&self, py: Python, tool_index: usize, py_parameters: Vec<PyObject>) -> PyResult<()> {
+        match self.py_tools.get(tool_index) {
+            Some(py_tool) => py_tool.update_messages(py, py_parameters),
+            _ => Err(PyValueError::new_err("Tool index is invalid!"))
+        }
+    }
+
+    /// Returns the metadata of the specified tool.
+    /// The metadata is stored as "<toolbox name>.<class name>.pyt.xml" next to the Python toolbox.
+    fn tool_metadata_xml(&self, tool_index: usize, class_name: &str) -> PyResult<String> {
+        match self.py_tools.get(tool_index) {
+            Some(py_tool) => Ok(metadata::generate_tool_xml(&self.alias, class_name, py_tool.tool_impl.as_ref())),
+            _ => Err(PyValueError::new_err("Tool index is invalid!"))
+        }
+    }
+
+    /// Returns the index of the tool having the specified label.
+    fn tool_index(&self, tool_label: &str) -> PyResult<usize> {
+        match self.find_tool_index(tool_label) {
+            Some(tool_index) => Ok(tool_index),
+            _ => Err(PyValueError::new_err(format!("Tool '{}' does not exist!", tool_label)))
+        }
+    }
+
+    /// Checks whether or not a tool having the specified label exists.
+    fn has_tool(&self, tool_label: &str) -> bool {
+        self.find_tool_index(tool_label).is_some()
+    }
+
+    /// Returns the stable name of the specified tool.
+    fn tool_name(&self, tool_index: usize) -> PyResult<String> {
+        match self.py_tools.get(tool_index) {
+            Some(py_tool) => Ok(py_tool.name.to_string()),
+            _ => Err(PyValueError::new_err("Tool index is invalid!"))
+        }
+    }
+
+    /// Returns the category of the specified tool.
+    fn tool_category(&self, tool_index: usize) -> PyResult<Option<String>> {
+        match self.py_tools.get(tool_index) {
+            Some(py_tool) => Ok(py_tool.category.clone()),
+            _ => Err(PyValueError::new_err("Tool index is invalid!"))
+        }
+    }
+
+    /// Returns the index of the tool having the specified stable name.
+    fn tool_index_by_name(&self, tool_name: &str) -> PyResult<usize> {
+        match self.find_tool_index_by_name(tool_name) {
+            Some(tool_index) => Ok(tool_index),
+            _ => Err(PyValueError::new_err(format!("Tool '{}' does not exist!", tool_name)))
+        }
+    }
+
+    /// Checks whether or not a tool having the specified stable name exists.
+    fn has_tool_name(&self, tool_name: &str) -> bool {
+        self.find_tool_index_by_name(tool_name).is_some()
+    }
+
+    /// Returns the stable names of all tools of this toolbox.
+    fn tool_names(&self) -> Vec<String> {
+        self.py_tools.iter().map(|py_tool| py_tool.name.to_string()).collect()
+    }
+
+    /// Returns all tool labels of this toolbox.
     fn tools(&self) -> PyResult<Vec<String>> {
         let mut py_tool_names = Vec::with_capacity(self.py_tools.len());
         for py_tool in &self.py_tools {
@@ -113,6 +349,29 @@ impl PyToolbox {
     }
 }
 
+impl PyToolbox {
+
+    /// Returns the index of the tool having the specified label.
+    pub fn find_tool_index(&self, tool_label: &str) -> Option<usize> {
+        self.py_tools.iter().position(|py_tool| py_tool.label == tool_label)
+    }
+
+    /// Returns the tool having the specified label.
+    pub fn find_tool(&self, tool_label: &str) -> Option<&PyTool> {
+        self.py_tools.iter().find(|py_tool| py_tool.label == tool_label)
+    }
+
+    /// Returns the index of the tool having the specified stable name.
+    pub fn find_tool_index_by_name(&self, tool_name: &str) -> Option<usize> {
+        self.py_tools.iter().position(|py_tool| py_tool.name == tool_name)
+    }
+
+    /// Returns the tool having the specified stable name.
+    pub fn find_tool_by_name(&self, tool_name: &str) -> Option<&PyTool> {
+        self.py_tools.iter().find(|py_tool| py_tool.name == tool_name)
+    }
+}
+
 
 
 /// Represents the geoprocessing utilities.
@@ -126,6 +385,18 @@ fn create_default_parameter(py: Python, param: api::GpParameter) -> PyResult<&Py
     parameter.setattr("datatype", param.data_type().as_str())?;
     parameter.setattr("parameterType", param.parameter_type().as_str())?;
     parameter.setattr("direction", param.direction().as_str())?;
+    if let Some(filter) = param.filter() {
+        let pyfilter = parameter.getattr("filter")?;
+        pyfilter.setattr("type", filter.as_str())?;
+        match filter {
+            api::GpFilter::ValueList(values) |
+            api::GpFilter::File(values) => pyfilter.setattr("list", values.to_object(py))?,
+            api::GpFilter::Range(min, max) => pyfilter.setattr("list", vec![*min, *max].to_object(py))?
+        }
+    }
+    if let Some(default_value) = param.default_value() {
+        parameter.setattr("value", default_value)?;
+    }
 
     Ok(parameter)
 }
@@ -137,7 +408,9 @@ fn create_features_input_parameter(py: Python) -> PyResult<PyObject> {
         name: String::from("in_features"),
         data_type: api::DataType::GPFeatureLayer,
         parameter_type: api::ParameterType::Required,
-        direction: api::Direction::Input
+        direction: api::Direction::Input,
+        filter: None,
+        default_value: None
     })?;
 
     Ok(parameter.to_object(py))
@@ -150,7 +423,9 @@ fn create_features_output_parameter(py: Python) -> PyResult<PyObject> {
         name: String::from("out_features"),
         data_type: api::DataType::GPFeatureLayer,
         parameter_type: api::ParameterType::Derived,
-        direction: api::Direction::Output
+        direction: api::Direction::Output,
+        filter: None,
+        default_value: None
     })?;
 
     Ok(parameter.to_object(py))
@@ -168,14 +443,14 @@ fn create_arcpy_parameters(py: Python, parameters: Vec<api::GpParameter>) -> PyR
 }
 
 /// Creates parameters from an arcpy parameters array
-fn create_parameters_from_arcpy<'a>(py: &'a Python, py_parameters: Vec<PyObject>) -> Result<Vec<api::PyParameterValue<'a>>, PyErr> {
+fn create_parameters_from_arcpy<'a>(py: &'a Python, py_parameters: Vec<PyObject>) -> Result<api::PyParameterValues<'a>, PyErr> {
     let mut pyparameter_values = Vec::with_capacity(py_parameters.len());
     for py_parameter in py_parameters {
         let pyparameter_value = api::PyParameterValue::new(&py, py_parameter);
         pyparameter_values.push(pyparameter_value);
     }
     
-    Ok(pyparameter_values)
+    api::PyParameterValues::new(pyparameter_values)
 }
 
 
@@ -183,15 +458,40 @@ fn create_parameters_from_arcpy<'a>(py: &'a Python, py_parameters: Vec<PyObject>
 /// Represents a Python geoprocessing tool.
 #[pyclass]
 pub struct PyTool {
+    #[pyo3(get)]
+    pub name: String,
+
     #[pyo3(get)]
     pub label: String,
 
     #[pyo3(get)]
     pub description: String,
 
+    #[pyo3(get)]
+    pub category: Option<String>,
+
     pub tool_impl: Box<dyn api::GpTool + Send>
 }
 
+impl PyTool {
+
+    /// Creates a new tool wrapping the specified geoprocessing tool.
+    pub fn new<T: 'static + api::GpTool + Send>(gp_tool: T) -> PyTool {
+        PyTool::from_boxed(Box::new(gp_tool))
+    }
+
+    /// Creates a new tool wrapping the specified boxed geoprocessing tool.
+    pub fn from_boxed(gp_tool: Box<dyn api::GpTool + Send>) -> PyTool {
+        PyTool {
+            name: gp_tool.name(),
+            label: gp_tool.label().to_string(),
+            description: gp_tool.description().to_string(),
+            category: gp_tool.category().map(|category| category.to_string()),
+            tool_impl: gp_tool
+        }
+    }
+}
+
 #[pymethods]
 impl PyTool {
  
@@ -217,6 +517,23 @@ impl PyTool {
 
         Ok(())
     }
+
+    /// Returns whether or not this tool is licensed to execute.
+    fn is_licensed(&self, py: Python) -> PyResult<bool> {
+        self.tool_impl.is_licensed(py)
+    }
+
+    /// Updates the parameters of this tool before internal validation.
+    fn update_parameters(&self, py: Python, py_parameters: Vec<PyObject>) -> PyResult<()> {
+        let gp_parameters = create_parameters_from_arcpy(&py, py_parameters)?;
+        self.tool_impl.update_parameters(py, gp_parameters)
+    }
+
+    /// Updates the messages of this tool after internal validation.
+    fn update_messages(&self, py: Python, py_parameters: Vec<PyObject>) -> PyResult<()> {
+        let gp_parameters = create_parameters_from_arcpy(&py, py_parameters)?;
+        self.tool_impl.update_messages(py, gp_parameters)
+    }
 }
 
 
@@ -248,4 +565,92 @@ pub struct PyParameter {
     #[pyo3(get)]
     //#[pyo3(item("valueAsText"))]
     pub value: String
-}
\ No newline at end of file
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    struct LabeledTool {
+        name: &'static str,
+        label: &'static str,
+        category: Option<&'static str>
+    }
+
+    impl api::GpTool for LabeledTool {
+
+        fn name(&self) -> String {
+            self.name.to_string()
+        }
+
+        fn label(&self) -> &str {
+            self.label
+        }
+
+        fn description(&self) -> &str {
+            ""
+        }
+
+        fn parameters(&self) -> Vec<api::GpParameter> {
+            Vec::new()
+        }
+
+        fn execute(&self, _py: Python, _parameters: api::PyParameterValues, _messages: api::PyGpMessages) -> PyResult<()> {
+            Ok(())
+        }
+
+        fn category(&self) -> Option<&str> {
+            self.category
+        }
+    }
+
+    fn labeled_tool(name: &'static str, label: &'static str, category: Option<&'static str>) -> LabeledTool {
+        LabeledTool {
+            name,
+            label,
+            category
+        }
+    }
+
+    #[test]
+    fn build_toolbox_with_unique_names() {
+        let py_toolbox = PyToolboxBuilder::new("Rust Tools", "rust_tools")
+            .add_tool(labeled_tool("CopyFeatures", "Copy features", Some("Features")))
+            .add_tool(labeled_tool("CopyRows", "Copy rows", Some("Features")))
+            .add_tool(labeled_tool("Buffer", "Buffer", None))
+            .build()
+            .unwrap();
+        assert_eq!(vec!["CopyFeatures", "CopyRows", "Buffer"], py_toolbox.tool_names(), "Tool names are wrong!");
+
+        let build_result = PyToolboxBuilder::new("Rust Tools", "rust_tools")
+            .add_tool(labeled_tool("CopyFeatures", "Copy features", None))
+            .add_tool(labeled_tool("CopyFeatures", "Copy features (legacy)", None))
+            .build();
+        assert!(build_result.is_err(), "Duplicate names must be rejected!");
+
+        let build_result = PyToolboxBuilder::new("Rust Tools", "rust_tools")
+            .add_tool(labeled_tool("", "Copy features", None))
+            .build();
+        assert!(build_result.is_err(), "Empty names must be rejected!");
+
+        let build_result = PyToolboxBuilder::new("Rust Tools", "rust_tools")
+            .add_tool(labeled_tool("Copy-Features", "Copy features", None))
+            .build();
+        assert!(build_result.is_err(), "Names being no Python identifiers must be rejected!");
+
+        let build_result = PyToolboxBuilder::new("Rust Tools", "rust_tools")
+            .add_tool(labeled_tool("Toolbox", "Toolbox", None))
+            .build();
+        assert!(build_result.is_err(), "The reserved name must be rejected!");
+        assert!(is_python_identifier("Copy_Features2") && !is_python_identifier("3DBuffer") && !is_python_identifier("class"), "Python identifiers are wrong!");
+
+        let build_result = PyToolboxBuilder::new("Rust Tools", "rust_tools")
+            .add_tool(labeled_tool("CopyFeatures", "Copy features", Some("Features")))
+            .add_tool(labeled_tool("CopyRows", "Copy rows", Some("features")))
+            .build();
+        assert!(build_result.is_err(), "Categories differing by case must be rejected!");
+    }
+}
@@ -15,10 +15,24 @@
 //   You should have received a copy of the GNU Lesser General Public License
 //   along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+#[cfg(feature = "chrono")]
+use super::dates;
+use super::describe::DatasetDescription;
+use super::edit::{EditSession, EditSessionBuilder};
+use crate::data::field_type_from_arcpy;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use pyo3::buffer::PyBuffer;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::PyNativeType;
+use std::collections::HashMap;
+use std::rc::Rc;
 use std::str::FromStr;
 
+#[cfg(feature = "derive")]
+pub use arcrs_derive::GpTool;
+
 /// Represents all available geoprocessing parameter data types.
 /// Be aware of the string representation.
 /// Usually aliases like "Feature Set" and "Feature Class" is used by the arcpy environment.
@@ -27,7 +41,11 @@ use std::str::FromStr;
 pub enum DataType {
     DEFeatureClass,
     GPFeatureLayer,
-    GPFeatureRecordSetLayer
+    GPFeatureRecordSetLayer,
+    GPString,
+    GPDouble,
+    GPLong,
+    GPBoolean
 }
 
 impl DataType {
@@ -35,7 +53,11 @@ impl DataType {
         match *self {
             DataType::DEFeatureClass => "DEFeatureClass",
             DataType::GPFeatureLayer => "GPFeatureLayer",
-            DataType::GPFeatureRecordSetLayer => "GPFeatureRecordSetLayer"
+            DataType::GPFeatureRecordSetLayer => "GPFeatureRecordSetLayer",
+            DataType::GPString => "GPString",
+            DataType::GPDouble => "GPDouble",
+            DataType::GPLong => "GPLong",
+            DataType::GPBoolean => "GPBoolean"
         }
     }
 }
@@ -50,13 +72,21 @@ impl FromStr for DataType {
             "Feature-Class" => Ok(DataType::DEFeatureClass),
             "Feature Class" => Ok(DataType::DEFeatureClass),
             "GPFeatureLayer" |
-            "FeatureLayer" => Ok(DataType::GPFeatureLayer),
+            "FeatureLayer" |
+            "Feature Layer" |
+            "Feature-Layer" => Ok(DataType::GPFeatureLayer),
             "GPFeatureRecordSetLayer" => Ok(DataType::GPFeatureRecordSetLayer),
             "Feature-Set" => Ok(DataType::GPFeatureRecordSetLayer),
             "Feature Set" => Ok(DataType::GPFeatureRecordSetLayer),
-            //_ => Err(data_type_str.to_string())
-            _ => todo!("DataType")
-            //_ => unimplemented!()
+            "GPString" |
+            "String" => Ok(DataType::GPString),
+            "GPDouble" |
+            "Double" => Ok(DataType::GPDouble),
+            "GPLong" |
+            "Long" => Ok(DataType::GPLong),
+            "GPBoolean" |
+            "Boolean" => Ok(DataType::GPBoolean),
+            _ => Err(())
         }
     }
 }
@@ -126,13 +156,37 @@ impl FromStr for Direction {
 
 
 
+/// Represents a filter restricting the valid values of a geoprocessing parameter.
+/// For more information take a look at https://pro.arcgis.com/de/pro-app/arcpy/classes/filter.htm
+pub enum GpFilter {
+    ValueList(Vec<String>),
+    Range(f64, f64),
+    File(Vec<String>)
+}
+
+impl GpFilter {
+
+    /// Returns the filter type being used by arcpy.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            GpFilter::ValueList(_) => "ValueList",
+            GpFilter::Range(_, _) => "Range",
+            GpFilter::File(_) => "File"
+        }
+    }
+}
+
+
+
 /// Defines a geoprocessing parameter.
 pub struct GpParameter {
     pub display_name: String,
     pub name: String,
     pub data_type: DataType,
     pub parameter_type: ParameterType,
-    pub direction: Direction
+    pub direction: Direction,
+    pub filter: Option<GpFilter>,
+    pub default_value: Option<String>
 }
 
 impl GpParameter {
@@ -156,6 +210,14 @@ impl GpParameter {
     pub fn direction(&self) -> &Direction {
         return &self.direction;
     }
+
+    pub fn filter(&self) -> Option<&GpFilter> {
+        self.filter.as_ref()
+    }
+
+    pub fn default_value(&self) -> Option<&str> {
+        self.default_value.as_deref()
+    }
 }
 
 // Represents a builder for a geoprocessing parameter
@@ -164,7 +226,9 @@ pub struct GpParameterBuilder {
     name: String,
     data_type: DataType,
     parameter_type: ParameterType,
-    direction: Direction
+    direction: Direction,
+    filter: Option<GpFilter>,
+    default_value: Option<String>
 }
 
 impl GpParameterBuilder {
@@ -175,7 +239,9 @@ impl GpParameterBuilder {
             name: String::from(""),
             data_type: DataType::GPFeatureLayer,
             parameter_type: ParameterType::Optional,
-            direction: Direction::Input
+            direction: Direction::Input,
+            filter: None,
+            default_value: None
         }
     }
 
@@ -204,13 +270,25 @@ impl GpParameterBuilder {
         self
     }
 
+    pub fn with_filter(mut self, filter: GpFilter) -> GpParameterBuilder {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn with_default_value(mut self, default_value: &str) -> GpParameterBuilder {
+        self.default_value = Some(default_value.to_owned());
+        self
+    }
+
     pub fn build(self) -> GpParameter {
         GpParameter {
             display_name: self.display_name,
             name: self.name,
             data_type: self.data_type,
             parameter_type: self.parameter_type,
-            direction: self.direction
+            direction: self.direction,
+            filter: self.filter,
+            default_value: self.default_value
         }
     }
 }
@@ -252,10 +330,8 @@ impl PyParameterValue<'_> {
     pub fn data_type(&self) -> PyResult<DataType> {
         let pydata_type = self.py_parameter.getattr(*self.py, "datatype")?;
         let data_type_as_text: &str = pydata_type.extract(*self.py)?;
-        match DataType::from_str(data_type_as_text) {
-            Ok(data_type) => Ok(data_type),
-            _ => todo!("DataType")
-        }        
+        DataType::from_str(data_type_as_text)
+            .map_err(|_| PyValueError::new_err(format!("Data type '{}' is not supported!", data_type_as_text)))
     }
 
     pub fn data_type_as_str(&self) -> PyResult<String> {
@@ -286,6 +362,15 @@ impl PyParameterValue<'_> {
         Ok(exists)
     }
 
+    /// Describes the value of this parameter using one arcpy.Describe call.
+    /// The parameter must represent an existing table or feature class.
+    /// Prefer this over the single properties like fields and oid_field_name when a tool needs more than one of them.
+    pub fn describe(&self) -> PyResult<DatasetDescription> {
+        let pyvalue = self.value()?;
+
+        DatasetDescription::new(*self.py, pyvalue.as_ref(*self.py))
+    }
+
     /// Extracts the fields out of this paramater.
     /// The parameter must represent a table or record set.
     pub fn fields(&self) -> PyResult<Vec<GpField>> {
@@ -296,17 +381,13 @@ impl PyParameterValue<'_> {
         let mut gp_fields = Vec::with_capacity(fields.len());
         for pyfield in fields {
             let field_name: String = pyfield.getattr("name")?.extract()?;
-            let field_type_as_text = pyfield.getattr("type")?.extract()?;
-            match FieldType::from_str(field_type_as_text) {
-                Ok(field_type) => {
-                    let gp_field = GpField {
-                        name: field_name,
-                        field_type
-                    };
-                    gp_fields.push(gp_field);
-                }
-                _ => todo!("FieldType")
-            }
+            let field_type_as_text: String = pyfield.getattr("type")?.extract()?;
+            let field_type = field_type_from_arcpy(&field_type_as_text)
+                .ok_or_else(|| PyValueError::new_err(format!("Field type '{}' is not supported!", field_type_as_text)))?;
+            gp_fields.push(GpField {
+                name: field_name,
+                field_type
+            });
         }
 
         Ok(gp_fields)
@@ -338,10 +419,8 @@ impl PyParameterValue<'_> {
         let arcpy = PyModule::import(*self.py, "arcpy")?;
         let pyvalue_describe = arcpy.call1("Describe", (self.value()?,))?;
         let shape_type_as_text: String = pyvalue_describe.getattr("shapeType")?.extract()?;
-        match ShapeType::from_str(&shape_type_as_text) {
-            Ok(shape_type) => Ok(shape_type),
-            _ => todo!("ShapeType")
-        }
+        ShapeType::from_str(&shape_type_as_text)
+            .map_err(|_| PyValueError::new_err(format!("Shape type '{}' is not supported!", shape_type_as_text)))
     }
 
     /// Extracts the spatial reference out of this parameter.
@@ -367,11 +446,301 @@ impl PyParameterValue<'_> {
 
     pub fn value(&self) -> PyResult<PyObject> {
         let pyvalue = self.py_parameter.getattr(*self.py, "value")?;
-        
+
         Ok(pyvalue)
     }
+
+    /// Sets the value of this parameter.
+    /// Use this for derived or output parameters so that the value
+    /// is available for subsequent tools e.g. in ModelBuilder.
+    pub fn set_value<T: ToPyObject>(&self, value: T) -> PyResult<()> {
+        self.py_parameter.as_ref(*self.py).setattr("value", value.to_object(*self.py))
+    }
+
+    /// Sets a text value for this parameter.
+    pub fn set_strvalue(&self, value: &str) -> PyResult<()> {
+        self.set_value(value)
+    }
+
+    /// Sets an integer value for this parameter.
+    pub fn set_intvalue(&self, value: i64) -> PyResult<()> {
+        self.set_value(value)
+    }
+
+    /// Sets a double value for this parameter.
+    pub fn set_doublevalue(&self, value: f64) -> PyResult<()> {
+        self.set_value(value)
+    }
+
+    /// Extracts the date and time out of this parameter.
+    /// The parameter must represent a date.
+    #[cfg(feature = "chrono")]
+    pub fn as_datetimevalue(&self) -> PyResult<NaiveDateTime> {
+        let pyvalue = self.value()?;
+
+        dates::naive_datetime_from_py(pyvalue.as_ref(*self.py))
+    }
+
+    /// Sets a date and time value for this parameter.
+    #[cfg(feature = "chrono")]
+    pub fn set_datetimevalue(&self, value: &NaiveDateTime) -> PyResult<()> {
+        self.set_value(dates::naive_datetime_to_py(*self.py, value)?)
+    }
+
+    /// Sets the catalog path of a dataset as the value of this parameter.
+    /// The parameter must represent a table, feature class or layer.
+    pub fn set_catalog_path(&self, catalog_path: &str) -> PyResult<()> {
+        self.set_value(catalog_path)
+    }
+
+    /// Loads the features of the specified catalog path into a new feature set
+    /// and sets this feature set as the value of this parameter.
+    /// The parameter must represent a feature set.
+    /// For more information take a look at https://pro.arcgis.com/de/pro-app/arcpy/classes/featureset.htm
+    pub fn set_feature_set(&self, catalog_path: &str) -> PyResult<()> {
+        let arcpy = PyModule::import(*self.py, "arcpy")?;
+        let pyfeature_set = arcpy.call0("FeatureSet")?;
+        pyfeature_set.call_method1("load", (catalog_path,))?;
+        self.set_value(pyfeature_set)
+    }
+
+    /// Marks this parameter as having a warning using the specified message.
+    /// Use this while updating the messages of a tool.
+    pub fn set_warning_message(&self, message: &str) -> PyResult<()> {
+        self.py_parameter.call_method1(*self.py, "setWarningMessage", (message,))?;
+
+        Ok(())
+    }
+
+    /// Marks this parameter as having an error using the specified message.
+    /// Use this while updating the messages of a tool.
+    pub fn set_error_message(&self, message: &str) -> PyResult<()> {
+        self.py_parameter.call_method1(*self.py, "setErrorMessage", (message,))?;
+
+        Ok(())
+    }
+
+    /// Checks whether or not this parameter has a value.
+    /// Optional parameters being left empty by the user have no value.
+    pub fn has_value(&self) -> PyResult<bool> {
+        let pyvalue = self.value()?;
+
+        Ok(!pyvalue.is_none(*self.py))
+    }
+}
+
+
+
+/// Represents all parameter values passed to a geoprocessing tool.
+/// The values keep the order of the declared parameters and can be accessed
+/// by index or by the name of the parameter.
+pub struct PyParameterValues<'a> {
+    names: Vec<String>,
+    values: Vec<PyParameterValue<'a>>
 }
 
+impl<'a> PyParameterValues<'a> {
+
+    /// Creates a new collection by using the name of every parameter as key.
+    pub fn new(values: Vec<PyParameterValue<'a>>) -> PyResult<PyParameterValues<'a>> {
+        let mut names = Vec::with_capacity(values.len());
+        for value in &values {
+            names.push(value.name()?);
+        }
+
+        Ok(PyParameterValues {
+            names,
+            values
+        })
+    }
+
+    /// Returns the parameter having the specified name.
+    /// Raises an error naming the parameter if no such parameter exists.
+    pub fn get(&self, name: &str) -> PyResult<&PyParameterValue<'a>> {
+        match self.index_of(name) {
+            Some(index) => Ok(&self.values[index]),
+            None => Err(PyValueError::new_err(format!("Parameter '{}' does not exist!", name)))
+        }
+    }
+
+    /// Returns the parameter having the specified name only if it has a value.
+    /// Use this for optional parameters.
+    pub fn get_optional(&self, name: &str) -> PyResult<Option<&PyParameterValue<'a>>> {
+        let value = self.get(name)?;
+        if value.has_value()? {
+            Ok(Some(value))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Sets the value of the parameter having the specified name.
+    /// Raises an error naming the parameter if no such parameter exists.
+    pub fn set_value<T: ToPyObject>(&self, name: &str, value: T) -> PyResult<()> {
+        self.get(name)?.set_value(value)
+    }
+
+    /// Checks whether or not a parameter having the specified name exists.
+    pub fn contains(&self, name: &str) -> bool {
+        self.index_of(name).is_some()
+    }
+
+    /// Checks whether or not the parameter having the specified name has a value.
+    /// Raises an error naming the parameter if no such parameter exists.
+    pub fn is_set(&self, name: &str) -> PyResult<bool> {
+        self.get(name)?.has_value()
+    }
+
+    /// Returns the index of the parameter having the specified name.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|known_name| known_name == name)
+    }
+
+    /// Returns the names of all parameters.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, PyParameterValue<'a>> {
+        self.values.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<'a> std::ops::Index<usize> for PyParameterValues<'a> {
+
+    type Output = PyParameterValue<'a>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.values[index]
+    }
+}
+
+impl<'a> IntoIterator for PyParameterValues<'a> {
+
+    type Item = PyParameterValue<'a>;
+    type IntoIter = std::vec::IntoIter<PyParameterValue<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b PyParameterValues<'a> {
+
+    type Item = &'b PyParameterValue<'a>;
+    type IntoIter = std::slice::Iter<'b, PyParameterValue<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter()
+    }
+}
+
+/// Offers the typed extraction of a value out of a geoprocessing parameter.
+/// Tools declared by using the GpTool derive macro extract every field using this trait.
+pub trait FromParameterValue: Sized {
+
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<Self>;
+}
+
+impl FromParameterValue for String {
+
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<String> {
+        parameter.value_as_text()
+    }
+}
+
+impl FromParameterValue for f64 {
+
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<f64> {
+        parameter.value()?.extract(*parameter.py)
+    }
+}
+
+impl FromParameterValue for f32 {
+
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<f32> {
+        parameter.value()?.extract(*parameter.py)
+    }
+}
+
+impl FromParameterValue for i64 {
+
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<i64> {
+        parameter.value()?.extract(*parameter.py)
+    }
+}
+
+impl FromParameterValue for i32 {
+
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<i32> {
+        parameter.value()?.extract(*parameter.py)
+    }
+}
+
+impl FromParameterValue for i16 {
+
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<i16> {
+        parameter.value()?.extract(*parameter.py)
+    }
+}
+
+impl FromParameterValue for bool {
+
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<bool> {
+        parameter.value()?.extract(*parameter.py)
+    }
+}
+
+/// Parameters without a value are extracted as None.
+impl<T: FromParameterValue> FromParameterValue for Option<T> {
+
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<Option<T>> {
+        if parameter.has_value()? {
+            Ok(Some(T::from_parameter_value(parameter)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+
+
+/// Represents the catalog path of a dataset like a table or feature class.
+#[derive(Clone, Default)]
+pub struct CatalogPath {
+    pub path: String
+}
+
+impl FromParameterValue for CatalogPath {
+
+    /// Extracts the catalog path using arcpy.Describe.
+    /// The parameter must represent a table or record set.
+    fn from_parameter_value(parameter: &PyParameterValue) -> PyResult<CatalogPath> {
+        let catalog_path = CatalogPath {
+            path: parameter.catalog_path()?
+        };
+
+        Ok(catalog_path)
+    }
+}
+
+impl std::fmt::Display for CatalogPath {
+
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "{}", self.path)
+    }
+}
+
+
+
 /// Implements the conversion from catalog path into a search cursor.
 impl IntoCursor for PyParameterValue<'_> {
     
@@ -392,6 +761,7 @@ impl IntoCursor for PyParameterValue<'_> {
 
 
 /// Represents a field returned by arcpy.Describe or arcpy.ListFields.
+#[derive(Clone, Debug, PartialEq)]
 pub struct GpField {
     pub name: String,
     pub field_type: FieldType
@@ -400,14 +770,26 @@ pub struct GpField {
 
 
 /// Represents all known field types.
-#[derive(Copy, Clone)]
+/// New field types of ArcGIS Pro are added without a major release, so that matches need a wildcard arm.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum FieldType {
     OID,
     Geometry,
     Date,
+    DateOnly,
+    TimeOnly,
+    TimestampOffset,
     Double,
     Integer,
-    String
+    /// Represents a 64-bit integer field.
+    BigInteger,
+    String,
+    Guid,
+    /// Represents the GUID field being maintained by the workspace.
+    GlobalID,
+    Blob,
+    Raster
 }
 
 impl FieldType {
@@ -417,20 +799,31 @@ impl FieldType {
             FieldType::OID => "OID",
             FieldType::Geometry => "Geometry",
             FieldType::Date => "Date",
+            FieldType::DateOnly => "DateOnly",
+            FieldType::TimeOnly => "TimeOnly",
+            FieldType::TimestampOffset => "TimestampOffset",
             FieldType::Double => "Double",
             FieldType::Integer => "Integer",
-            FieldType::String => "String"
+            FieldType::BigInteger => "BigInteger",
+            FieldType::String => "String",
+            FieldType::Guid => "Guid",
+            FieldType::GlobalID => "GlobalID",
+            FieldType::Blob => "Blob",
+            FieldType::Raster => "Raster"
         }
     }
 
     /// Some geoprocessing tools like arcpy.management.AddFields
     /// expect the following values:
-    /// Text | Float | Double | Short | Long | Date | BLOB | Raster | GUID
+    /// Text | Float | Double | Short | Long | BigInteger | Date | DateOnly | TimeOnly | TimestampOffset | BLOB | Raster | GUID
+    /// GlobalID fields cannot be added and are added as GUID fields.
     pub fn as_gpstr(&self) -> &'static str {
         match *self {
             FieldType::Double => "Float",
             FieldType::Integer => "Long",
             FieldType::String => "Text",
+            FieldType::Guid | FieldType::GlobalID => "GUID",
+            FieldType::Blob => "BLOB",
             _ => self.as_str()
         }
     }
@@ -445,9 +838,17 @@ impl FromStr for FieldType {
             "OID" => Ok(FieldType::OID),
             "Geometry" => Ok(FieldType::Geometry),
             "Date" => Ok(FieldType::Date),
+            "DateOnly" => Ok(FieldType::DateOnly),
+            "TimeOnly" => Ok(FieldType::TimeOnly),
+            "TimestampOffset" => Ok(FieldType::TimestampOffset),
             "Double" => Ok(FieldType::Double),
             "Integer" => Ok(FieldType::Integer),
+            "BigInteger" => Ok(FieldType::BigInteger),
             "String" => Ok(FieldType::String),
+            "Guid" => Ok(FieldType::Guid),
+            "GlobalID" => Ok(FieldType::GlobalID),
+            "Blob" => Ok(FieldType::Blob),
+            "Raster" => Ok(FieldType::Raster),
             _ => Err(())
         }
     }
@@ -456,11 +857,13 @@ impl FromStr for FieldType {
 
 
 /// Represents all known shape types.
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ShapeType {
     Point,
     Polyline,
     Polygon,
-    Multipoint
+    Multipoint,
+    MultiPatch
 }
 
 impl ShapeType {
@@ -470,7 +873,8 @@ impl ShapeType {
             ShapeType::Point => "POINT",
             ShapeType::Polyline => "POLYLINE",
             ShapeType::Polygon => "POLYGON",
-            ShapeType::Multipoint => "MULTIPOINT"
+            ShapeType::Multipoint => "MULTIPOINT",
+            ShapeType::MultiPatch => "MULTIPATCH"
         }
     }
 }
@@ -485,14 +889,107 @@ impl FromStr for ShapeType {
             "Polyline" | "POLYLINE" => Ok(ShapeType::Polyline),
             "Polygon" | "POLYGON" => Ok(ShapeType::Polygon),
             "Multipoint" | "MULTIPOINT" => Ok(ShapeType::Multipoint),
+            "MultiPatch" | "Multipatch" | "MULTIPATCH" => Ok(ShapeType::MultiPatch),
+            _ => Err(())
+        }
+    }
+}
+
+
+
+/// Represents the tokens of cursor field lists.
+/// A token requests exactly the representation a tool needs, so that no geometry object is created per row
+/// unless the tool asks for the whole shape.
+/// # Examples
+///
+/// ```ignore
+/// let field_names = vec![FieldToken::OID.into(), FieldToken::ShapeXY.into(), String::from("Name")];
+/// let row = search_cursor.next()?;
+/// let oid = row.as_oidvalue(0)?;
+/// let location = row.as_point(1)?;
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FieldToken {
+    /// The value of the object ID field.
+    OID,
+    /// The geometry object.
+    Shape,
+    /// The centroid as a tuple of x and y.
+    ShapeXY,
+    /// The true centroid as a tuple of x and y.
+    ShapeTrueCentroid,
+    ShapeX,
+    ShapeY,
+    ShapeZ,
+    ShapeM,
+    /// The geometry as Esri JSON.
+    ShapeJSON,
+    /// The geometry as well-known binary.
+    ShapeWKB,
+    /// The geometry as well-known text.
+    ShapeWKT,
+    /// The area as a double.
+    ShapeArea,
+    /// The length as a double.
+    ShapeLength
+}
+
+impl FieldToken {
+
+    pub const fn as_str(&self) -> &'static str {
+        match *self {
+            FieldToken::OID => "OID@",
+            FieldToken::Shape => "SHAPE@",
+            FieldToken::ShapeXY => "SHAPE@XY",
+            FieldToken::ShapeTrueCentroid => "SHAPE@TRUECENTROID",
+            FieldToken::ShapeX => "SHAPE@X",
+            FieldToken::ShapeY => "SHAPE@Y",
+            FieldToken::ShapeZ => "SHAPE@Z",
+            FieldToken::ShapeM => "SHAPE@M",
+            FieldToken::ShapeJSON => "SHAPE@JSON",
+            FieldToken::ShapeWKB => "SHAPE@WKB",
+            FieldToken::ShapeWKT => "SHAPE@WKT",
+            FieldToken::ShapeArea => "SHAPE@AREA",
+            FieldToken::ShapeLength => "SHAPE@LENGTH"
+        }
+    }
+}
+
+impl FromStr for FieldToken {
+
+    type Err = ();
+
+    fn from_str(token_str: &str) -> Result<FieldToken, Self::Err> {
+        match token_str.to_uppercase().as_str() {
+            "OID@" => Ok(FieldToken::OID),
+            "SHAPE@" => Ok(FieldToken::Shape),
+            "SHAPE@XY" => Ok(FieldToken::ShapeXY),
+            "SHAPE@TRUECENTROID" => Ok(FieldToken::ShapeTrueCentroid),
+            "SHAPE@X" => Ok(FieldToken::ShapeX),
+            "SHAPE@Y" => Ok(FieldToken::ShapeY),
+            "SHAPE@Z" => Ok(FieldToken::ShapeZ),
+            "SHAPE@M" => Ok(FieldToken::ShapeM),
+            "SHAPE@JSON" => Ok(FieldToken::ShapeJSON),
+            "SHAPE@WKB" => Ok(FieldToken::ShapeWKB),
+            "SHAPE@WKT" => Ok(FieldToken::ShapeWKT),
+            "SHAPE@AREA" => Ok(FieldToken::ShapeArea),
+            "SHAPE@LENGTH" => Ok(FieldToken::ShapeLength),
             _ => Err(())
         }
     }
 }
 
+impl From<FieldToken> for String {
+
+    fn from(token: FieldToken) -> String {
+        token.as_str().to_string()
+    }
+}
+
 
 
 /// Represents a spatial reference.
+#[derive(Clone, Debug, PartialEq)]
 pub struct GpSpatialReference {
     pub wkid: i32
 }
@@ -513,25 +1010,43 @@ impl PyGpMessages<'_> {
         Ok(())
     }
 
+    /// Adds a warning message.
+    pub fn add_warning(&self, message: &str) -> PyResult<()> {
+        self.py_messages.call_method1(*self.py, "addWarningMessage", (message.to_string(), ))?;
+
+        Ok(())
+    }
+
+    /// Adds an error message.
+    /// The tool execution does not fail unless an error is returned.
+    pub fn add_error(&self, message: &str) -> PyResult<()> {
+        self.py_messages.call_method1(*self.py, "addErrorMessage", (message.to_string(), ))?;
+
+        Ok(())
+    }
+
 }
 
 
 
 /// Represents a search cursor.
+/// The rows know the field names of the cursor, so that their values can be accessed by name.
 pub struct PySearchCursor<'a> {
     py: &'a Python<'a>,
-    pycursor: &'a PyAny
+    pycursor: &'a PyAny,
+    field_names: Rc<[String]>
 }
 
 impl PySearchCursor<'_> {
 
     pub fn new<'a>(py: &'a Python, catalog_path: &str, field_names: Vec<String>, where_clause: &str) -> PyResult<PySearchCursor<'a>> {
         let arcpy_da = PyModule::import(*py, "arcpy.da")?;
-        let pycursor = arcpy_da.call1("SearchCursor", (catalog_path, field_names, where_clause))?;
+        let pycursor = arcpy_da.call1("SearchCursor", (catalog_path, field_names.clone(), where_clause))?;
 
         let new_instance = PySearchCursor {
             py,
-            pycursor
+            pycursor,
+            field_names: field_names.into()
         };
 
         Ok(new_instance)
@@ -539,7 +1054,8 @@ impl PySearchCursor<'_> {
 
     pub fn next(&self) -> PyResult<PyRow> {
         let row_values = self.pycursor.call_method0("next")?.extract()?;
-        let row = PyRow::new(self.py, row_values);
+        let row = PyRow::new(self.py, row_values)
+            .with_field_names(self.field_names.clone());
 
         Ok(row)
     }
@@ -547,6 +1063,11 @@ impl PySearchCursor<'_> {
     pub fn next_row(&self) -> PyResult<&PyAny> {
         self.pycursor.call_method0("next")
     }
+
+    /// Returns the field names and tokens being requested by this cursor.
+    pub fn field_names(&self) -> &[String] {
+        &self.field_names
+    }
 }
 
 
@@ -579,6 +1100,96 @@ impl PyInsertCursor<'_> {
     pub fn insert_row(&self, row: &PyAny) -> PyResult<&PyAny> {
         self.pycursor.call_method1("insertRow", (row, ))
     }
+
+    /// Inserts the values of the buffer and returns the new object ID.
+    /// The buffer is cleared but keeps its capacity, so that it can be reused for the next row.
+    pub fn insert_buffer(&self, insert_buffer: &mut InsertBuffer) -> PyResult<i64> {
+        let py = self.pycursor.py();
+        let py_oid = self.pycursor.call_method1("insertRow", (insert_buffer.values.to_object(py), ))?;
+        insert_buffer.reset();
+
+        py_oid.extract()
+    }
+
+    /// Inserts all rows and returns their object IDs.
+    /// Every row is a tuple or vector of values ordered like the field names.
+    /// The insertRow method is only looked up once and all temporary Python objects are released row by row,
+    /// so that bulk loads do not pile up Python objects.
+    pub fn insert_rows<I, R>(&self, rows: I) -> PyResult<Vec<i64>>
+    where
+        I: IntoIterator<Item = R>,
+        R: ToPyObject
+    {
+        let py = self.pycursor.py();
+        let insert_row: PyObject = self.pycursor.getattr("insertRow")?.into();
+        let rows = rows.into_iter();
+        let mut oids = Vec::with_capacity(rows.size_hint().0);
+        for row in rows {
+            let py_oid = insert_row.call1(py, (row.to_object(py), ))?;
+            oids.push(py_oid.extract(py)?);
+        }
+
+        Ok(oids)
+    }
+
+    /// Releases the cursor and its locks like leaving a with statement does.
+    pub fn close(&self) -> PyResult<()> {
+        if self.pycursor.hasattr("__exit__")? {
+            let py = self.pycursor.py();
+            self.pycursor.call_method1("__exit__", (py.None(), py.None(), py.None()))?;
+        }
+
+        Ok(())
+    }
+}
+
+
+
+/// Represents a bulk load of rows into a table or feature class using one insert cursor.
+/// The load can be wrapped in an edit session, which is required for versioned data
+/// and for feature classes participating in a topology or geometric network.
+pub struct PyBatchInsert {
+    catalog_path: String,
+    field_names: Vec<String>,
+    edit_workspace: Option<String>
+}
+
+impl PyBatchInsert {
+
+    pub fn new(catalog_path: &str, field_names: Vec<String>) -> PyBatchInsert {
+        PyBatchInsert {
+            catalog_path: catalog_path.to_string(),
+            field_names,
+            edit_workspace: None
+        }
+    }
+
+    /// Inserts the rows in an edit session of the specified workspace.
+    /// All rows are discarded if any insert fails.
+    pub fn with_edit_session(mut self, workspace: &str) -> PyBatchInsert {
+        self.edit_workspace = Some(workspace.to_string());
+        self
+    }
+
+    /// Inserts all rows and returns their object IDs.
+    pub fn insert<I, R>(&self, py: Python, rows: I) -> PyResult<Vec<i64>>
+    where
+        I: IntoIterator<Item = R>,
+        R: ToPyObject
+    {
+        let insert_rows = || -> PyResult<Vec<i64>> {
+            let insert_cursor = PyInsertCursor::new(&py, &self.catalog_path, self.field_names.clone())?;
+            let oids = insert_cursor.insert_rows(rows);
+            insert_cursor.close()?;
+
+            oids
+        };
+
+        match &self.edit_workspace {
+            Some(edit_workspace) => EditSession::run(py, EditSessionBuilder::new(edit_workspace).with_undo(false), |_| insert_rows()),
+            None => insert_rows()
+        }
+    }
 }
 
 
@@ -601,6 +1212,51 @@ impl InsertBuffer {
         self.values.push(value.to_object(py));
     }
 
+    /// Adds a new value for a Date field.
+    #[cfg(feature = "chrono")]
+    pub fn add_datetimevalue(&mut self, py: Python, value: &NaiveDateTime) -> PyResult<()> {
+        self.values.push(dates::naive_datetime_to_py(py, value)?);
+
+        Ok(())
+    }
+
+    /// Adds a new value for a DateOnly field.
+    #[cfg(feature = "chrono")]
+    pub fn add_datevalue(&mut self, py: Python, value: &NaiveDate) -> PyResult<()> {
+        self.values.push(dates::naive_date_to_py(py, value)?);
+
+        Ok(())
+    }
+
+    /// Adds a new value for a TimeOnly field.
+    #[cfg(feature = "chrono")]
+    pub fn add_timevalue(&mut self, py: Python, value: &NaiveTime) -> PyResult<()> {
+        self.values.push(dates::naive_time_to_py(py, value)?);
+
+        Ok(())
+    }
+
+    /// Adds a new value for a TimestampOffset field.
+    #[cfg(feature = "chrono")]
+    pub fn add_datetimeoffsetvalue(&mut self, py: Python, value: &DateTime<FixedOffset>) -> PyResult<()> {
+        self.values.push(dates::datetime_offset_to_py(py, value)?);
+
+        Ok(())
+    }
+
+    /// Adds a null value into this buffer.
+    pub fn add_null(&mut self, py: Python) {
+        self.values.push(py.None());
+    }
+
+    /// Adds the value or a null value into this buffer.
+    pub fn add_optional_value<T: ToPyObject>(&mut self, py: Python, value: Option<T>) {
+        match value {
+            Some(value) => self.add_value(py, value),
+            None => self.add_null(py)
+        }
+    }
+
     /// Returns all values and replaces the internal values with an empty vector!
     pub fn values(&mut self) -> Vec<PyObject> {
         let empty_values = Vec::with_capacity(self.values.len());
@@ -616,9 +1272,11 @@ impl InsertBuffer {
 
 
 /// Represents a row from a cursor.
+/// Null values are represented by None, use the optional accessors for nullable fields.
 pub struct PyRow<'a> {
     pub py: &'a Python<'a>,
-    pub py_values: Vec<PyObject>
+    pub py_values: Vec<PyObject>,
+    field_names: Option<Rc<[String]>>
 }
 
 impl PyRow<'_> {
@@ -626,8 +1284,107 @@ impl PyRow<'_> {
     pub fn new<'a>(py: &'a Python, py_values: Vec<PyObject>) -> PyRow<'a> {
         PyRow {
             py,
-            py_values
+            py_values,
+            field_names: None
+        }
+    }
+
+    /// Sets the field names of the cursor, so that the values can be accessed by name.
+    pub fn with_field_names(mut self, field_names: Rc<[String]>) -> Self {
+        self.field_names = Some(field_names);
+        self
+    }
+
+    /// Returns the index of the specified field name ignoring the case.
+    pub fn field_index(&self, field_name: &str) -> PyResult<usize> {
+        let field_names = self.field_names.as_ref()
+            .ok_or_else(|| PyValueError::new_err("The row has no field names!"))?;
+        field_names.iter()
+            .position(|name| name.eq_ignore_ascii_case(field_name))
+            .ok_or_else(|| PyValueError::new_err(format!("Field '{}' is not part of the row!", field_name)))
+    }
+
+    /// Returns the index of the specified token like FieldToken::ShapeXY.
+    pub fn token_index(&self, token: FieldToken) -> PyResult<usize> {
+        self.field_index(token.as_str())
+    }
+
+    /// Returns the object ID being requested using the OID@ token.
+    pub fn as_oidvalue(&self, index: usize) -> PyResult<i64> {
+        self.value(index)
+    }
+
+    /// Returns the point being requested using the SHAPE@XY or SHAPE@TRUECENTROID token.
+    /// A point geometry being requested using the SHAPE@ token is also supported.
+    pub fn as_point(&self, index: usize) -> PyResult<Point> {
+        let pyvalue: &PyAny = self.value(index)?;
+        if pyvalue.is_none() {
+            return Err(PyValueError::new_err("The geometry is empty!"));
+        }
+        if let Ok((x, y)) = pyvalue.extract::<(f64, f64)>() {
+            return Ok(Point {
+                x,
+                y
+            });
+        }
+
+        pyvalue.extract()
+    }
+
+    /// Returns the well-known binary being requested using the SHAPE@WKB token.
+    /// The bytes are copied using the buffer protocol.
+    pub fn as_wkbvalue(&self, index: usize) -> PyResult<Vec<u8>> {
+        let pyvalue: &PyAny = self.value(index)?;
+        let buffer = PyBuffer::<u8>::get(pyvalue)?;
+
+        buffer.to_vec(*self.py)
+    }
+
+    /// Checks whether or not the value at the specified field index is null.
+    pub fn is_null(&self, index: usize) -> PyResult<bool> {
+        match &self.py_values.get(index) {
+            Some(pyvalue) => Ok(pyvalue.is_none(*self.py)),
+            _ => Err(PyValueError::new_err("Failed to access the row value!"))
+        }
+    }
+
+    /// Returns the value at the specified field index or None if the value is null.
+    pub fn optional_value<'a, T: FromPyObject<'a>>(&'a self, index: usize) -> PyResult<Option<T>> {
+        if self.is_null(index)? {
+            return Ok(None);
+        }
+
+        self.value(index).map(Some)
+    }
+
+    /// Returns the value of the specified field or None if the value is null.
+    pub fn value_by_name<'a, T: FromPyObject<'a>>(&'a self, field_name: &str) -> PyResult<Option<T>> {
+        self.optional_value(self.field_index(field_name)?)
+    }
+
+    /// Returns the text representation of the value or None if the value is null.
+    pub fn as_optional_strvalue(&self, index: usize) -> PyResult<Option<String>> {
+        if self.is_null(index)? {
+            return Ok(None);
+        }
+
+        self.as_strvalue(index).map(Some)
+    }
+
+    pub fn as_optional_intvalue(&self, index: usize) -> PyResult<Option<i32>> {
+        if self.is_null(index)? {
+            return Ok(None);
+        }
+
+        self.as_intvalue(index).map(Some)
+    }
+
+    pub fn as_optional_doublevalue(&self, index: usize) -> PyResult<Option<f64>> {
+        if self.is_null(index)? {
+            return Ok(None);
         }
+
+        self.as_doublevalue(index).map(Some)
     }
 
     /// Returns the value at the specified field index.
@@ -657,6 +1414,8 @@ impl PyRow<'_> {
         }
     }
 
+    /// Returns the text representation of the value.
+    /// Null values are returned as "None", use as_optional_strvalue for nullable fields.
     pub fn as_strvalue(&self, index: usize) -> PyResult<String> {
         match &self.py_values.get(index) {
             Some(pytuple) => {
@@ -690,6 +1449,38 @@ impl PyRow<'_> {
         }
     }
 
+    /// Returns the value of a Date field.
+    #[cfg(feature = "chrono")]
+    pub fn as_datetimevalue(&self, index: usize) -> PyResult<NaiveDateTime> {
+        self.convert_value(index, dates::naive_datetime_from_py)
+    }
+
+    /// Returns the value of a DateOnly field.
+    #[cfg(feature = "chrono")]
+    pub fn as_datevalue(&self, index: usize) -> PyResult<NaiveDate> {
+        self.convert_value(index, dates::naive_date_from_py)
+    }
+
+    /// Returns the value of a TimeOnly field.
+    #[cfg(feature = "chrono")]
+    pub fn as_timevalue(&self, index: usize) -> PyResult<NaiveTime> {
+        self.convert_value(index, dates::naive_time_from_py)
+    }
+
+    /// Returns the value of a TimestampOffset field.
+    #[cfg(feature = "chrono")]
+    pub fn as_datetimeoffsetvalue(&self, index: usize) -> PyResult<DateTime<FixedOffset>> {
+        self.convert_value(index, dates::datetime_offset_from_py)
+    }
+
+    #[cfg(feature = "chrono")]
+    fn convert_value<T>(&self, index: usize, convert: fn(&PyAny) -> PyResult<T>) -> PyResult<T> {
+        match &self.py_values.get(index) {
+            Some(pyvalue) => convert(pyvalue.as_ref(*self.py)),
+            _ => Err(PyValueError::new_err("Failed to access the row value!"))
+        }
+    }
+
     pub fn value_count(&self) -> usize {
         self.py_values.len()
     }
@@ -790,14 +1581,393 @@ pub trait IntoCursor {
 
 
 
+/// Represents the documentation of a geoprocessing tool.
+/// ArcGIS Pro shows the documentation as side-panel help in the tool dialog.
+#[derive(Default)]
+pub struct GpToolMetadata {
+    pub summary: String,
+    pub usage: Vec<String>,
+    pub tags: Vec<String>,
+    pub parameter_explanations: HashMap<String, String>,
+    pub code_samples: Vec<GpCodeSample>
+}
+
+impl GpToolMetadata {
+
+    /// Returns the dialog explanation of the parameter having the specified name.
+    pub fn parameter_explanation(&self, name: &str) -> Option<&str> {
+        self.parameter_explanations.get(name).map(|explanation| explanation.as_str())
+    }
+}
+
+/// Represents a code sample showing how to use a geoprocessing tool.
+pub struct GpCodeSample {
+    pub title: String,
+    pub description: String,
+    pub code: String
+}
+
+
+
+/// Converts a label like "Copy features" into a Python class name like "CopyFeatures".
+pub fn as_class_name(label: &str) -> String {
+    let mut class_name = String::with_capacity(label.len());
+    for word in label.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first_char) = chars.next() {
+            class_name.push(first_char.to_ascii_uppercase());
+            class_name.extend(chars);
+        }
+    }
+    if class_name.starts_with(|c: char| c.is_ascii_digit()) {
+        class_name.insert_str(0, "Tool");
+    }
+
+    class_name
+}
+
+
+
 /// Offers the functionalities of a geoprocessing tool
 pub trait GpTool {
 
+    /// Returns the stable name of this tool.
+    /// The name is used as Python class name and for scripting calls like arcpy.alias.ToolName,
+    /// so it must not change when the label changes.
+    /// By default the name is derived from the label like "Copy features" into "CopyFeatures".
+    fn name(&self) -> String {
+        as_class_name(self.label())
+    }
+
     fn label(&self) -> &str;
 
     fn description(&self) -> &str;
 
     fn parameters(&self) -> Vec<GpParameter>;
 
-    fn execute(&self, py: Python, parameters: Vec<PyParameterValue>, messages: PyGpMessages) -> PyResult<()>;
-}
\ No newline at end of file
+    fn execute(&self, py: Python, parameters: PyParameterValues, messages: PyGpMessages) -> PyResult<()>;
+
+    /// Returns whether or not this tool is licensed to execute.
+    fn is_licensed(&self, _py: Python) -> PyResult<bool> {
+        Ok(true)
+    }
+
+    /// Modifies the values and properties of parameters before internal validation is performed.
+    /// This method is called whenever a parameter is changed in the tool dialog.
+    fn update_parameters(&self, _py: Python, _parameters: PyParameterValues) -> PyResult<()> {
+        Ok(())
+    }
+
+    /// Modifies the messages created by internal validation for each tool parameter.
+    fn update_messages(&self, _py: Python, _parameters: PyParameterValues) -> PyResult<()> {
+        Ok(())
+    }
+
+    /// Returns the documentation being shown as help in the tool dialog.
+    fn metadata(&self) -> GpToolMetadata {
+        GpToolMetadata::default()
+    }
+
+    /// Returns the category for grouping this tool in the Catalog pane.
+    fn category(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Boxed tools can be used wherever a geoprocessing tool is expected.
+/// So that, a toolbox can offer different tool types.
+impl<T: GpTool + ?Sized> GpTool for Box<T> {
+
+    fn name(&self) -> String {
+        (**self).name()
+    }
+
+    fn label(&self) -> &str {
+        (**self).label()
+    }
+
+    fn description(&self) -> &str {
+        (**self).description()
+    }
+
+    fn parameters(&self) -> Vec<GpParameter> {
+        (**self).parameters()
+    }
+
+    fn execute(&self, py: Python, parameters: PyParameterValues, messages: PyGpMessages) -> PyResult<()> {
+        (**self).execute(py, parameters, messages)
+    }
+
+    fn is_licensed(&self, py: Python) -> PyResult<bool> {
+        (**self).is_licensed(py)
+    }
+
+    fn update_parameters(&self, py: Python, parameters: PyParameterValues) -> PyResult<()> {
+        (**self).update_parameters(py, parameters)
+    }
+
+    fn update_messages(&self, py: Python, parameters: PyParameterValues) -> PyResult<()> {
+        (**self).update_messages(py, parameters)
+    }
+
+    fn metadata(&self) -> GpToolMetadata {
+        (**self).metadata()
+    }
+
+    fn category(&self) -> Option<&str> {
+        (**self).category()
+    }
+}
+
+
+
+/// Offers the execution of a geoprocessing tool being declared by using the GpTool derive macro.
+/// The tool instance contains the values extracted from the parameters.
+pub trait GpToolRun {
+
+    fn run(&self, py: Python, parameters: &PyParameterValues, messages: &PyGpMessages) -> PyResult<()>;
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[cfg(feature = "testing")]
+    fn create_parameter_values<'a>(py: &'a Python, gp_parameters: Vec<GpParameter>) -> PyParameterValues<'a> {
+        let py_parameters = crate::gp::create_arcpy_parameters(*py, gp_parameters).unwrap();
+
+        crate::gp::create_parameters_from_arcpy(py, py_parameters).unwrap()
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn get_parameters_by_name() {
+        use crate::gp::testing::MockArcpy;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        MockArcpy::install(py).unwrap();
+        let parameter_values = create_parameter_values(&py, vec![
+            GpParameterBuilder::new().with_name("in_features").build(),
+            GpParameterBuilder::new().with_name("distance").with_data_type(DataType::GPDouble).with_default_value("5").build()
+        ]);
+
+        assert_eq!(vec!["in_features", "distance"], parameter_values.names(), "Names are wrong!");
+        assert_eq!(Some(1), parameter_values.index_of("distance"), "Index is wrong!");
+        assert!(parameter_values.contains("in_features"), "Parameter must exist!");
+        assert_eq!("distance", parameter_values.get("distance").unwrap().name().unwrap(), "Parameter is wrong!");
+        assert!(parameter_values.is_set("distance").unwrap(), "Distance must be set!");
+        assert!(parameter_values.get_optional("in_features").unwrap().is_none(), "Input features must not be set!");
+
+        assert!(!parameter_values.contains("out_features"), "Parameter must not exist!");
+        assert_eq!(None, parameter_values.index_of("out_features"), "Index is wrong!");
+        assert!(parameter_values.get("out_features").is_err(), "Missing parameter must not be accessible!");
+        assert!(parameter_values.is_set("out_features").is_err(), "Missing parameter must not be set!");
+        assert!(parameter_values.set_value("out_features", "memory/out").is_err(), "Missing parameter must not be changed!");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn extract_narrow_numbers() {
+        use crate::gp::testing::MockArcpy;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        MockArcpy::install(py).unwrap();
+        let parameter_values = create_parameter_values(&py, vec![
+            GpParameterBuilder::new().with_name("ratio").with_data_type(DataType::GPDouble).build(),
+            GpParameterBuilder::new().with_name("count").with_data_type(DataType::GPLong).build()
+        ]);
+        parameter_values.set_value("ratio", 0.25).unwrap();
+        parameter_values.set_value("count", 12).unwrap();
+
+        assert_eq!(0.25, f32::from_parameter_value(parameter_values.get("ratio").unwrap()).unwrap(), "Ratio is wrong!");
+        assert_eq!(12, i16::from_parameter_value(parameter_values.get("count").unwrap()).unwrap(), "Count is wrong!");
+    }
+
+    #[test]
+    fn parse_data_types() {
+        assert_eq!("GPDouble", DataType::from_str("Double").unwrap().as_str(), "Data type is wrong!");
+        assert_eq!("GPBoolean", DataType::from_str("GPBoolean").unwrap().as_str(), "Data type is wrong!");
+        assert_eq!("GPFeatureLayer", DataType::from_str("Feature Layer").unwrap().as_str(), "Data type is wrong!");
+        assert_eq!("DEFeatureClass", DataType::from_str("Feature Class").unwrap().as_str(), "Data type is wrong!");
+        assert_eq!("GPFeatureRecordSetLayer", DataType::from_str("Feature Set").unwrap().as_str(), "Data type is wrong!");
+        assert!(DataType::from_str("GPRasterLayer").is_err(), "Unknown data type must not be parsed!");
+    }
+
+    #[test]
+    fn parse_field_and_shape_types() {
+        assert_eq!(Ok(FieldType::GlobalID), FieldType::from_str("GlobalID"), "Field type is wrong!");
+        assert_eq!(Ok(FieldType::Raster), FieldType::from_str("Raster"), "Field type is wrong!");
+        assert_eq!(Ok(ShapeType::MultiPatch), ShapeType::from_str("MultiPatch"), "Shape type is wrong!");
+        assert_eq!("MULTIPATCH", ShapeType::MultiPatch.as_str(), "Shape type is wrong!");
+        assert!(ShapeType::from_str("Annotation").is_err(), "Unknown shape type must not be parsed!");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn set_output_values() {
+        use crate::gp::testing::MockArcpy;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        arcpy.create_table("memory/api_outputs", &[]).unwrap();
+        let parameter_values = create_parameter_values(&py, vec![
+            GpParameterBuilder::new().with_name("out_text").with_data_type(DataType::GPString).build(),
+            GpParameterBuilder::new().with_name("out_count").with_data_type(DataType::GPLong).build(),
+            GpParameterBuilder::new().with_name("out_ratio").with_data_type(DataType::GPDouble).build(),
+            GpParameterBuilder::new().with_name("out_table").with_data_type(DataType::DEFeatureClass).build(),
+            GpParameterBuilder::new().with_name("out_features").with_data_type(DataType::GPFeatureRecordSetLayer).build()
+        ]);
+
+        parameter_values.get("out_text").unwrap().set_strvalue("Dessau").unwrap();
+        parameter_values.get("out_count").unwrap().set_intvalue(42).unwrap();
+        parameter_values.get("out_ratio").unwrap().set_doublevalue(0.5).unwrap();
+        parameter_values.get("out_table").unwrap().set_catalog_path("memory/api_outputs").unwrap();
+        parameter_values.get("out_features").unwrap().set_feature_set("memory/api_outputs").unwrap();
+        assert_eq!("Dessau", parameter_values.get("out_text").unwrap().value_as_text().unwrap(), "Text is wrong!");
+        assert_eq!(42, i64::from_parameter_value(parameter_values.get("out_count").unwrap()).unwrap(), "Count is wrong!");
+        assert_eq!(0.5, f64::from_parameter_value(parameter_values.get("out_ratio").unwrap()).unwrap(), "Ratio is wrong!");
+        assert_eq!("memory/api_outputs", parameter_values.get("out_table").unwrap().value_as_text().unwrap(), "Catalog path is wrong!");
+        assert_eq!("memory/api_outputs", parameter_values.get("out_features").unwrap().catalog_path().unwrap(), "Feature set is wrong!");
+
+        parameter_values.set_value("out_count", 7).unwrap();
+        assert_eq!(7, i64::from_parameter_value(parameter_values.get("out_count").unwrap()).unwrap(), "Count is wrong!");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn batch_insert_in_edit_session() {
+        use crate::gp::testing::MockArcpy;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let fields = vec![GpField {
+            name: String::from("Name"),
+            field_type: FieldType::String
+        }];
+        arcpy.create_feature_class("memory/batch_cities", ShapeType::Point, 4326, &fields).unwrap();
+
+        let field_names = vec![String::from("SHAPE@XY"), String::from("Name")];
+        let rows = vec![((12.24555, 51.83864), "Dessau"), ((13.40495, 52.52001), "Berlin")];
+        let oids = PyBatchInsert::new("memory/batch_cities", field_names.clone())
+            .with_edit_session("memory")
+            .insert(py, rows)
+            .unwrap();
+        assert_eq!(vec![1, 2], oids, "Object IDs are wrong!");
+
+        let invalid_rows = vec![((11.57549, 48.13743), "Munich").to_object(py), ("Hamburg", ).to_object(py)];
+        let result = PyBatchInsert::new("memory/batch_cities", field_names)
+            .with_edit_session("memory")
+            .insert(py, invalid_rows);
+        assert!(result.is_err(), "Invalid rows must not be inserted!");
+        assert_eq!(2, arcpy.row_count("memory/batch_cities").unwrap(), "Row count is wrong!");
+    }
+
+    #[cfg(all(feature = "chrono", feature = "testing"))]
+    #[test]
+    fn read_and_write_chrono_values() {
+        use crate::gp::testing::MockArcpy;
+        use chrono::TimeZone;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let field_types = [FieldType::Date, FieldType::DateOnly, FieldType::TimeOnly, FieldType::TimestampOffset];
+        let fields: Vec<GpField> = field_types.iter()
+            .map(|field_type| GpField { name: field_type.as_str().to_string(), field_type: *field_type })
+            .collect();
+        arcpy.create_table("memory/api_dates", &fields).unwrap();
+
+        let observed_at = NaiveDate::from_ymd_opt(2021, 6, 1).unwrap().and_hms_opt(12, 30, 0).unwrap();
+        let reported_at = FixedOffset::east_opt(7200).unwrap().from_local_datetime(&observed_at).unwrap();
+        let field_names: Vec<String> = fields.iter().map(|field| field.name.to_string()).collect();
+        let insert_cursor = PyInsertCursor::new(&py, "memory/api_dates", field_names.clone()).unwrap();
+        let mut insert_buffer = InsertBuffer::new(4);
+        insert_buffer.add_datetimevalue(py, &observed_at).unwrap();
+        insert_buffer.add_datevalue(py, &observed_at.date()).unwrap();
+        insert_buffer.add_timevalue(py, &observed_at.time()).unwrap();
+        insert_buffer.add_datetimeoffsetvalue(py, &reported_at).unwrap();
+        insert_cursor.insert_buffer(&mut insert_buffer).unwrap();
+        insert_cursor.close().unwrap();
+
+        let search_cursor = PySearchCursor::new(&py, "memory/api_dates", field_names, "").unwrap();
+        let row = search_cursor.next().unwrap();
+        assert_eq!(observed_at, row.as_datetimevalue(0).unwrap(), "Date is wrong!");
+        assert_eq!(observed_at.date(), row.as_datevalue(1).unwrap(), "DateOnly is wrong!");
+        assert_eq!(observed_at.time(), row.as_timevalue(2).unwrap(), "TimeOnly is wrong!");
+        assert_eq!(reported_at, row.as_datetimeoffsetvalue(3).unwrap(), "TimestampOffset is wrong!");
+
+        let parameter_values = create_parameter_values(&py, vec![
+            GpParameterBuilder::new().with_name("since").with_data_type(DataType::GPString).build()
+        ]);
+        parameter_values.get("since").unwrap().set_datetimevalue(&observed_at).unwrap();
+        assert_eq!(observed_at, parameter_values.get("since").unwrap().as_datetimevalue().unwrap(), "Parameter date is wrong!");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn read_and_write_null_values() {
+        use crate::gp::testing::MockArcpy;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let fields = vec![GpField {
+            name: String::from("Name"),
+            field_type: FieldType::String
+        }, GpField {
+            name: String::from("Population"),
+            field_type: FieldType::Integer
+        }];
+        arcpy.create_table("memory/testing_nulls", &fields).unwrap();
+
+        let field_names = vec![String::from("Name"), String::from("Population")];
+        let insert_cursor = PyInsertCursor::new(&py, "memory/testing_nulls", field_names.clone()).unwrap();
+        let mut insert_buffer = InsertBuffer::new(2);
+        insert_buffer.add_value(py, "Dessau");
+        insert_buffer.add_optional_value::<i32>(py, None);
+        insert_cursor.insert_buffer(&mut insert_buffer).unwrap();
+        insert_cursor.close().unwrap();
+
+        let search_cursor = PySearchCursor::new(&py, "memory/testing_nulls", field_names, "").unwrap();
+        let row = search_cursor.next().unwrap();
+        assert!(!row.is_null(0).unwrap(), "Name must not be null!");
+        assert!(row.is_null(1).unwrap(), "Population must be null!");
+        assert_eq!(None, row.as_optional_intvalue(1).unwrap(), "Population is wrong!");
+        assert_eq!(Some(String::from("Dessau")), row.value_by_name::<String>("name").unwrap(), "Name is wrong!");
+        assert_eq!(None, row.value_by_name::<i32>("Population").unwrap(), "Population is wrong!");
+        assert!(row.value_by_name::<i32>("Area").is_err(), "Unknown field must not be accessible!");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn read_values_using_tokens() {
+        use crate::gp::testing::{MockArcpy, TestTable, TestValue};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let parcels = TestTable::features(ShapeType::Polygon, 3857)
+            .with_row(vec![TestValue::Geometry(String::from("{\"rings\": [[[0, 0], [0, 20], [10, 20], [10, 0], [0, 0]]]}"))]);
+        arcpy.create_test_table("memory/api_parcels", &parcels).unwrap();
+
+        let field_names: Vec<String> = vec![FieldToken::OID.into(), FieldToken::ShapeXY.into(), FieldToken::ShapeWKB.into(),
+            FieldToken::ShapeArea.into(), FieldToken::ShapeLength.into()];
+        let search_cursor = PySearchCursor::new(&py, "memory/api_parcels", field_names, "").unwrap();
+        let row = search_cursor.next().unwrap();
+        assert_eq!(1, row.as_oidvalue(row.token_index(FieldToken::OID).unwrap()).unwrap(), "Object ID is wrong!");
+        let centroid = row.as_point(row.token_index(FieldToken::ShapeXY).unwrap()).unwrap();
+        assert_eq!((5.0, 10.0), (centroid.x, centroid.y), "Centroid is wrong!");
+        let wkb = row.as_wkbvalue(row.token_index(FieldToken::ShapeWKB).unwrap()).unwrap();
+        assert_eq!(&[1, 6, 0, 0, 0], &wkb[..5], "WKB header is wrong!");
+        assert_eq!(200.0, row.as_doublevalue(row.token_index(FieldToken::ShapeArea).unwrap()).unwrap(), "Area is wrong!");
+        assert_eq!(60.0, row.as_doublevalue(row.token_index(FieldToken::ShapeLength).unwrap()).unwrap(), "Length is wrong!");
+        assert_eq!(Ok(FieldToken::ShapeXY), "shape@xy".parse(), "Token is wrong!");
+    }
+}
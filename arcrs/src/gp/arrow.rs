@@ -0,0 +1,495 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Converts tables and feature classes into Arrow record batches and back.
+//! The shapes are stored as WKB in a column having the GeoArrow extension type "geoarrow.wkb".
+//! So that, the record batches can be passed to DataFusion, Polars or other Rust services.
+
+use super::api::{FieldToken, FieldType, GpField, PyInsertCursor, PyParameterValue, PySearchCursor};
+use crate::data::arcpy::ArcpyDataSource;
+use crate::data::{DataSource, TableSchema};
+use arrow_array::builder::{BinaryBuilder, Date32Builder, Float64Builder, Int32Builder, Int64Builder, StringBuilder, Time64MicrosecondBuilder, TimestampMillisecondBuilder};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Date32Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Time64MicrosecondType, TimestampMillisecondType};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use pyo3::exceptions::{PyStopIteration, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The name of the column holding the shapes.
+pub const GEOMETRY_COLUMN: &str = "geometry";
+
+/// The extension type of WKB encoded geometries defined by GeoArrow.
+pub const GEOARROW_WKB: &str = "geoarrow.wkb";
+
+const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+const EXTENSION_METADATA_KEY: &str = "ARROW:extension:metadata";
+
+/// The time zone of TimestampOffset columns, the offsets of the values are normalized to UTC.
+pub const UTC: &str = "UTC";
+
+/// The number of rows being converted into Python objects before they are inserted.
+const WRITE_BATCH_SIZE: usize = 1000;
+
+
+
+/// Returns the Arrow schema of a table or feature class.
+/// The geometry column comes first followed by all fields.
+/// The geometry column is named like "geometry_1" if a field is already named "geometry".
+pub fn arrow_schema(schema: &TableSchema) -> Schema {
+    let mut fields = Vec::with_capacity(schema.fields.len() + 1);
+    if schema.shape_type.is_some() {
+        fields.push(geometry_field(schema.wkid).with_name(geometry_column_name(schema)));
+    }
+    fields.extend(schema.fields.iter().filter_map(arrow_field));
+
+    Schema::new(fields)
+}
+
+/// Returns the Arrow field of a geoprocessing field.
+/// Object ID, geometry, blob and raster fields have no Arrow counterpart.
+pub fn arrow_field(gp_field: &GpField) -> Option<Field> {
+    let data_type = match gp_field.field_type {
+        FieldType::OID | FieldType::Geometry | FieldType::Blob | FieldType::Raster => return None,
+        FieldType::Date => DataType::Timestamp(TimeUnit::Millisecond, None),
+        FieldType::DateOnly => DataType::Date32,
+        FieldType::TimeOnly => DataType::Time64(TimeUnit::Microsecond),
+        FieldType::TimestampOffset => DataType::Timestamp(TimeUnit::Millisecond, Some(UTC.into())),
+        FieldType::Double => DataType::Float64,
+        FieldType::Integer => DataType::Int32,
+        FieldType::BigInteger => DataType::Int64,
+        FieldType::String | FieldType::Guid | FieldType::GlobalID => DataType::Utf8
+    };
+
+    Some(Field::new(&gp_field.name, data_type, true))
+}
+
+/// Returns the name of the geometry column not being used by any field.
+fn geometry_column_name(schema: &TableSchema) -> String {
+    let is_field_name = |column_name: &str| schema.fields.iter().any(|field| field.name.eq_ignore_ascii_case(column_name));
+    let mut column_name = GEOMETRY_COLUMN.to_string();
+    let mut suffix = 0;
+    while is_field_name(&column_name) {
+        suffix += 1;
+        column_name = format!("{}_{}", GEOMETRY_COLUMN, suffix);
+    }
+
+    column_name
+}
+
+/// Returns the geometry field using the GeoArrow metadata.
+/// The spatial reference is declared as EPSG authority code unless the well-known ID is zero.
+pub fn geometry_field(wkid: i32) -> Field {
+    let extension_metadata = if 0 < wkid {
+        format!("{{\"crs\":\"EPSG:{}\",\"crs_type\":\"authority_code\"}}", wkid)
+    } else {
+        String::from("{}")
+    };
+    let metadata: HashMap<String, String> = vec![
+        (EXTENSION_NAME_KEY.to_string(), GEOARROW_WKB.to_string()),
+        (EXTENSION_METADATA_KEY.to_string(), extension_metadata)
+    ].into_iter().collect();
+
+    Field::new(GEOMETRY_COLUMN, DataType::Binary, true).with_metadata(metadata)
+}
+
+/// Checks whether or not the field holds WKB encoded geometries.
+pub fn is_geometry_field(field: &Field) -> bool {
+    field.metadata().get(EXTENSION_NAME_KEY).map(|extension_name| extension_name == GEOARROW_WKB).unwrap_or_default()
+}
+
+
+
+/// Reads all rows of a table or feature class matching the where clause into one record batch.
+pub fn read_record_batch(py: Python, catalog_path: &str, where_clause: &str) -> PyResult<RecordBatch> {
+    let data_source = ArcpyDataSource::new(py);
+    let schema = data_source.describe(catalog_path)?;
+    let arrow_schema = arrow_schema(&schema);
+    let field_names: Vec<String> = arrow_schema.fields().iter()
+        .map(|field| if is_geometry_field(field) { FieldToken::ShapeWKB.into() } else { field.name().to_string() })
+        .collect();
+
+    let date_converter = DateConverter::new(py)?;
+    let mut column_builders: Vec<ColumnBuilder> = arrow_schema.fields().iter().map(|field| ColumnBuilder::new(field.data_type())).collect();
+    let search_cursor = PySearchCursor::new(&py, catalog_path, field_names, where_clause)?;
+    loop {
+        let pyrow = match search_cursor.next_row() {
+            Ok(pyrow) => pyrow,
+            Err(err) if err.is_instance::<PyStopIteration>(py) => break,
+            Err(err) => return Err(err)
+        };
+        for (index, column_builder) in column_builders.iter_mut().enumerate() {
+            column_builder.append(pyrow.get_item(index)?, &date_converter)?;
+        }
+    }
+
+    let columns: Vec<ArrayRef> = column_builders.into_iter().map(ColumnBuilder::finish).collect();
+    RecordBatch::try_new(Arc::new(arrow_schema), columns)
+        .map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+/// Reads all rows of the dataset being the value of the parameter.
+pub fn read_parameter(py: Python, parameter: &PyParameterValue, where_clause: &str) -> PyResult<RecordBatch> {
+    read_record_batch(py, &parameter.catalog_path()?, where_clause)
+}
+
+/// Inserts all rows of the record batch into an existing table or feature class and returns their object IDs.
+/// The columns are matched by name, the geometry column must use the GeoArrow WKB extension type.
+/// The rows are converted and inserted in batches, so that only one batch of Python objects is held at once.
+pub fn write_record_batch(py: Python, catalog_path: &str, record_batch: &RecordBatch) -> PyResult<Vec<i64>> {
+    let arrow_schema = record_batch.schema();
+    let field_names: Vec<String> = arrow_schema.fields().iter()
+        .map(|field| if is_geometry_field(field) { FieldToken::ShapeWKB.into() } else { field.name().to_string() })
+        .collect();
+    for field in arrow_schema.fields() {
+        if !is_supported(field.data_type()) {
+            return Err(PyValueError::new_err(format!("Data type {} of column '{}' is not supported!", field.data_type(), field.name())));
+        }
+    }
+
+    let date_converter = DateConverter::new(py)?;
+    let insert_cursor = PyInsertCursor::new(&py, catalog_path, field_names)?;
+    let oids = insert_record_batch(py, &insert_cursor, record_batch, &date_converter);
+    insert_cursor.close()?;
+
+    oids
+}
+
+fn insert_record_batch(py: Python, insert_cursor: &PyInsertCursor, record_batch: &RecordBatch, date_converter: &DateConverter) -> PyResult<Vec<i64>> {
+    let mut oids = Vec::with_capacity(record_batch.num_rows());
+    let mut rows = Vec::with_capacity(WRITE_BATCH_SIZE.min(record_batch.num_rows()));
+    for batch_start in (0..record_batch.num_rows()).step_by(WRITE_BATCH_SIZE) {
+        let batch_end = (batch_start + WRITE_BATCH_SIZE).min(record_batch.num_rows());
+        for row_index in batch_start..batch_end {
+            let row = record_batch.columns().iter()
+                .map(|column| column_value(py, column, row_index, date_converter))
+                .collect::<PyResult<Vec<PyObject>>>()?;
+            rows.push(row);
+        }
+        oids.append(&mut insert_cursor.insert_rows(rows.drain(..))?);
+    }
+
+    Ok(oids)
+}
+
+fn is_supported(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Int16 | DataType::Int32 | DataType::Int64 | DataType::Float32 | DataType::Float64
+        | DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary | DataType::LargeBinary | DataType::Boolean
+        | DataType::Timestamp(TimeUnit::Millisecond, _) | DataType::Date32 | DataType::Time64(TimeUnit::Microsecond))
+}
+
+fn column_value(py: Python, column: &ArrayRef, index: usize, date_converter: &DateConverter) -> PyResult<PyObject> {
+    if column.is_null(index) {
+        return Ok(py.None());
+    }
+
+    let value = match column.data_type() {
+        DataType::Int16 => column.as_primitive::<Int16Type>().value(index).to_object(py),
+        DataType::Int32 => column.as_primitive::<Int32Type>().value(index).to_object(py),
+        DataType::Int64 => column.as_primitive::<Int64Type>().value(index).to_object(py),
+        DataType::Float32 => column.as_primitive::<Float32Type>().value(index).to_object(py),
+        DataType::Float64 => column.as_primitive::<Float64Type>().value(index).to_object(py),
+        DataType::Utf8 => column.as_string::<i32>().value(index).to_object(py),
+        DataType::LargeUtf8 => column.as_string::<i64>().value(index).to_object(py),
+        DataType::Binary => PyBytes::new(py, column.as_binary::<i32>().value(index)).to_object(py),
+        DataType::LargeBinary => PyBytes::new(py, column.as_binary::<i64>().value(index)).to_object(py),
+        DataType::Boolean => column.as_boolean().value(index).to_object(py),
+        DataType::Timestamp(TimeUnit::Millisecond, None) => date_converter.to_datetime(py, column.as_primitive::<TimestampMillisecondType>().value(index))?,
+        DataType::Timestamp(TimeUnit::Millisecond, Some(_)) => date_converter.to_utc_datetime(py, column.as_primitive::<TimestampMillisecondType>().value(index))?,
+        DataType::Date32 => date_converter.to_date(py, column.as_primitive::<Date32Type>().value(index))?,
+        DataType::Time64(TimeUnit::Microsecond) => date_converter.to_time(py, column.as_primitive::<Time64MicrosecondType>().value(index))?,
+        data_type => return Err(PyValueError::new_err(format!("Data type {} is not supported!", data_type)))
+    };
+
+    Ok(value)
+}
+
+
+
+/// Represents the conversion between Python dates and times and the Arrow temporal types.
+/// arcpy returns Date values without a time zone, so that these dates are converted as they are.
+/// TimestampOffset values are normalized to UTC, DateOnly values are days and TimeOnly values are microseconds.
+struct DateConverter<'py> {
+    datetime: &'py PyModule,
+    epoch: &'py PyAny,
+    utc_epoch: &'py PyAny,
+    epoch_date: &'py PyAny,
+    millisecond: &'py PyAny
+}
+
+impl<'py> DateConverter<'py> {
+
+    fn new(py: Python<'py>) -> PyResult<DateConverter<'py>> {
+        let datetime = py.import("datetime")?;
+        let epoch = datetime.call1("datetime", (1970, 1, 1))?;
+        let utc_epoch = datetime.call1("datetime", (1970, 1, 1, 0, 0, 0, 0, datetime.getattr("timezone")?.getattr("utc")?))?;
+        let epoch_date = datetime.call1("date", (1970, 1, 1))?;
+        let millisecond = timedelta(py, datetime, "milliseconds", 1)?;
+
+        Ok(DateConverter {
+            datetime,
+            epoch,
+            utc_epoch,
+            epoch_date,
+            millisecond
+        })
+    }
+
+    fn to_milliseconds(&self, pydatetime: &PyAny) -> PyResult<i64> {
+        pydatetime.call_method1("__sub__", (self.epoch,))?
+            .call_method1("__floordiv__", (self.millisecond,))?
+            .extract()
+    }
+
+    fn to_utc_milliseconds(&self, pydatetime: &PyAny) -> PyResult<i64> {
+        if pydatetime.call_method0("utcoffset")?.is_none() {
+            return Err(PyValueError::new_err("The datetime must have a time zone!"));
+        }
+
+        pydatetime.call_method1("__sub__", (self.utc_epoch,))?
+            .call_method1("__floordiv__", (self.millisecond,))?
+            .extract()
+    }
+
+    fn to_days(&self, pydate: &PyAny) -> PyResult<i32> {
+        pydate.call_method1("__sub__", (self.epoch_date,))?
+            .getattr("days")?
+            .extract()
+    }
+
+    fn to_microseconds(&self, pytime: &PyAny) -> PyResult<i64> {
+        let hour: i64 = pytime.getattr("hour")?.extract()?;
+        let minute: i64 = pytime.getattr("minute")?.extract()?;
+        let second: i64 = pytime.getattr("second")?.extract()?;
+        let microsecond: i64 = pytime.getattr("microsecond")?.extract()?;
+
+        Ok(((hour * 60 + minute) * 60 + second) * 1_000_000 + microsecond)
+    }
+
+    fn to_datetime(&self, py: Python, milliseconds: i64) -> PyResult<PyObject> {
+        let pytimedelta = timedelta(py, self.datetime, "milliseconds", milliseconds)?;
+
+        Ok(self.epoch.call_method1("__add__", (pytimedelta,))?.to_object(py))
+    }
+
+    fn to_utc_datetime(&self, py: Python, milliseconds: i64) -> PyResult<PyObject> {
+        let pytimedelta = timedelta(py, self.datetime, "milliseconds", milliseconds)?;
+
+        Ok(self.utc_epoch.call_method1("__add__", (pytimedelta,))?.to_object(py))
+    }
+
+    fn to_date(&self, py: Python, days: i32) -> PyResult<PyObject> {
+        let pytimedelta = timedelta(py, self.datetime, "days", days.into())?;
+
+        Ok(self.epoch_date.call_method1("__add__", (pytimedelta,))?.to_object(py))
+    }
+
+    fn to_time(&self, py: Python, microseconds: i64) -> PyResult<PyObject> {
+        let seconds = microseconds.div_euclid(1_000_000);
+        let time = (seconds / 3600, seconds / 60 % 60, seconds % 60, microseconds.rem_euclid(1_000_000));
+
+        Ok(self.datetime.call1("time", time)?.to_object(py))
+    }
+}
+
+fn timedelta<'py>(py: Python<'py>, datetime: &'py PyModule, unit: &str, value: i64) -> PyResult<&'py PyAny> {
+    let kwargs = PyDict::new(py);
+    kwargs.set_item(unit, value)?;
+
+    datetime.call("timedelta", (), Some(kwargs))
+}
+
+
+
+/// Represents a column being built from the values of a search cursor.
+enum ColumnBuilder {
+    Geometry(BinaryBuilder),
+    Date(TimestampMillisecondBuilder),
+    DateOnly(Date32Builder),
+    TimeOnly(Time64MicrosecondBuilder),
+    TimestampOffset(TimestampMillisecondBuilder),
+    Double(Float64Builder),
+    Integer(Int32Builder),
+    BigInteger(Int64Builder),
+    Text(StringBuilder)
+}
+
+impl ColumnBuilder {
+
+    fn new(data_type: &DataType) -> ColumnBuilder {
+        match data_type {
+            DataType::Binary => ColumnBuilder::Geometry(BinaryBuilder::new()),
+            DataType::Timestamp(_, None) => ColumnBuilder::Date(TimestampMillisecondBuilder::new()),
+            DataType::Timestamp(_, Some(time_zone)) => ColumnBuilder::TimestampOffset(TimestampMillisecondBuilder::new().with_timezone(time_zone.clone())),
+            DataType::Date32 => ColumnBuilder::DateOnly(Date32Builder::new()),
+            DataType::Time64(_) => ColumnBuilder::TimeOnly(Time64MicrosecondBuilder::new()),
+            DataType::Float64 => ColumnBuilder::Double(Float64Builder::new()),
+            DataType::Int32 => ColumnBuilder::Integer(Int32Builder::new()),
+            DataType::Int64 => ColumnBuilder::BigInteger(Int64Builder::new()),
+            _ => ColumnBuilder::Text(StringBuilder::new())
+        }
+    }
+
+    fn append(&mut self, pyvalue: &PyAny, date_converter: &DateConverter) -> PyResult<()> {
+        if pyvalue.is_none() {
+            match self {
+                ColumnBuilder::Geometry(builder) => builder.append_null(),
+                ColumnBuilder::Date(builder) => builder.append_null(),
+                ColumnBuilder::DateOnly(builder) => builder.append_null(),
+                ColumnBuilder::TimeOnly(builder) => builder.append_null(),
+                ColumnBuilder::TimestampOffset(builder) => builder.append_null(),
+                ColumnBuilder::Double(builder) => builder.append_null(),
+                ColumnBuilder::Integer(builder) => builder.append_null(),
+                ColumnBuilder::BigInteger(builder) => builder.append_null(),
+                ColumnBuilder::Text(builder) => builder.append_null()
+            }
+
+            return Ok(());
+        }
+
+        match self {
+            ColumnBuilder::Geometry(builder) => builder.append_value(pyvalue.extract::<Vec<u8>>()?),
+            ColumnBuilder::Date(builder) => builder.append_value(date_converter.to_milliseconds(pyvalue)?),
+            ColumnBuilder::DateOnly(builder) => builder.append_value(date_converter.to_days(pyvalue)?),
+            ColumnBuilder::TimeOnly(builder) => builder.append_value(date_converter.to_microseconds(pyvalue)?),
+            ColumnBuilder::TimestampOffset(builder) => builder.append_value(date_converter.to_utc_milliseconds(pyvalue)?),
+            ColumnBuilder::Double(builder) => builder.append_value(pyvalue.extract()?),
+            ColumnBuilder::Integer(builder) => builder.append_value(pyvalue.extract()?),
+            ColumnBuilder::BigInteger(builder) => builder.append_value(pyvalue.extract()?),
+            ColumnBuilder::Text(builder) => builder.append_value(pyvalue.str()?.to_str()?)
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> ArrayRef {
+        match self {
+            ColumnBuilder::Geometry(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::Date(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::DateOnly(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::TimeOnly(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::TimestampOffset(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::Double(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::Integer(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::BigInteger(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::Text(mut builder) => Arc::new(builder.finish())
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::gp::api::ShapeType;
+
+    #[test]
+    fn derive_schema_from_fields() {
+        let schema = TableSchema::features(ShapeType::Point, 4326)
+            .with_field("Name", FieldType::String)
+            .with_field("Population", FieldType::Integer)
+            .with_field("Founded", FieldType::Date);
+        let arrow_schema = arrow_schema(&schema);
+        let field_names: Vec<&str> = arrow_schema.fields().iter().map(|field| field.name().as_str()).collect();
+        assert_eq!(vec![GEOMETRY_COLUMN, "Name", "Population", "Founded"], field_names, "Field names are wrong!");
+        assert!(is_geometry_field(arrow_schema.field(0)), "Geometry field is wrong!");
+        assert_eq!(Some(&String::from("{\"crs\":\"EPSG:4326\",\"crs_type\":\"authority_code\"}")),
+            arrow_schema.field(0).metadata().get(EXTENSION_METADATA_KEY), "GeoArrow metadata is wrong!");
+        assert_eq!(&DataType::Int32, arrow_schema.field(2).data_type(), "Data type is wrong!");
+
+        let schema = TableSchema::features(ShapeType::Point, 4326)
+            .with_field("Geometry", FieldType::String)
+            .with_field("geometry_1", FieldType::String);
+        let renamed_schema = super::arrow_schema(&schema);
+        assert_eq!("geometry_2", renamed_schema.field(0).name(), "Geometry column name is wrong!");
+        assert!(is_geometry_field(renamed_schema.field(0)), "Geometry field is wrong!");
+        assert!(!is_geometry_field(renamed_schema.field(1)), "User field must not be a geometry field!");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn read_and_write_record_batch() {
+        use crate::gp::testing::{MockArcpy, TestTable, TestValue};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let cities = TestTable::features(ShapeType::Point, 4326)
+            .with_field("Name", FieldType::String)
+            .with_field("Population", FieldType::Integer)
+            .with_row(vec![TestValue::Point(12.24555, 51.83864), "Dessau".into(), 74_000.into()])
+            .with_row(vec![TestValue::Point(13.40495, 52.52001), "Berlin".into(), TestValue::Null]);
+        arcpy.create_test_table("memory/arrow_cities", &cities).unwrap();
+
+        let record_batch = read_record_batch(py, "memory/arrow_cities", "").unwrap();
+        assert_eq!(2, record_batch.num_rows(), "Row count is wrong!");
+        assert_eq!(3, record_batch.num_columns(), "Column count is wrong!");
+        assert_eq!("Berlin", record_batch.column(1).as_string::<i32>().value(1), "Name is wrong!");
+        assert!(record_batch.column(2).is_null(1), "Population must be null!");
+
+        arcpy.create_test_table("memory/arrow_copies", &TestTable::features(ShapeType::Point, 4326)
+            .with_field("Name", FieldType::String)
+            .with_field("Population", FieldType::Integer)).unwrap();
+        let oids = write_record_batch(py, "memory/arrow_copies", &record_batch).unwrap();
+        assert_eq!(vec![1, 2], oids, "Object IDs are wrong!");
+        let copies = arcpy.read_test_table("memory/arrow_copies").unwrap();
+        assert_eq!(cities, copies, "Copied features are wrong!");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn read_and_write_dates_and_times() {
+        use crate::gp::testing::MockArcpy;
+        use pyo3::types::{IntoPyDict, PyTuple};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let fields = vec![
+            GpField { name: String::from("Observed"), field_type: FieldType::Date },
+            GpField { name: String::from("Day"), field_type: FieldType::DateOnly },
+            GpField { name: String::from("Time"), field_type: FieldType::TimeOnly },
+            GpField { name: String::from("Reported"), field_type: FieldType::TimestampOffset }
+        ];
+        arcpy.create_table("memory/arrow_observations", &fields).unwrap();
+        arcpy.create_table("memory/arrow_observation_copies", &fields).unwrap();
+        let pyrow = py.eval("(datetime.datetime(2021, 6, 1, 12, 30), datetime.date(2021, 6, 1), datetime.time(12, 30, 15, 250), \
+            datetime.datetime(2021, 6, 1, 14, 30, tzinfo=datetime.timezone(datetime.timedelta(hours=2))))", None, Some([("datetime", py.import("datetime").unwrap())].into_py_dict(py))).unwrap();
+        arcpy.insert_row("memory/arrow_observations", &["Observed", "Day", "Time", "Reported"], pyrow).unwrap();
+
+        let record_batch = read_record_batch(py, "memory/arrow_observations", "").unwrap();
+        let schema = record_batch.schema();
+        assert_eq!(&DataType::Date32, schema.field(1).data_type(), "Data type is wrong!");
+        assert_eq!(&DataType::Time64(TimeUnit::Microsecond), schema.field(2).data_type(), "Data type is wrong!");
+        assert_eq!(&DataType::Timestamp(TimeUnit::Millisecond, Some(UTC.into())), record_batch.column(3).data_type(), "Data type is wrong!");
+        assert_eq!(18779, record_batch.column(1).as_primitive::<Date32Type>().value(0), "Day is wrong!");
+        assert_eq!(45_015_000_250, record_batch.column(2).as_primitive::<Time64MicrosecondType>().value(0), "Time is wrong!");
+        assert_eq!(1_622_550_600_000, record_batch.column(3).as_primitive::<TimestampMillisecondType>().value(0), "Timestamp is wrong!");
+        assert_eq!(record_batch.column(0).as_primitive::<TimestampMillisecondType>().value(0), record_batch.column(3).as_primitive::<TimestampMillisecondType>().value(0),
+            "Timestamp must be normalized to UTC!");
+
+        write_record_batch(py, "memory/arrow_observation_copies", &record_batch).unwrap();
+        let rows = arcpy.rows("memory/arrow_observation_copies", &["Observed", "Day", "Time", "Reported"]).unwrap();
+        let pycopy = PyTuple::new(py, &rows[0]);
+        assert_eq!(std::cmp::Ordering::Equal, pyrow.compare(pycopy).unwrap(), "Copied dates and times are wrong!");
+    }
+}
@@ -0,0 +1,345 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Offers columnar access to tables and feature classes using NumPy structured arrays.
+//! Reading a whole column is a single Python call, the values are copied using the buffer protocol.
+//! NumPy must be available in the Python environment of ArcGIS Pro, the module needs the cargo feature `columnar`.
+
+use pyo3::buffer::{Element, PyBuffer};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+use pyo3::PyNativeType;
+
+
+
+/// Represents a NumPy structured array being created by arcpy.da.
+/// # Examples
+///
+/// ```ignore
+/// let pyarray = columnar::PyStructuredArray::from_feature_class(py, &in_features, &["SHAPE@XY", "Population"], "", false)?;
+/// let locations = pyarray.xy_values("SHAPE@XY")?;
+/// let populations = pyarray.f64_values("Population")?;
+/// ```
+pub struct PyStructuredArray<'py> {
+    pyarray: &'py PyAny
+}
+
+impl<'py> PyStructuredArray<'py> {
+
+    /// Reads the specified fields of a feature class using arcpy.da.FeatureClassToNumPyArray.
+    /// Tokens like "SHAPE@XY", "SHAPE@X", "SHAPE@Y" and "OID@" are supported.
+    /// Rows having null values are skipped if skip_nulls is set, otherwise reading them fails.
+    pub fn from_feature_class(py: Python<'py>, in_table: &str, field_names: &[&str], where_clause: &str, skip_nulls: bool) -> PyResult<PyStructuredArray<'py>> {
+        Self::read(py, "FeatureClassToNumPyArray", in_table, field_names, where_clause, skip_nulls)
+    }
+
+    /// Reads the specified fields of a table using arcpy.da.TableToNumPyArray.
+    /// Rows having null values are skipped if skip_nulls is set, otherwise reading them fails.
+    pub fn from_table(py: Python<'py>, in_table: &str, field_names: &[&str], where_clause: &str, skip_nulls: bool) -> PyResult<PyStructuredArray<'py>> {
+        Self::read(py, "TableToNumPyArray", in_table, field_names, where_clause, skip_nulls)
+    }
+
+    fn read(py: Python<'py>, function_name: &str, in_table: &str, field_names: &[&str], where_clause: &str, skip_nulls: bool) -> PyResult<PyStructuredArray<'py>> {
+        let arcpy_da = PyModule::import(py, "arcpy.da")?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("skip_nulls", skip_nulls)?;
+        let where_clause = if where_clause.is_empty() { None } else { Some(where_clause) };
+        let pyarray = arcpy_da.call(function_name, (in_table, field_names.to_vec(), where_clause), Some(kwargs))?;
+
+        Ok(PyStructuredArray {
+            pyarray
+        })
+    }
+
+    /// Wraps an existing structured array.
+    pub fn from_object(pyarray: &'py PyAny) -> PyStructuredArray<'py> {
+        PyStructuredArray {
+            pyarray
+        }
+    }
+
+    /// Returns the underlying structured array.
+    pub fn as_object(&self) -> &'py PyAny {
+        self.pyarray
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> PyResult<usize> {
+        self.pyarray.len()
+    }
+
+    pub fn is_empty(&self) -> PyResult<bool> {
+        Ok(0 == self.len()?)
+    }
+
+    /// Returns the field names of the structured array.
+    pub fn field_names(&self) -> PyResult<Vec<String>> {
+        self.pyarray.getattr("dtype")?.getattr("names")?.extract()
+    }
+
+    /// Returns the values of the specified field as 64-bit floating point numbers.
+    /// 32-bit floating point and integer fields are converted.
+    pub fn f64_values(&self, field_name: &str) -> PyResult<Vec<f64>> {
+        self.column_values(field_name, "float64")
+    }
+
+    /// Returns the values of the specified field as 32-bit integers.
+    /// Floating point and 64-bit integer fields are rejected instead of being truncated.
+    pub fn i32_values(&self, field_name: &str) -> PyResult<Vec<i32>> {
+        self.column_values(field_name, "int32")
+    }
+
+    /// Returns the values of the specified field as 64-bit integers.
+    /// 32-bit integer fields are converted and floating point fields are rejected.
+    pub fn i64_values(&self, field_name: &str) -> PyResult<Vec<i64>> {
+        self.column_values(field_name, "int64")
+    }
+
+    /// Returns the values of the specified text field.
+    pub fn string_values(&self, field_name: &str) -> PyResult<Vec<String>> {
+        self.pyarray.get_item(field_name)?.call_method0("tolist")?.extract()
+    }
+
+    /// Returns the coordinate pairs of the specified field like "SHAPE@XY".
+    pub fn xy_values(&self, field_name: &str) -> PyResult<Vec<(f64, f64)>> {
+        let coordinates: Vec<f64> = self.column_values(field_name, "float64")?;
+        if coordinates.len() != 2 * self.len()? {
+            return Err(PyValueError::new_err(format!("Field '{}' does not contain coordinate pairs!", field_name)));
+        }
+
+        Ok(coordinates.chunks_exact(2).map(|xy| (xy[0], xy[1])).collect())
+    }
+
+    /// Copies the values of the field using the buffer protocol.
+    /// The buffer of the field is used directly if the field has the NumPy data type,
+    /// otherwise the values are cast if NumPy considers the cast to be safe.
+    fn column_values<T: Element + Copy>(&self, field_name: &str, dtype: &str) -> PyResult<Vec<T>> {
+        let py = self.pyarray.py();
+        let pycolumn = self.pyarray.get_item(field_name)?;
+        if let Ok(buffer) = PyBuffer::<T>::get(pycolumn) {
+            return buffer.to_vec(py);
+        }
+
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("casting", "safe")?;
+        let pycolumn = pycolumn.call_method("astype", (dtype,), Some(kwargs))
+            .map_err(|_| PyValueError::new_err(format!("Field '{}' cannot be converted into {} without losing values!", field_name, dtype)))?;
+        let buffer = PyBuffer::<T>::get(pycolumn)?;
+
+        buffer.to_vec(py)
+    }
+
+    /// Writes the structured array as a new point feature class using arcpy.da.NumPyArrayToFeatureClass.
+    /// The shape fields are one XY field or the X and the Y field.
+    pub fn to_feature_class(&self, out_table: &str, shape_fields: &[&str], wkid: Option<i32>) -> PyResult<()> {
+        let py = self.pyarray.py();
+        let arcpy = PyModule::import(py, "arcpy")?;
+        let spatial_reference = match wkid {
+            Some(wkid) => arcpy.call1("SpatialReference", (wkid,))?.to_object(py),
+            None => py.None()
+        };
+        arcpy.getattr("da")?.call_method1("NumPyArrayToFeatureClass", (self.pyarray, out_table, shape_fields.to_vec(), spatial_reference))?;
+
+        Ok(())
+    }
+
+    /// Joins the fields of the structured array to an existing table using arcpy.da.ExtendTable.
+    /// Existing fields are updated unless append_only is set.
+    pub fn extend_table(&self, in_table: &str, table_match_field: &str, array_match_field: &str, append_only: bool) -> PyResult<()> {
+        let arcpy_da = PyModule::import(self.pyarray.py(), "arcpy.da")?;
+        arcpy_da.call1("ExtendTable", (in_table, table_match_field, self.pyarray, array_match_field, append_only))?;
+
+        Ok(())
+    }
+}
+
+
+
+/// Represents a column of a structured array being built.
+enum ColumnValues {
+    Double(Vec<f64>),
+    Integer(Vec<i32>),
+    BigInteger(Vec<i64>),
+    Text(Vec<String>),
+    XY(Vec<(f64, f64)>)
+}
+
+impl ColumnValues {
+
+    fn len(&self) -> usize {
+        match self {
+            ColumnValues::Double(values) => values.len(),
+            ColumnValues::Integer(values) => values.len(),
+            ColumnValues::BigInteger(values) => values.len(),
+            ColumnValues::Text(values) => values.len(),
+            ColumnValues::XY(values) => values.len()
+        }
+    }
+
+    fn dtype(&self, py: Python, name: &str) -> PyObject {
+        match self {
+            ColumnValues::Double(_) => (name, "<f8").to_object(py),
+            ColumnValues::Integer(_) => (name, "<i4").to_object(py),
+            ColumnValues::BigInteger(_) => (name, "<i8").to_object(py),
+            ColumnValues::Text(values) => {
+                let max_length = values.iter().map(|value| value.chars().count()).max().unwrap_or_default().max(1);
+                (name, format!("<U{}", max_length)).to_object(py)
+            },
+            ColumnValues::XY(_) => (name, "<f8", (2,)).to_object(py)
+        }
+    }
+
+    fn value(&self, py: Python, index: usize) -> PyObject {
+        match self {
+            ColumnValues::Double(values) => values[index].to_object(py),
+            ColumnValues::Integer(values) => values[index].to_object(py),
+            ColumnValues::BigInteger(values) => values[index].to_object(py),
+            ColumnValues::Text(values) => values[index].to_object(py),
+            ColumnValues::XY(values) => values[index].to_object(py)
+        }
+    }
+}
+
+
+
+/// Represents a builder for a NumPy structured array from Rust columns.
+/// # Examples
+///
+/// ```ignore
+/// let pyarray = columnar::StructuredArrayBuilder::new()
+///     .with_xy_column("XY", locations)
+///     .with_f64_column("Density", densities)
+///     .build(py)?;
+/// pyarray.to_feature_class(&out_features, &["XY"], Some(4326))?;
+/// ```
+#[derive(Default)]
+pub struct StructuredArrayBuilder {
+    columns: Vec<(String, ColumnValues)>
+}
+
+impl StructuredArrayBuilder {
+
+    pub fn new() -> StructuredArrayBuilder {
+        StructuredArrayBuilder::default()
+    }
+
+    /// Adds a column of 64-bit floating point numbers.
+    pub fn with_f64_column(self, name: &str, values: Vec<f64>) -> StructuredArrayBuilder {
+        self.with_column(name, ColumnValues::Double(values))
+    }
+
+    /// Adds a column of 32-bit integers.
+    pub fn with_i32_column(self, name: &str, values: Vec<i32>) -> StructuredArrayBuilder {
+        self.with_column(name, ColumnValues::Integer(values))
+    }
+
+    /// Adds a column of 64-bit integers.
+    pub fn with_i64_column(self, name: &str, values: Vec<i64>) -> StructuredArrayBuilder {
+        self.with_column(name, ColumnValues::BigInteger(values))
+    }
+
+    /// Adds a text column.
+    pub fn with_string_column(self, name: &str, values: Vec<String>) -> StructuredArrayBuilder {
+        self.with_column(name, ColumnValues::Text(values))
+    }
+
+    /// Adds a column of coordinate pairs being usable as shape field.
+    pub fn with_xy_column(self, name: &str, values: Vec<(f64, f64)>) -> StructuredArrayBuilder {
+        self.with_column(name, ColumnValues::XY(values))
+    }
+
+    fn with_column(mut self, name: &str, values: ColumnValues) -> StructuredArrayBuilder {
+        self.columns.push((name.to_string(), values));
+        self
+    }
+
+    /// Creates the structured array using numpy.array.
+    pub fn build(self, py: Python) -> PyResult<PyStructuredArray> {
+        let row_count = self.columns.first().map(|(_, values)| values.len()).unwrap_or_default();
+        if self.columns.iter().any(|(_, values)| values.len() != row_count) {
+            return Err(PyValueError::new_err("Columns must have the same length!"));
+        }
+
+        let dtype: Vec<PyObject> = self.columns.iter().map(|(name, values)| values.dtype(py, name)).collect();
+        let rows: Vec<&PyTuple> = (0..row_count)
+            .map(|index| PyTuple::new(py, self.columns.iter().map(|(_, values)| values.value(py, index))))
+            .collect();
+        let numpy = PyModule::import(py, "numpy")?;
+        let pyarray = numpy.call1("array", (rows, dtype))?;
+
+        Ok(PyStructuredArray::from_object(pyarray))
+    }
+}
+
+
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn read_and_write_columns() {
+        use crate::gp::api::{FieldType, ShapeType};
+        use crate::gp::testing::{MockArcpy, TestTable, TestValue};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let cities = TestTable::features(ShapeType::Point, 4326)
+            .with_field("Name", FieldType::String)
+            .with_field("Population", FieldType::Integer)
+            .with_row(vec![TestValue::Point(12.24555, 51.83864), "Dessau".into(), 74_000.into()])
+            .with_row(vec![TestValue::Point(13.40495, 52.52001), "Berlin".into(), 3_645_000.into()]);
+        arcpy.create_test_table("memory/columnar_cities", &cities).unwrap();
+
+        let pyarray = PyStructuredArray::from_feature_class(py, "memory/columnar_cities", &["OID@", "SHAPE@XY", "Name", "Population"], "", false).unwrap();
+        assert_eq!(2, pyarray.len().unwrap(), "Row count is wrong!");
+        assert_eq!(vec![1, 2], pyarray.i32_values("OID@").unwrap(), "Object IDs are wrong!");
+        assert_eq!(vec![(12.24555, 51.83864), (13.40495, 52.52001)], pyarray.xy_values("SHAPE@XY").unwrap(), "Locations are wrong!");
+        assert_eq!(vec!["Dessau", "Berlin"], pyarray.string_values("Name").unwrap(), "Names are wrong!");
+        assert_eq!(vec![74_000.0, 3_645_000.0], pyarray.f64_values("Population").unwrap(), "Populations are wrong!");
+        assert_eq!(vec![74_000, 3_645_000], pyarray.i64_values("Population").unwrap(), "Populations are wrong!");
+        assert!(pyarray.i32_values("SHAPE@XY").is_err(), "Coordinates must not be truncated!");
+
+        let densities = StructuredArrayBuilder::new()
+            .with_i32_column("CityID", vec![1, 2])
+            .with_f64_column("Density", vec![1000.5, 4090.0])
+            .build(py)
+            .unwrap();
+        densities.extend_table("memory/columnar_cities", "OBJECTID", "CityID", true).unwrap();
+        let extended = PyStructuredArray::from_table(py, "memory/columnar_cities", &["Density"], "", false).unwrap();
+        assert_eq!(vec![1000.5, 4090.0], extended.f64_values("Density").unwrap(), "Densities are wrong!");
+
+        let invalid = StructuredArrayBuilder::new()
+            .with_f64_column("X", vec![1.0])
+            .with_f64_column("Y", vec![]);
+        assert!(invalid.build(py).is_err(), "Columns of different length must not be accepted!");
+
+        StructuredArrayBuilder::new()
+            .with_xy_column("XY", vec![(7.0, 50.0)])
+            .with_string_column("Name", vec![String::from("Bonn")])
+            .build(py)
+            .unwrap()
+            .to_feature_class("memory/columnar_capitals", &["XY"], Some(4326))
+            .unwrap();
+        let capitals = PyStructuredArray::from_feature_class(py, "memory/columnar_capitals", &["SHAPE@XY", "Name"], "", false).unwrap();
+        assert_eq!(vec![(7.0, 50.0)], capitals.xy_values("SHAPE@XY").unwrap(), "Locations are wrong!");
+        assert_eq!(vec!["Bonn"], capitals.string_values("Name").unwrap(), "Names are wrong!");
+    }
+}
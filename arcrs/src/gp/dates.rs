@@ -0,0 +1,158 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Converts the values of date and time fields between Python and chrono.
+//! Date fields are read as datetime.datetime without a time zone, DateOnly fields as datetime.date,
+//! TimeOnly fields as datetime.time and TimestampOffset fields as datetime.datetime having a time zone.
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDate, PyDateAccess, PyDateTime, PyDelta, PyTime, PyTimeAccess};
+
+
+
+/// Converts a datetime.datetime or a datetime.date into a date and time.
+/// The time zone of a datetime is ignored.
+pub fn naive_datetime_from_py(pyvalue: &PyAny) -> PyResult<NaiveDateTime> {
+    if let Ok(pydatetime) = pyvalue.downcast::<PyDateTime>() {
+        let date = date_from_parts(pydatetime.get_year(), pydatetime.get_month(), pydatetime.get_day())?;
+        let time = time_from_parts(pydatetime.get_hour(), pydatetime.get_minute(), pydatetime.get_second(), pydatetime.get_microsecond())?;
+        return Ok(date.and_time(time));
+    }
+
+    Ok(naive_date_from_py(pyvalue)?.and_time(NaiveTime::MIN))
+}
+
+/// Converts a datetime.date or the date of a datetime.datetime.
+pub fn naive_date_from_py(pyvalue: &PyAny) -> PyResult<NaiveDate> {
+    let pydate: &PyDate = pyvalue.downcast()
+        .map_err(|_| PyValueError::new_err("The value must be a date!"))?;
+
+    date_from_parts(pydate.get_year(), pydate.get_month(), pydate.get_day())
+}
+
+/// Converts a datetime.time or the time of a datetime.datetime.
+pub fn naive_time_from_py(pyvalue: &PyAny) -> PyResult<NaiveTime> {
+    if let Ok(pydatetime) = pyvalue.downcast::<PyDateTime>() {
+        return time_from_parts(pydatetime.get_hour(), pydatetime.get_minute(), pydatetime.get_second(), pydatetime.get_microsecond());
+    }
+
+    let pytime: &PyTime = pyvalue.downcast()
+        .map_err(|_| PyValueError::new_err("The value must be a time!"))?;
+    time_from_parts(pytime.get_hour(), pytime.get_minute(), pytime.get_second(), pytime.get_microsecond())
+}
+
+/// Converts a datetime.datetime having a time zone like the values of TimestampOffset fields.
+pub fn datetime_offset_from_py(pyvalue: &PyAny) -> PyResult<DateTime<FixedOffset>> {
+    let naive_datetime = naive_datetime_from_py(pyvalue)?;
+    let pyoffset = pyvalue.call_method0("utcoffset")?;
+    if pyoffset.is_none() {
+        return Err(PyValueError::new_err("The datetime must have a time zone!"));
+    }
+
+    let offset_seconds: f64 = pyoffset.call_method0("total_seconds")?.extract()?;
+    let offset = FixedOffset::east_opt(offset_seconds as i32)
+        .ok_or_else(|| PyValueError::new_err("The time zone offset is not valid!"))?;
+    offset.from_local_datetime(&naive_datetime)
+        .single()
+        .ok_or_else(|| PyValueError::new_err("The datetime is not valid!"))
+}
+
+fn date_from_parts(year: i32, month: u8, day: u8) -> PyResult<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month.into(), day.into())
+        .ok_or_else(|| PyValueError::new_err("The date is not valid!"))
+}
+
+fn time_from_parts(hour: u8, minute: u8, second: u8, microsecond: u32) -> PyResult<NaiveTime> {
+    NaiveTime::from_hms_micro_opt(hour.into(), minute.into(), second.into(), microsecond)
+        .ok_or_else(|| PyValueError::new_err("The time is not valid!"))
+}
+
+
+
+/// Converts a date and time into a datetime.datetime without a time zone.
+/// Python only supports microseconds, so that nanoseconds are truncated.
+pub fn naive_datetime_to_py(py: Python, datetime: &NaiveDateTime) -> PyResult<PyObject> {
+    let pydatetime = new_pydatetime(py, datetime, None)?;
+
+    Ok(pydatetime.to_object(py))
+}
+
+/// Converts a date into a datetime.date.
+pub fn naive_date_to_py(py: Python, date: &NaiveDate) -> PyResult<PyObject> {
+    let pydate = PyDate::new(py, date.year(), date.month() as u8, date.day() as u8)?;
+
+    Ok(pydate.to_object(py))
+}
+
+/// Converts a time into a datetime.time without a time zone.
+pub fn naive_time_to_py(py: Python, time: &NaiveTime) -> PyResult<PyObject> {
+    let pytime = PyTime::new(py, time.hour() as u8, time.minute() as u8, time.second() as u8, microsecond(time.nanosecond()), None)?;
+
+    Ok(pytime.to_object(py))
+}
+
+/// Converts a date and time having an offset into a datetime.datetime using a datetime.timezone.
+pub fn datetime_offset_to_py(py: Python, datetime: &DateTime<FixedOffset>) -> PyResult<PyObject> {
+    let pyoffset = PyDelta::new(py, 0, datetime.offset().local_minus_utc(), 0, true)?;
+    let pytimezone = py.import("datetime")?.call1("timezone", (pyoffset,))?.to_object(py);
+    let pydatetime = new_pydatetime(py, &datetime.naive_local(), Some(&pytimezone))?;
+
+    Ok(pydatetime.to_object(py))
+}
+
+fn new_pydatetime<'py>(py: Python<'py>, datetime: &NaiveDateTime, pytimezone: Option<&PyObject>) -> PyResult<&'py PyDateTime> {
+    PyDateTime::new(py, datetime.year(), datetime.month() as u8, datetime.day() as u8,
+        datetime.hour() as u8, datetime.minute() as u8, datetime.second() as u8, microsecond(datetime.nanosecond()), pytimezone)
+}
+
+/// Returns the microseconds of the nanoseconds and keeps leap seconds below one million.
+fn microsecond(nanosecond: u32) -> u32 {
+    (nanosecond / 1000).min(999_999)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn convert_dates_and_times() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let datetime = NaiveDate::from_ymd_opt(2021, 3, 14).unwrap().and_hms_micro_opt(15, 9, 26, 535_897).unwrap();
+        let pydatetime = naive_datetime_to_py(py, &datetime).unwrap();
+        assert_eq!("2021-03-14 15:09:26.535897", pydatetime.as_ref(py).str().unwrap().to_str().unwrap(), "Python datetime is wrong!");
+        assert_eq!(datetime, naive_datetime_from_py(pydatetime.as_ref(py)).unwrap(), "Datetime is wrong!");
+        assert_eq!(datetime.date(), naive_date_from_py(pydatetime.as_ref(py)).unwrap(), "Date is wrong!");
+        assert_eq!(datetime.time(), naive_time_from_py(pydatetime.as_ref(py)).unwrap(), "Time is wrong!");
+        assert!(datetime_offset_from_py(pydatetime.as_ref(py)).is_err(), "Datetime without time zone must not have an offset!");
+
+        let pydate = naive_date_to_py(py, &datetime.date()).unwrap();
+        assert_eq!(datetime.date().and_time(NaiveTime::MIN), naive_datetime_from_py(pydate.as_ref(py)).unwrap(), "Datetime of date is wrong!");
+        let pytime = naive_time_to_py(py, &datetime.time()).unwrap();
+        assert_eq!(datetime.time(), naive_time_from_py(pytime.as_ref(py)).unwrap(), "Time is wrong!");
+
+        let offset_datetime = FixedOffset::east_opt(3600).unwrap().from_local_datetime(&datetime).unwrap();
+        let pyoffset_datetime = datetime_offset_to_py(py, &offset_datetime).unwrap();
+        assert_eq!("2021-03-14 15:09:26.535897+01:00", pyoffset_datetime.as_ref(py).str().unwrap().to_str().unwrap(), "Python datetime is wrong!");
+        assert_eq!(offset_datetime, datetime_offset_from_py(pyoffset_datetime.as_ref(py)).unwrap(), "Datetime with offset is wrong!");
+    }
+}
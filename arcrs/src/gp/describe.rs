@@ -0,0 +1,272 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Describes tables and feature classes using one arcpy.Describe call.
+//! The description is a plain Rust value, so that tools can cache it instead of
+//! calling arcpy.Describe for every property.
+
+use super::api::{GpField, GpSpatialReference, ShapeType};
+use crate::data::field_type_from_arcpy;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::str::FromStr;
+
+const ATTACHMENT_RELATIONSHIP_SUFFIX: &str = "__ATTACHREL";
+
+
+
+/// Represents the properties of a table or feature class returned by arcpy.Describe.
+/// The properties of feature classes like the shape field name are None for tables.
+/// # Examples
+///
+/// ```ignore
+/// let description = in_parameter.describe()?;
+/// for field in description.attribute_fields() {
+///     messages.add_message(&field.name)?;
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DatasetDescription {
+    pub name: String,
+    pub catalog_path: String,
+    /// The path of the workspace or feature dataset containing the dataset.
+    pub workspace_path: String,
+    /// The data type reported by arcpy.Describe like FeatureClass, Table or FeatureLayer.
+    pub data_type: String,
+    pub fields: Vec<GpField>,
+    pub oid_field_name: Option<String>,
+    pub shape_field_name: Option<String>,
+    pub global_id_field_name: Option<String>,
+    pub shape_type: Option<ShapeType>,
+    pub has_z: bool,
+    pub has_m: bool,
+    /// The extent as (xmin, ymin, xmax, ymax) which is None for empty feature classes.
+    pub extent: Option<(f64, f64, f64, f64)>,
+    pub spatial_reference: Option<GpSpatialReference>,
+    pub feature_count: i64,
+    pub editor_tracking: bool,
+    pub has_attachments: bool
+}
+
+impl DatasetDescription {
+
+    /// Describes the dataset using arcpy.Describe.
+    /// The feature count is the only property being requested by another call using arcpy.management.GetCount.
+    pub fn new(py: Python, dataset: &PyAny) -> PyResult<DatasetDescription> {
+        let arcpy = PyModule::import(py, "arcpy")?;
+        let pydescribe = arcpy.call1("Describe", (dataset,))?;
+        let mut description = DatasetDescription::from_describe(pydescribe)?;
+        let pyresult = arcpy.getattr("management")?.call_method1("GetCount", (&description.catalog_path,))?;
+        let feature_count_as_text: String = pyresult.call_method1("getOutput", (0,))?.extract()?;
+        description.feature_count = feature_count_as_text.parse()
+            .map_err(|_| PyValueError::new_err(format!("Feature count '{}' is not valid!", feature_count_as_text)))?;
+
+        Ok(description)
+    }
+
+    /// Reads the properties of an existing arcpy.Describe result.
+    /// The feature count is not part of arcpy.Describe and is always zero.
+    pub fn from_describe(pydescribe: &PyAny) -> PyResult<DatasetDescription> {
+        let pyfields: Vec<&PyAny> = pydescribe.getattr("fields")?.extract()?;
+        let mut fields = Vec::with_capacity(pyfields.len());
+        for pyfield in pyfields {
+            let field_name: String = pyfield.getattr("name")?.extract()?;
+            let field_type_as_text: String = pyfield.getattr("type")?.extract()?;
+            let field_type = field_type_from_arcpy(&field_type_as_text)
+                .ok_or_else(|| PyValueError::new_err(format!("Field type '{}' is not supported!", field_type_as_text)))?;
+            fields.push(GpField {
+                name: field_name,
+                field_type
+            });
+        }
+
+        let shape_type = match optional_text(pydescribe, "shapeType")? {
+            Some(shape_type_as_text) => Some(ShapeType::from_str(&shape_type_as_text)
+                .map_err(|_| PyValueError::new_err(format!("Shape type '{}' is not supported!", shape_type_as_text)))?),
+            None => None
+        };
+
+        let spatial_reference = match optional_attr(pydescribe, "spatialReference")? {
+            Some(pyspatial_reference) => Some(GpSpatialReference {
+                wkid: pyspatial_reference.getattr("factoryCode")?.extract()?
+            }),
+            None => None
+        };
+
+        let relationship_class_names: Vec<String> = match optional_attr(pydescribe, "relationshipClassNames")? {
+            Some(pynames) => pynames.extract()?,
+            None => Vec::new()
+        };
+
+        Ok(DatasetDescription {
+            name: pydescribe.getattr("name")?.extract()?,
+            catalog_path: pydescribe.getattr("catalogPath")?.extract()?,
+            workspace_path: optional_text(pydescribe, "path")?.unwrap_or_default(),
+            data_type: pydescribe.getattr("dataType")?.extract()?,
+            fields,
+            oid_field_name: optional_text(pydescribe, "OIDFieldName")?,
+            shape_field_name: optional_text(pydescribe, "shapeFieldName")?,
+            global_id_field_name: optional_text(pydescribe, "globalIDFieldName")?,
+            shape_type,
+            has_z: optional_flag(pydescribe, "hasZ")?,
+            has_m: optional_flag(pydescribe, "hasM")?,
+            extent: extent_from_describe(pydescribe)?,
+            spatial_reference,
+            feature_count: 0,
+            editor_tracking: optional_flag(pydescribe, "editorTrackingEnabled")?,
+            has_attachments: relationship_class_names.iter()
+                .any(|relationship_class_name| relationship_class_name.to_uppercase().ends_with(ATTACHMENT_RELATIONSHIP_SUFFIX))
+        })
+    }
+
+    pub fn is_feature_class(&self) -> bool {
+        self.shape_type.is_some()
+    }
+
+    /// Returns the field having the specified name ignoring the case.
+    pub fn field(&self, field_name: &str) -> Option<&GpField> {
+        self.fields.iter()
+            .find(|field| field.name.eq_ignore_ascii_case(field_name))
+    }
+
+    /// Returns the fields without the OID, shape and GlobalID field.
+    pub fn attribute_fields(&self) -> impl Iterator<Item = &GpField> {
+        self.fields.iter()
+            .filter(move |field| !self.is_system_field(&field.name))
+    }
+
+    fn is_system_field(&self, field_name: &str) -> bool {
+        [&self.oid_field_name, &self.shape_field_name, &self.global_id_field_name].iter()
+            .any(|system_field_name| match system_field_name {
+                Some(system_field_name) => system_field_name.eq_ignore_ascii_case(field_name),
+                None => false
+            })
+    }
+}
+
+
+
+/// Returns the attribute if the describe object has it.
+/// arcpy raises an AttributeError for properties not being supported by the dataset.
+fn optional_attr<'py>(pydescribe: &'py PyAny, attr_name: &str) -> PyResult<Option<&'py PyAny>> {
+    if !pydescribe.hasattr(attr_name)? {
+        return Ok(None);
+    }
+
+    let pyvalue = pydescribe.getattr(attr_name)?;
+    if pyvalue.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(pyvalue))
+}
+
+/// Returns the text attribute and treats empty text as None.
+fn optional_text(pydescribe: &PyAny, attr_name: &str) -> PyResult<Option<String>> {
+    match optional_attr(pydescribe, attr_name)? {
+        Some(pyvalue) => {
+            let text: String = pyvalue.extract()?;
+            if text.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(text))
+            }
+        },
+        None => Ok(None)
+    }
+}
+
+fn optional_flag(pydescribe: &PyAny, attr_name: &str) -> PyResult<bool> {
+    match optional_attr(pydescribe, attr_name)? {
+        Some(pyvalue) => pyvalue.extract(),
+        None => Ok(false)
+    }
+}
+
+/// Returns the extent which arcpy reports using NaN for empty feature classes.
+fn extent_from_describe(pydescribe: &PyAny) -> PyResult<Option<(f64, f64, f64, f64)>> {
+    let pyextent = match optional_attr(pydescribe, "extent")? {
+        Some(pyextent) => pyextent,
+        None => return Ok(None)
+    };
+
+    let mut coordinates = [0.0_f64; 4];
+    for (coordinate, attr_name) in coordinates.iter_mut().zip(["XMin", "YMin", "XMax", "YMax"]) {
+        let pycoordinate = pyextent.getattr(attr_name)?;
+        if pycoordinate.is_none() {
+            return Ok(None);
+        }
+
+        *coordinate = pycoordinate.extract()?;
+        if coordinate.is_nan() {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some((coordinates[0], coordinates[1], coordinates[2], coordinates[3])))
+}
+
+
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn describe_feature_class_and_table() {
+        use crate::gp::api::FieldType;
+        use crate::gp::testing::{MockArcpy, TestTable, TestValue};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let wells = TestTable::features(ShapeType::Point, 4326)
+            .with_field("Depth", FieldType::Double)
+            .with_row(vec![TestValue::Point(7.0, 51.0), TestValue::Double(12.5)])
+            .with_row(vec![TestValue::Point(8.0, 52.0), TestValue::Null]);
+        arcpy.create_test_table("memory/describe_wells", &wells).unwrap();
+        let pyfield_description = vec![("GlobalID", "GLOBALID")];
+        py.import("arcpy").unwrap().getattr("management").unwrap().call_method1("AddFields", ("memory/describe_wells", pyfield_description)).unwrap();
+        arcpy.create_test_table("memory/describe_owners", &TestTable::table().with_field("Name", FieldType::String)).unwrap();
+
+        let pycatalog_path = "memory/describe_wells".to_object(py);
+        let description = DatasetDescription::new(py, pycatalog_path.as_ref(py)).unwrap();
+        assert_eq!("describe_wells", description.name, "Name is wrong!");
+        assert_eq!("memory", description.workspace_path, "Workspace path is wrong!");
+        assert_eq!("FeatureClass", description.data_type, "Data type is wrong!");
+        assert!(description.is_feature_class(), "Wells must be a feature class!");
+        assert_eq!(Some(ShapeType::Point), description.shape_type, "Shape type is wrong!");
+        assert_eq!(Some("OBJECTID"), description.oid_field_name.as_deref(), "OID field name is wrong!");
+        assert_eq!(Some("Shape"), description.shape_field_name.as_deref(), "Shape field name is wrong!");
+        assert_eq!(Some("GlobalID"), description.global_id_field_name.as_deref(), "GlobalID field name is wrong!");
+        assert_eq!(Some(4326), description.spatial_reference.as_ref().map(|spatial_reference| spatial_reference.wkid), "Spatial reference is wrong!");
+        assert_eq!(Some((7.0, 51.0, 8.0, 52.0)), description.extent, "Extent is wrong!");
+        assert_eq!(2, description.feature_count, "Feature count is wrong!");
+        assert_eq!(vec!["Depth"], description.attribute_fields().map(|field| field.name.as_str()).collect::<Vec<_>>(), "Attribute fields are wrong!");
+        assert_eq!(Some(FieldType::Double), description.field("depth").map(|field| field.field_type), "Field is wrong!");
+        assert_eq!(Some(FieldType::GlobalID), description.field("globalid").map(|field| field.field_type), "GlobalID field is wrong!");
+        assert!(!description.has_z && !description.has_m && !description.editor_tracking && !description.has_attachments, "Flags are wrong!");
+
+        let pycatalog_path = "memory/describe_owners".to_object(py);
+        let description = DatasetDescription::new(py, pycatalog_path.as_ref(py)).unwrap();
+        assert!(!description.is_feature_class(), "Owners must be a table!");
+        assert_eq!(None, description.shape_field_name, "Shape field name is wrong!");
+        assert_eq!(None, description.extent, "Extent is wrong!");
+        assert_eq!(0, description.feature_count, "Feature count is wrong!");
+    }
+}
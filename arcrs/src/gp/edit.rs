@@ -0,0 +1,267 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Wraps arcpy.da.Editor, which is required for writing into versioned data
+//! and into datasets participating in a topology or network.
+//! For more information take a look at https://pro.arcgis.com/en/pro-app/arcpy/data-access/editor.htm
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::PyNativeType;
+
+
+
+/// Represents the settings of an edit session before it is started.
+pub struct EditSessionBuilder {
+    workspace: String,
+    with_undo: bool,
+    multiuser_mode: bool
+}
+
+impl EditSessionBuilder {
+
+    /// Uses the defaults of arcpy which enables the undo stack and the multiuser mode.
+    pub fn new(workspace: &str) -> EditSessionBuilder {
+        EditSessionBuilder {
+            workspace: workspace.to_string(),
+            with_undo: true,
+            multiuser_mode: true
+        }
+    }
+
+    /// Disabling the undo stack improves the performance of bulk edits.
+    pub fn with_undo(mut self, with_undo: bool) -> EditSessionBuilder {
+        self.with_undo = with_undo;
+        self
+    }
+
+    /// The multiuser mode must be enabled for versioned data and disabled for non-versioned enterprise data.
+    pub fn with_multiuser_mode(mut self, multiuser_mode: bool) -> EditSessionBuilder {
+        self.multiuser_mode = multiuser_mode;
+        self
+    }
+
+    /// Starts editing and starts the first edit operation.
+    pub fn start(self, py: Python<'_>) -> PyResult<EditSession<'_>> {
+        let arcpy_da = PyModule::import(py, "arcpy.da")?;
+        let pyeditor = arcpy_da.call1("Editor", (&self.workspace,))?;
+        pyeditor.call_method1("startEditing", (self.with_undo, self.multiuser_mode))?;
+        let mut edit_session = EditSession {
+            pyeditor,
+            in_operation: false,
+            is_editing: true
+        };
+        edit_session.start_operation()?;
+
+        Ok(edit_session)
+    }
+}
+
+
+
+/// Represents a running edit session like a with statement using arcpy.da.Editor.
+/// The edits are only saved by calling commit, so that the edits are rolled back
+/// if the session is dropped because of an error or a panic.
+/// # Examples
+///
+/// ```ignore
+/// let edit_session = edit::EditSession::start(py, "c:/connections/prod.sde")?;
+/// let insert_cursor = PyInsertCursor::new(&py, "c:/connections/prod.sde/roads", field_names)?;
+/// insert_cursor.insert_rows(rows)?;
+/// insert_cursor.close()?;
+/// edit_session.commit()?;
+/// ```
+pub struct EditSession<'py> {
+    pyeditor: &'py PyAny,
+    in_operation: bool,
+    is_editing: bool
+}
+
+impl<'py> EditSession<'py> {
+
+    /// Starts an edit session using the defaults of arcpy.
+    pub fn start(py: Python<'py>, workspace: &str) -> PyResult<EditSession<'py>> {
+        EditSessionBuilder::new(workspace).start(py)
+    }
+
+    /// Runs the edits in a new edit session.
+    /// The edits are saved if the closure succeeds and rolled back otherwise.
+    /// The error of the closure is returned even if the rollback fails, which is printed instead.
+    pub fn run<T>(py: Python<'py>, builder: EditSessionBuilder, edits: impl FnOnce(&mut EditSession<'py>) -> PyResult<T>) -> PyResult<T> {
+        let mut edit_session = builder.start(py)?;
+        match edits(&mut edit_session) {
+            Ok(result) => {
+                edit_session.commit()?;
+
+                Ok(result)
+            },
+            Err(err) => {
+                if let Err(rollback_err) = edit_session.rollback() {
+                    rollback_err.print(py);
+                }
+
+                Err(err)
+            }
+        }
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.is_editing
+    }
+
+    pub fn in_operation(&self) -> bool {
+        self.in_operation
+    }
+
+    /// Starts a new edit operation.
+    pub fn start_operation(&mut self) -> PyResult<()> {
+        if self.in_operation {
+            return Err(PyValueError::new_err("The edit operation was already started!"));
+        }
+
+        self.pyeditor.call_method0("startOperation")?;
+        self.in_operation = true;
+
+        Ok(())
+    }
+
+    /// Stops the current edit operation and keeps its edits.
+    pub fn stop_operation(&mut self) -> PyResult<()> {
+        if !self.in_operation {
+            return Err(PyValueError::new_err("There is no edit operation!"));
+        }
+
+        self.pyeditor.call_method0("stopOperation")?;
+        self.in_operation = false;
+
+        Ok(())
+    }
+
+    /// Aborts the current edit operation and discards its edits.
+    pub fn abort_operation(&mut self) -> PyResult<()> {
+        if !self.in_operation {
+            return Err(PyValueError::new_err("There is no edit operation!"));
+        }
+
+        self.pyeditor.call_method0("abortOperation")?;
+        self.in_operation = false;
+
+        Ok(())
+    }
+
+    /// Stops the current edit operation and saves all edits of this session.
+    pub fn commit(mut self) -> PyResult<()> {
+        if self.in_operation {
+            self.stop_operation()?;
+        }
+
+        self.pyeditor.call_method1("stopEditing", (true,))?;
+        self.is_editing = false;
+
+        Ok(())
+    }
+
+    /// Aborts the current edit operation and discards all edits of this session.
+    pub fn rollback(mut self) -> PyResult<()> {
+        self.discard()
+    }
+
+    fn discard(&mut self) -> PyResult<()> {
+        if self.in_operation {
+            self.abort_operation()?;
+        }
+
+        self.is_editing = false;
+        self.pyeditor.call_method1("stopEditing", (false,))?;
+
+        Ok(())
+    }
+}
+
+impl Drop for EditSession<'_> {
+
+    /// Rolls back the edits of a session being neither committed nor rolled back.
+    fn drop(&mut self) {
+        if self.is_editing {
+            if let Err(err) = self.discard() {
+                err.print(self.pyeditor.py());
+            }
+        }
+    }
+}
+
+
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn commit_and_roll_back_edits() {
+        use crate::gp::api::{FieldType, PyInsertCursor};
+        use crate::gp::testing::{MockArcpy, TestTable};
+        use std::panic::{self, AssertUnwindSafe};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        arcpy.create_test_table("memory/edit_owners", &TestTable::table().with_field("Name", FieldType::String)).unwrap();
+        let insert_owner = |name: &str| -> PyResult<()> {
+            let insert_cursor = PyInsertCursor::new(&py, "memory/edit_owners", vec![String::from("Name")])?;
+            insert_cursor.insert_rows(vec![(name,)])?;
+            insert_cursor.close()
+        };
+
+        let mut edit_session = EditSession::start(py, "memory").unwrap();
+        assert!(edit_session.is_editing() && edit_session.in_operation(), "Edit session must be started!");
+        insert_owner("Alice").unwrap();
+        edit_session.stop_operation().unwrap();
+        edit_session.start_operation().unwrap();
+        insert_owner("Bob").unwrap();
+        edit_session.abort_operation().unwrap();
+        assert!(edit_session.abort_operation().is_err(), "Aborting without an operation must fail!");
+        edit_session.commit().unwrap();
+        assert_eq!(1, arcpy.row_count("memory/edit_owners").unwrap(), "Committed rows are wrong!");
+
+        let edit_result: PyResult<()> = EditSession::run(py, EditSessionBuilder::new("memory").with_undo(false), |_| {
+            insert_owner("Carol")?;
+            Err(PyValueError::new_err("Failed!"))
+        });
+        assert!(edit_result.is_err(), "Failed edits must return the error!");
+        assert_eq!(1, arcpy.row_count("memory/edit_owners").unwrap(), "Failed edits must be rolled back!");
+
+        let edit_result: PyResult<()> = EditSession::run(py, EditSessionBuilder::new("memory"), |edit_session| {
+            edit_session.pyeditor.call_method1("stopEditing", (true,))?;
+            Err(PyValueError::new_err("Failed!"))
+        });
+        assert_eq!("ValueError: Failed!", edit_result.unwrap_err().to_string(), "Failed rollbacks must return the error of the edits!");
+
+        let panic_result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _edit_session = EditSession::start(py, "memory").unwrap();
+            insert_owner("Dave").unwrap();
+            panic!("Edits panicked!");
+        }));
+        assert!(panic_result.is_err(), "Edits must panic!");
+        assert_eq!(1, arcpy.row_count("memory/edit_owners").unwrap(), "Panicking edits must be rolled back!");
+
+        let dropped_session = EditSession::start(py, "memory").unwrap();
+        insert_owner("Eve").unwrap();
+        drop(dropped_session);
+        assert_eq!(1, arcpy.row_count("memory/edit_owners").unwrap(), "Dropped edits must be rolled back!");
+    }
+}
@@ -0,0 +1,274 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Offers typed geometry operations by wrapping the methods of arcpy geometries.
+//! For more information take a look at https://pro.arcgis.com/en/pro-app/arcpy/classes/geometry.htm
+
+use super::api::{Point, ShapeType};
+use super::projection::PySpatialReference;
+use crate::data::arcpy::{geometry_from_object, geometry_to_object, is_plain_geometry};
+use crate::data::Geometry;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::PyNativeType;
+use std::str::FromStr;
+
+
+
+/// Represents a handle to an arcpy geometry like a shape being read using the SHAPE@ token.
+/// Operations creating new geometries return new handles, so that operations can be chained.
+/// # Examples
+///
+/// ```ignore
+/// let parcel = geometry::PyGeometry::from_object(row.shape(0)?);
+/// let protection_zone = well.buffer(500.0)?;
+/// if parcel.overlaps(&protection_zone)? {
+///     let affected_area = parcel.intersect(&protection_zone, ShapeType::Polygon)?.area()?;
+/// }
+/// ```
+pub struct PyGeometry<'py> {
+    pygeometry: &'py PyAny
+}
+
+impl<'py> PyGeometry<'py> {
+
+    /// Wraps an existing arcpy geometry.
+    pub fn from_object(pygeometry: &'py PyAny) -> PyGeometry<'py> {
+        PyGeometry {
+            pygeometry
+        }
+    }
+
+    /// Creates a new arcpy geometry using plain coordinates.
+    /// The created geometry has neither z-values nor m-values nor curves.
+    pub fn from_geometry(py: Python<'py>, geometry: &Geometry, spatial_reference: Option<&PySpatialReference>) -> PyResult<PyGeometry<'py>> {
+        let pygeometry = geometry_to_object(py, geometry, spatial_reference.map(|spatial_reference| spatial_reference.as_object()))?;
+
+        Ok(PyGeometry::from_object(pygeometry))
+    }
+
+    /// Creates a new point geometry.
+    pub fn from_point(py: Python<'py>, point: &Point, spatial_reference: Option<&PySpatialReference>) -> PyResult<PyGeometry<'py>> {
+        PyGeometry::from_geometry(py, &Geometry::Point(point.x, point.y), spatial_reference)
+    }
+
+    /// Returns the underlying arcpy geometry.
+    pub fn as_object(&self) -> &'py PyAny {
+        self.pygeometry
+    }
+
+    /// Converts the arcpy geometry into plain coordinates.
+    /// Geometries having z-values, m-values or curves are rejected, because plain coordinates cannot hold them.
+    pub fn to_geometry(&self) -> PyResult<Geometry> {
+        if !is_plain_geometry(self.pygeometry)? {
+            return Err(PyValueError::new_err("The geometry has z-values, m-values or curves which cannot be converted into plain coordinates!"));
+        }
+
+        Ok(geometry_from_object(self.pygeometry)?)
+    }
+
+    /// Returns the first point of the geometry.
+    pub fn to_point(&self) -> PyResult<Point> {
+        let first_point = self.pygeometry.getattr("firstPoint")?;
+        if first_point.is_none() {
+            return Err(PyValueError::new_err("The geometry is empty!"));
+        }
+
+        Ok(Point {
+            x: first_point.getattr("X")?.extract()?,
+            y: first_point.getattr("Y")?.extract()?
+        })
+    }
+
+    pub fn shape_type(&self) -> PyResult<ShapeType> {
+        let geometry_type: String = self.pygeometry.getattr("type")?.extract()?;
+        ShapeType::from_str(&geometry_type.to_uppercase())
+            .map_err(|_| PyValueError::new_err(format!("Geometry type '{}' is not supported!", geometry_type)))
+    }
+
+    /// Returns the spatial reference of the geometry.
+    pub fn spatial_reference(&self) -> PyResult<PySpatialReference<'py>> {
+        PySpatialReference::of_geometry(self.pygeometry)
+    }
+
+    pub fn area(&self) -> PyResult<f64> {
+        self.pygeometry.getattr("area")?.extract()
+    }
+
+    pub fn length(&self) -> PyResult<f64> {
+        self.pygeometry.getattr("length")?.extract()
+    }
+
+    pub fn point_count(&self) -> PyResult<usize> {
+        self.pygeometry.getattr("pointCount")?.extract()
+    }
+
+    pub fn part_count(&self) -> PyResult<usize> {
+        self.pygeometry.getattr("partCount")?.extract()
+    }
+
+    /// Returns the extent as (xmin, ymin, xmax, ymax).
+    pub fn extent(&self) -> PyResult<(f64, f64, f64, f64)> {
+        let pyextent = self.pygeometry.getattr("extent")?;
+        if pyextent.is_none() {
+            return Err(PyValueError::new_err("The geometry is empty!"));
+        }
+
+        Ok((pyextent.getattr("XMin")?.extract()?, pyextent.getattr("YMin")?.extract()?,
+            pyextent.getattr("XMax")?.extract()?, pyextent.getattr("YMax")?.extract()?))
+    }
+
+    /// Returns the shortest distance to the other geometry using the units of the spatial reference.
+    pub fn distance_to(&self, other: &PyGeometry) -> PyResult<f64> {
+        self.pygeometry.call_method1("distanceTo", (other.pygeometry,))?.extract()
+    }
+
+    /// Creates a polygon having the specified distance to this geometry.
+    pub fn buffer(&self, distance: f64) -> PyResult<PyGeometry<'py>> {
+        self.create("buffer", (distance,))
+    }
+
+    /// Creates the intersection having the specified shape type.
+    pub fn intersect(&self, other: &PyGeometry, result_type: ShapeType) -> PyResult<PyGeometry<'py>> {
+        let dimension = match result_type {
+            ShapeType::Point | ShapeType::Multipoint => 1,
+            ShapeType::Polyline => 2,
+            ShapeType::Polygon => 4,
+            ShapeType::MultiPatch => return Err(PyValueError::new_err("Intersections cannot be multipatches!"))
+        };
+
+        self.create("intersect", (other.pygeometry, dimension))
+    }
+
+    pub fn union(&self, other: &PyGeometry) -> PyResult<PyGeometry<'py>> {
+        self.create("union", (other.pygeometry,))
+    }
+
+    pub fn difference(&self, other: &PyGeometry) -> PyResult<PyGeometry<'py>> {
+        self.create("difference", (other.pygeometry,))
+    }
+
+    pub fn symmetric_difference(&self, other: &PyGeometry) -> PyResult<PyGeometry<'py>> {
+        self.create("symmetricDifference", (other.pygeometry,))
+    }
+
+    /// Clips the geometry using the extent (xmin, ymin, xmax, ymax).
+    pub fn clip(&self, extent: (f64, f64, f64, f64)) -> PyResult<PyGeometry<'py>> {
+        let arcpy = PyModule::import(self.pygeometry.py(), "arcpy")?;
+        let pyextent = arcpy.call1("Extent", extent)?;
+
+        self.create("clip", (pyextent,))
+    }
+
+    pub fn contains(&self, other: &PyGeometry) -> PyResult<bool> {
+        self.relation("contains", other)
+    }
+
+    pub fn within(&self, other: &PyGeometry) -> PyResult<bool> {
+        self.relation("within", other)
+    }
+
+    pub fn touches(&self, other: &PyGeometry) -> PyResult<bool> {
+        self.relation("touches", other)
+    }
+
+    pub fn crosses(&self, other: &PyGeometry) -> PyResult<bool> {
+        self.relation("crosses", other)
+    }
+
+    pub fn overlaps(&self, other: &PyGeometry) -> PyResult<bool> {
+        self.relation("overlaps", other)
+    }
+
+    pub fn disjoint(&self, other: &PyGeometry) -> PyResult<bool> {
+        self.relation("disjoint", other)
+    }
+
+    pub fn equals(&self, other: &PyGeometry) -> PyResult<bool> {
+        self.relation("equals", other)
+    }
+
+    fn create(&self, method_name: &str, args: impl IntoPy<Py<pyo3::types::PyTuple>>) -> PyResult<PyGeometry<'py>> {
+        let pygeometry = self.pygeometry.call_method1(method_name, args)?;
+
+        Ok(PyGeometry::from_object(pygeometry))
+    }
+
+    fn relation(&self, method_name: &str, other: &PyGeometry) -> PyResult<bool> {
+        self.pygeometry.call_method1(method_name, (other.pygeometry,))?.extract()
+    }
+}
+
+
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn measure_and_relate_geometries() {
+        use crate::gp::testing::MockArcpy;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        MockArcpy::install(py).unwrap();
+        let square = |xmin: f64, ymin: f64, size: f64| Geometry::Polygon(vec![vec![(xmin, ymin), (xmin, ymin + size), (xmin + size, ymin + size), (xmin + size, ymin), (xmin, ymin)]]);
+        let web_mercator = PySpatialReference::from_wkid(py, 3857).unwrap();
+        let parcel = PyGeometry::from_geometry(py, &square(0.0, 0.0, 10.0), Some(&web_mercator)).unwrap();
+        let neighbour = PyGeometry::from_geometry(py, &square(10.0, 0.0, 10.0), None).unwrap();
+        let overlapping = PyGeometry::from_geometry(py, &square(5.0, 5.0, 10.0), None).unwrap();
+        let distant = PyGeometry::from_geometry(py, &square(30.0, 0.0, 10.0), None).unwrap();
+        let well = PyGeometry::from_point(py, &Point { x: 2.0, y: 2.0 }, None).unwrap();
+
+        assert_eq!(ShapeType::Polygon, parcel.shape_type().unwrap(), "Shape type is wrong!");
+        assert_eq!(3857, parcel.spatial_reference().unwrap().wkid().unwrap(), "Spatial reference is wrong!");
+        assert_eq!(square(0.0, 0.0, 10.0), parcel.to_geometry().unwrap(), "Geometry is wrong!");
+        assert_eq!((0.0, 0.0, 10.0, 10.0), parcel.extent().unwrap(), "Extent is wrong!");
+        assert_eq!(100.0, parcel.area().unwrap(), "Area is wrong!");
+        assert_eq!(40.0, parcel.length().unwrap(), "Length is wrong!");
+        assert_eq!(25.0, parcel.intersect(&overlapping, ShapeType::Polygon).unwrap().area().unwrap(), "Intersection is wrong!");
+        assert_eq!(25.0, parcel.clip((0.0, 0.0, 5.0, 5.0)).unwrap().area().unwrap(), "Clipped area is wrong!");
+        assert_eq!(2, parcel.union(&distant).unwrap().part_count().unwrap(), "Union is wrong!");
+        assert_eq!(8.0, neighbour.distance_to(&well).unwrap(), "Distance is wrong!");
+
+        assert!(parcel.contains(&well).unwrap(), "Parcel must contain the well!");
+        assert!(well.within(&parcel).unwrap(), "Well must be within the parcel!");
+        assert!(parcel.touches(&neighbour).unwrap(), "Parcels must touch!");
+        assert!(!parcel.touches(&overlapping).unwrap(), "Overlapping parcels must not touch!");
+        assert!(parcel.overlaps(&overlapping).unwrap(), "Parcels must overlap!");
+        assert!(!parcel.overlaps(&neighbour).unwrap(), "Neighbouring parcels must not overlap!");
+        assert!(neighbour.disjoint(&well).unwrap(), "Neighbour and well must be disjoint!");
+        assert!(well.buffer(1.0).unwrap().within(&parcel).unwrap(), "Buffer must be within the parcel!");
+        assert_eq!(2.0, well.to_point().unwrap().x, "Point is wrong!");
+    }
+
+    #[test]
+    fn reject_converting_z_geometries() {
+        use crate::gp::testing::MockArcpy;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        MockArcpy::install(py).unwrap();
+        let arcpy = py.import("arcpy").unwrap();
+        let pypoint = arcpy.call_method1("Point", (1.0, 2.0, 3.0)).unwrap();
+        let well = PyGeometry::from_object(arcpy.call_method1("PointGeometry", (pypoint,)).unwrap());
+
+        assert!(well.to_geometry().is_err(), "Converting a geometry having z-values must fail!");
+        assert_eq!(1.0, well.to_point().unwrap().x, "Point is wrong!");
+    }
+}
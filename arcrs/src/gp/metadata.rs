@@ -0,0 +1,234 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::api;
+use super::pyt::PytToolboxDefinition;
+use std::fmt::Write;
+
+/// The help path ArcGIS Pro uses for system toolboxes.
+const ARCTOOLBOX_HELP_PATH: &str = "c:\\program files\\arcgis\\pro\\Resources\\Help\\gp";
+
+/// Returns the specified text escaped for XML content and attribute values.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c)
+        }
+    }
+
+    escaped
+}
+
+/// Returns the specified paragraphs as escaped HTML being used by the help elements.
+fn as_html(paragraphs: &[&str]) -> String {
+    let mut html = String::from("<DIV STYLE=\"text-align:Left;\">");
+    for paragraph in paragraphs {
+        html.push_str("<P><SPAN>");
+        html.push_str(&escape_xml(paragraph));
+        html.push_str("</SPAN></P>");
+    }
+    html.push_str("</DIV>");
+
+    escape_xml(&html)
+}
+
+/// Returns the specified items as escaped HTML list being used by the usage element.
+fn as_html_list(items: &[String]) -> String {
+    let mut html = String::from("<DIV STYLE=\"text-align:Left;\"><UL>");
+    for item in items {
+        html.push_str("<LI><P><SPAN>");
+        html.push_str(&escape_xml(item));
+        html.push_str("</SPAN></P></LI>");
+    }
+    html.push_str("</UL></DIV>");
+
+    escape_xml(&html)
+}
+
+/// Writes the search keys and the distribution info shared by toolbox and tool metadata.
+fn write_identification(xml: &mut String, title: &str, summary: &str, tags: &[String], format_name: &str) {
+    write!(xml, "<dataIdInfo><idCitation><resTitle>{}</resTitle></idCitation>", escape_xml(title)).unwrap();
+    if !summary.is_empty() {
+        write!(xml, "<idPurp>{}</idPurp>", escape_xml(summary)).unwrap();
+    }
+    if !tags.is_empty() {
+        xml.push_str("<searchKeys>");
+        for tag in tags {
+            write!(xml, "<keyword>{}</keyword>", escape_xml(tag)).unwrap();
+        }
+        xml.push_str("</searchKeys>");
+    }
+    xml.push_str("</dataIdInfo>");
+    write!(xml, "<distInfo><distributor><distorFormat><formatName>{}</formatName></distorFormat></distributor></distInfo>", format_name).unwrap();
+}
+
+/// Generates the toolbox metadata being stored as "<toolbox name>.pyt.xml" next to the Python toolbox.
+pub fn generate_toolbox_xml(toolbox_name: &str, definition: &PytToolboxDefinition) -> String {
+    let mut xml = String::new();
+
+    // The write macro never fails writing into a string
+    writeln!(xml, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    xml.push_str("<metadata xml:lang=\"en\"><Esri><ArcGISFormat>1.0</ArcGISFormat><SyncOnce>TRUE</SyncOnce></Esri>");
+    write!(xml, "<toolbox name=\"{}\" alias=\"{}\">", escape_xml(toolbox_name), escape_xml(&definition.alias)).unwrap();
+    write!(xml, "<arcToolboxHelpPath>{}</arcToolboxHelpPath><toolsets/></toolbox>", ARCTOOLBOX_HELP_PATH).unwrap();
+    write_identification(&mut xml, &definition.label, "", &[], "ArcToolbox Toolbox");
+    xml.push_str("</metadata>");
+    xml.push('\n');
+
+    xml
+}
+
+/// Generates the tool metadata being stored as "<toolbox name>.<class name>.pyt.xml" next to the Python toolbox.
+/// The metadata contains the summary, usage notes, parameter explanations and code samples of the tool.
+pub fn generate_tool_xml(toolbox_alias: &str, class_name: &str, gp_tool: &dyn api::GpTool) -> String {
+    let tool_metadata = gp_tool.metadata();
+    let mut xml = String::new();
+
+    // The write macro never fails writing into a string
+    writeln!(xml, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    xml.push_str("<metadata xml:lang=\"en\"><Esri><ArcGISFormat>1.0</ArcGISFormat><SyncOnce>TRUE</SyncOnce></Esri>");
+    write!(xml, "<tool name=\"{}\" displayname=\"{}\" toolboxalias=\"{}\" xmlns=\"\">",
+        escape_xml(class_name), escape_xml(gp_tool.label()), escape_xml(toolbox_alias)).unwrap();
+    write!(xml, "<arcToolboxHelpPath>{}</arcToolboxHelpPath>", ARCTOOLBOX_HELP_PATH).unwrap();
+
+    xml.push_str("<parameters>");
+    for parameter in gp_tool.parameters() {
+        write!(xml, "<param name=\"{name}\" displayname=\"{display_name}\" type=\"{parameter_type}\" direction=\"{direction}\" datatype=\"{data_type}\" expression=\"{name}\">",
+            name = escape_xml(parameter.name()),
+            display_name = escape_xml(parameter.display_name()),
+            parameter_type = parameter.parameter_type().as_str(),
+            direction = parameter.direction().as_str(),
+            data_type = parameter.data_type().as_str()).unwrap();
+        if let Some(explanation) = tool_metadata.parameter_explanation(parameter.name()) {
+            let explanation_html = as_html(&[explanation]);
+            write!(xml, "<dialogReference>{}</dialogReference>", explanation_html).unwrap();
+            write!(xml, "<pythonReference>{}</pythonReference>", explanation_html).unwrap();
+        }
+        xml.push_str("</param>");
+    }
+    xml.push_str("</parameters>");
+
+    let summary = if tool_metadata.summary.is_empty() { gp_tool.description() } else { &tool_metadata.summary };
+    if !summary.is_empty() {
+        write!(xml, "<summary>{}</summary>", as_html(&[summary])).unwrap();
+    }
+    if !tool_metadata.usage.is_empty() {
+        write!(xml, "<usage>{}</usage>", as_html_list(&tool_metadata.usage)).unwrap();
+    }
+    if !tool_metadata.code_samples.is_empty() {
+        xml.push_str("<scriptExamples>");
+        for code_sample in &tool_metadata.code_samples {
+            write!(xml, "<scriptExample><title>{}</title><para>{}</para><code>{}</code></scriptExample>",
+                escape_xml(&code_sample.title), escape_xml(&code_sample.description), escape_xml(&code_sample.code)).unwrap();
+        }
+        xml.push_str("</scriptExamples>");
+    }
+    xml.push_str("</tool>");
+
+    write_identification(&mut xml, gp_tool.label(), summary, &tool_metadata.tags, "ArcToolbox Tool");
+    xml.push_str("</metadata>");
+    xml.push('\n');
+
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn escape_help_text() {
+        assert_eq!("a &lt; b &amp;&amp; c", escape_xml("a < b && c"), "Escaping is wrong!");
+        assert_eq!("&lt;DIV STYLE=&quot;text-align:Left;&quot;&gt;&lt;P&gt;&lt;SPAN&gt;x &amp;amp; y&lt;/SPAN&gt;&lt;/P&gt;&lt;/DIV&gt;",
+            as_html(&["x & y"]), "HTML is wrong!");
+    }
+
+    struct BufferTool;
+
+    impl api::GpTool for BufferTool {
+
+        fn label(&self) -> &str {
+            "Buffer <features>"
+        }
+
+        fn description(&self) -> &str {
+            "Buffers features."
+        }
+
+        fn parameters(&self) -> Vec<api::GpParameter> {
+            vec![api::GpParameterBuilder::new()
+                .with_display_name("Distance")
+                .with_name("distance")
+                .with_data_type(api::DataType::GPDouble)
+                .build()]
+        }
+
+        fn execute(&self, _py: pyo3::Python, _parameters: api::PyParameterValues, _messages: api::PyGpMessages) -> pyo3::PyResult<()> {
+            Ok(())
+        }
+
+        fn metadata(&self) -> api::GpToolMetadata {
+            let mut parameter_explanations = std::collections::HashMap::new();
+            parameter_explanations.insert(String::from("distance"), String::from("The distance in meters."));
+
+            api::GpToolMetadata {
+                summary: String::new(),
+                usage: vec![String::from("Use a projected coordinate system.")],
+                tags: vec![String::from("buffer")],
+                parameter_explanations,
+                code_samples: vec![api::GpCodeSample {
+                    title: String::from("Buffer"),
+                    description: String::from("Buffers roads."),
+                    code: String::from("arcpy.rust.Buffer('roads', 100)")
+                }]
+            }
+        }
+    }
+
+    #[test]
+    fn generate_toolbox_and_tool_xml() {
+        let definition = PytToolboxDefinition {
+            label: String::from("Rust & Tools"),
+            alias: String::from("rust"),
+            module_name: String::from("buffer"),
+            package_name: None,
+            tools: Vec::new()
+        };
+        let toolbox_xml = generate_toolbox_xml("arcintegration", &definition);
+        assert!(toolbox_xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<metadata xml:lang=\"en\">"), "Header is wrong!");
+        assert!(toolbox_xml.contains("<toolbox name=\"arcintegration\" alias=\"rust\">"), "Toolbox is wrong!");
+        assert!(toolbox_xml.contains("<resTitle>Rust &amp; Tools</resTitle>"), "Title is wrong!");
+        assert!(toolbox_xml.contains("<formatName>ArcToolbox Toolbox</formatName>"), "Format is wrong!");
+
+        let tool_xml = generate_tool_xml("rust", "Buffer", &BufferTool);
+        assert!(tool_xml.contains("<tool name=\"Buffer\" displayname=\"Buffer &lt;features&gt;\" toolboxalias=\"rust\" xmlns=\"\">"), "Tool is wrong!");
+        assert!(tool_xml.contains("<param name=\"distance\" displayname=\"Distance\" type=\"Optional\" direction=\"Input\" datatype=\"GPDouble\" expression=\"distance\">"), "Parameter is wrong!");
+        assert!(tool_xml.contains("<dialogReference>&lt;DIV STYLE=&quot;text-align:Left;&quot;&gt;&lt;P&gt;&lt;SPAN&gt;The distance in meters."), "Parameter explanation is wrong!");
+        assert!(tool_xml.contains("<summary>&lt;DIV STYLE=&quot;text-align:Left;&quot;&gt;&lt;P&gt;&lt;SPAN&gt;Buffers features."), "Summary must fall back to the description!");
+        assert!(tool_xml.contains("&lt;LI&gt;&lt;P&gt;&lt;SPAN&gt;Use a projected coordinate system."), "Usage is wrong!");
+        assert!(tool_xml.contains("<scriptExample><title>Buffer</title><para>Buffers roads.</para><code>arcpy.rust.Buffer(&apos;roads&apos;, 100)</code></scriptExample>"), "Code sample is wrong!");
+        assert!(tool_xml.contains("<searchKeys><keyword>buffer</keyword></searchKeys>"), "Tags are wrong!");
+        assert!(tool_xml.contains("<formatName>ArcToolbox Tool</formatName>"), "Format is wrong!");
+    }
+}
@@ -0,0 +1,230 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Projects geometries into another spatial reference using arcpy.
+//! Inputs having different geographic coordinate systems need a geographic transformation,
+//! the available transformations are listed by arcpy.ListTransformations.
+
+use super::api::{FieldToken, GpSpatialReference, PyRow, PySearchCursor};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::PyNativeType;
+
+
+
+/// Represents a spatial reference living in arcpy.
+pub struct PySpatialReference<'py> {
+    pyspatial_reference: &'py PyAny
+}
+
+impl<'py> PySpatialReference<'py> {
+
+    /// Creates a new spatial reference using the well-known ID.
+    pub fn from_wkid(py: Python<'py>, wkid: i32) -> PyResult<PySpatialReference<'py>> {
+        let arcpy = PyModule::import(py, "arcpy")?;
+        let pyspatial_reference = arcpy.call1("SpatialReference", (wkid,))?;
+
+        Ok(PySpatialReference {
+            pyspatial_reference
+        })
+    }
+
+    /// Wraps an existing spatial reference like the spatialReference of Describe or of a geometry.
+    pub fn from_object(pyspatial_reference: &'py PyAny) -> PySpatialReference<'py> {
+        PySpatialReference {
+            pyspatial_reference
+        }
+    }
+
+    /// Returns the spatial reference of the geometry.
+    pub fn of_geometry(geometry: &'py PyAny) -> PyResult<PySpatialReference<'py>> {
+        let pyspatial_reference = geometry.getattr("spatialReference")?;
+        if pyspatial_reference.is_none() {
+            return Err(PyValueError::new_err("The geometry has no spatial reference!"));
+        }
+
+        Ok(PySpatialReference::from_object(pyspatial_reference))
+    }
+
+    /// Returns the well-known ID.
+    pub fn wkid(&self) -> PyResult<i32> {
+        self.pyspatial_reference.getattr("factoryCode")?.extract()
+    }
+
+    pub fn name(&self) -> PyResult<String> {
+        self.pyspatial_reference.getattr("name")?.extract()
+    }
+
+    /// Returns the underlying arcpy.SpatialReference.
+    pub fn as_object(&self) -> &'py PyAny {
+        self.pyspatial_reference
+    }
+
+    pub fn to_gp(&self) -> PyResult<GpSpatialReference> {
+        Ok(GpSpatialReference {
+            wkid: self.wkid()?
+        })
+    }
+}
+
+
+
+/// Lists the names of the geographic transformations between both spatial references.
+/// The list is empty if both spatial references use the same geographic coordinate system.
+pub fn list_transformations(from_spatial_reference: &PySpatialReference, to_spatial_reference: &PySpatialReference) -> PyResult<Vec<String>> {
+    let arcpy = PyModule::import(from_spatial_reference.as_object().py(), "arcpy")?;
+    let pytransformations = arcpy.call1("ListTransformations", (from_spatial_reference.as_object(), to_spatial_reference.as_object()))?;
+
+    pytransformations.extract()
+}
+
+
+
+/// Represents the projection of geometries into a target spatial reference.
+/// # Examples
+///
+/// ```ignore
+/// let source = projection::PySpatialReference::from_wkid(py, in_parameter.spatial_reference()?.wkid)?;
+/// let target = projection::PySpatialReference::from_wkid(py, 3857)?;
+/// let gp_projection = projection::GpProjection::new(&target)
+///     .with_default_transformation(&source)?;
+/// let search_cursor = gp_projection.project_cursor(PySearchCursor::new(&py, &in_features, field_names, "")?)?;
+/// ```
+pub struct GpProjection {
+    pytarget: PyObject,
+    transformation: Option<String>
+}
+
+impl GpProjection {
+
+    pub fn new(target: &PySpatialReference) -> GpProjection {
+        GpProjection {
+            pytarget: target.as_object().into(),
+            transformation: None
+        }
+    }
+
+    /// Uses the specified geographic transformation.
+    pub fn with_transformation(mut self, transformation: &str) -> GpProjection {
+        self.transformation = Some(transformation.to_string());
+        self
+    }
+
+    /// Uses the first geographic transformation being listed by arcpy for the source spatial reference.
+    /// No transformation is used if both spatial references use the same geographic coordinate system.
+    pub fn with_default_transformation(mut self, source: &PySpatialReference) -> PyResult<GpProjection> {
+        let target = PySpatialReference::from_object(self.pytarget.as_ref(source.as_object().py()));
+        self.transformation = list_transformations(source, &target)?.into_iter().next();
+
+        Ok(self)
+    }
+
+    /// Returns the name of the geographic transformation being used.
+    pub fn transformation(&self) -> Option<&str> {
+        self.transformation.as_deref()
+    }
+
+    /// Projects the geometry using Geometry.projectAs and returns the projected geometry.
+    pub fn project<'py>(&self, geometry: &'py PyAny) -> PyResult<&'py PyAny> {
+        let pytarget = self.pytarget.as_ref(geometry.py());
+        match &self.transformation {
+            Some(transformation) => geometry.call_method1("projectAs", (pytarget, transformation)),
+            None => geometry.call_method1("projectAs", (pytarget,))
+        }
+    }
+
+    /// Wraps the search cursor, so that the shape of every row is projected.
+    /// The cursor must request the shape using the SHAPE@ token.
+    pub fn project_cursor<'a>(self, search_cursor: PySearchCursor<'a>) -> PyResult<PyProjectedCursor<'a>> {
+        let shape_index = search_cursor.field_names().iter()
+            .position(|field_name| field_name.eq_ignore_ascii_case(FieldToken::Shape.as_str()))
+            .ok_or_else(|| PyValueError::new_err("The cursor must request the shape using SHAPE@!"))?;
+
+        Ok(PyProjectedCursor {
+            search_cursor,
+            shape_index,
+            gp_projection: self
+        })
+    }
+}
+
+
+
+/// Represents a search cursor returning projected shapes.
+pub struct PyProjectedCursor<'a> {
+    search_cursor: PySearchCursor<'a>,
+    shape_index: usize,
+    gp_projection: GpProjection
+}
+
+impl PyProjectedCursor<'_> {
+
+    /// Returns the next row having a projected shape.
+    /// Rows without a shape are returned as they are.
+    pub fn next(&self) -> PyResult<PyRow<'_>> {
+        let mut row = self.search_cursor.next()?;
+        if !row.is_null(self.shape_index)? {
+            let geometry = row.shape(self.shape_index)?;
+            let projected_geometry = self.gp_projection.project(geometry)?;
+            row.py_values[self.shape_index] = projected_geometry.into();
+        }
+
+        Ok(row)
+    }
+}
+
+
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn list_transformations_and_project_cursor() {
+        use crate::gp::api::{Point, ShapeType};
+        use crate::gp::testing::{MockArcpy, TestTable, TestValue};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let cities = TestTable::features(ShapeType::Point, 4326)
+            .with_row(vec![TestValue::Point(12.24555, 51.83864)])
+            .with_row(vec![TestValue::Null]);
+        arcpy.create_test_table("memory/projection_cities", &cities).unwrap();
+
+        let source = PySpatialReference::from_wkid(py, 4258).unwrap();
+        let target = PySpatialReference::from_wkid(py, 4326).unwrap();
+        assert_eq!(vec!["ETRS_1989_To_WGS_1984"], list_transformations(&source, &target).unwrap(), "Transformations are wrong!");
+        assert!(list_transformations(&target, &PySpatialReference::from_wkid(py, 3857).unwrap()).unwrap().is_empty(), "Transformations must be empty!");
+
+        let web_mercator = PySpatialReference::from_wkid(py, 3857).unwrap();
+        let gp_projection = GpProjection::new(&web_mercator)
+            .with_default_transformation(&target)
+            .unwrap();
+        assert_eq!(None, gp_projection.transformation(), "Transformation is wrong!");
+
+        let search_cursor = PySearchCursor::new(&py, "memory/projection_cities", vec![FieldToken::Shape.into()], "").unwrap();
+        let projected_cursor = gp_projection.project_cursor(search_cursor).unwrap();
+        let row = projected_cursor.next().unwrap();
+        let point: Point = row.value(0).unwrap();
+        assert!((point.x - 1363168.39).abs() < 0.01 && (point.y - 6771001.92).abs() < 0.01, "Projected point is wrong!");
+        let shape = row.shape(0).unwrap();
+        assert_eq!(3857, PySpatialReference::of_geometry(shape).unwrap().wkid().unwrap(), "Spatial reference is wrong!");
+        assert!(projected_cursor.next().unwrap().is_null(0).unwrap(), "Shape must be null!");
+    }
+}
@@ -0,0 +1,226 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{is_python_identifier, PyToolbox};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::fmt::Write;
+
+/// Represents a tool being exposed by a generated Python toolbox.
+/// The stable name of the tool is used as Python class name.
+pub struct PytToolDefinition {
+    pub name: String,
+    pub label: String,
+    pub description: String,
+    pub category: Option<String>
+}
+
+/// Represents a Python toolbox (.pyt) forwarding all tools to a native module.
+/// The native module must offer a function creating the toolbox using label and alias.
+pub struct PytToolboxDefinition {
+    pub label: String,
+    pub alias: String,
+    pub module_name: String,
+    pub package_name: Option<String>,
+    pub tools: Vec<PytToolDefinition>
+}
+
+impl PytToolboxDefinition {
+
+    /// Creates a new definition from the specified toolbox.
+    pub fn from_toolbox(module_name: &str, py_toolbox: &PyToolbox) -> PytToolboxDefinition {
+        let mut tools = Vec::with_capacity(py_toolbox.py_tools.len());
+        for py_tool in &py_toolbox.py_tools {
+            tools.push(PytToolDefinition {
+                name: py_tool.name.to_string(),
+                label: py_tool.label.to_string(),
+                description: py_tool.description.to_string(),
+                category: py_tool.category.clone()
+            });
+        }
+
+        PytToolboxDefinition {
+            label: py_toolbox.label.to_string(),
+            alias: py_toolbox.alias.to_string(),
+            module_name: module_name.to_string(),
+            package_name: None,
+            tools
+        }
+    }
+
+    /// Creates a new definition from a toolbox instance living in the Python environment.
+    /// The toolbox may be created by any native module being built using this crate.
+    pub fn from_pytoolbox(module_name: &str, py_toolbox: &PyAny) -> PyResult<PytToolboxDefinition> {
+        let tool_labels: Vec<String> = py_toolbox.call_method0("tools")?.extract()?;
+        let mut tools = Vec::with_capacity(tool_labels.len());
+        for (tool_index, tool_label) in tool_labels.into_iter().enumerate() {
+            let name: String = py_toolbox.call_method1("tool_name", (tool_index,))?.extract()?;
+            if !is_python_identifier(&name) || "Toolbox" == name {
+                return Err(PyValueError::new_err(format!("The tool name '{}' cannot be used as Python class name!", name)));
+            }
+            let description = py_toolbox.call_method1("tool_description", (tool_index,))?.extract()?;
+            let category = py_toolbox.call_method1("tool_category", (tool_index,))?.extract()?;
+            tools.push(PytToolDefinition {
+                name,
+                label: tool_label,
+                description,
+                category
+            });
+        }
+
+        Ok(PytToolboxDefinition {
+            label: py_toolbox.getattr("label")?.extract()?,
+            alias: py_toolbox.getattr("alias")?.extract()?,
+            module_name: module_name.to_string(),
+            package_name: None,
+            tools
+        })
+    }
+
+    /// Imports the native module from the specified package instead of a top-level module.
+    pub fn with_package_name(mut self, package_name: &str) -> PytToolboxDefinition {
+        self.package_name = Some(package_name.to_string());
+        self
+    }
+}
+
+
+
+/// Returns the specified text as a single quoted Python string literal.
+fn as_python_str(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('\'');
+    for c in text.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '\'' => literal.push_str("\\'"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            _ => literal.push(c)
+        }
+    }
+    literal.push('\'');
+
+    literal
+}
+
+/// Generates the content of a Python toolbox (.pyt) file.
+/// Every tool is exposed by its own Python class forwarding all methods to the native module.
+/// # Examples
+///
+/// ```ignore
+/// let definition = gp::pyt::PytToolboxDefinition::from_toolbox("copyfeatures", &py_toolbox)
+///     .with_package_name("arcrust");
+/// std::fs::write("arcintegration.pyt", gp::pyt::generate_pyt(&definition))?;
+/// ```
+pub fn generate_pyt(definition: &PytToolboxDefinition) -> String {
+    let mut pyt = String::new();
+    let class_names: Vec<&str> = definition.tools.iter().map(|tool| tool.name.as_str()).collect();
+
+    // The write macro never fails writing into a string
+    writeln!(pyt, "# -*- coding: utf-8 -*-").unwrap();
+    writeln!(pyt, "# This file was generated by arc-rs, do not edit it manually!").unwrap();
+    writeln!(pyt, "import arcpy").unwrap();
+    match &definition.package_name {
+        Some(package_name) => writeln!(pyt, "from {} import {} as rust_module", package_name, definition.module_name).unwrap(),
+        None => writeln!(pyt, "import {} as rust_module", definition.module_name).unwrap()
+    }
+    writeln!(pyt).unwrap();
+    writeln!(pyt, "rust_toolbox = rust_module.create_toolbox({}, {})", as_python_str(&definition.label), as_python_str(&definition.alias)).unwrap();
+    writeln!(pyt).unwrap();
+    writeln!(pyt).unwrap();
+    writeln!(pyt, "class Toolbox(object):").unwrap();
+    writeln!(pyt, "    def __init__(self):").unwrap();
+    writeln!(pyt, "        self.label = {}", as_python_str(&definition.label)).unwrap();
+    writeln!(pyt, "        self.alias = {}", as_python_str(&definition.alias)).unwrap();
+    writeln!(pyt, "        self.tools = [{}]", class_names.join(", ")).unwrap();
+
+    // The tools are bound by their stable name, so that reordering the tools in Rust does not bind another tool
+    for tool in &definition.tools {
+        writeln!(pyt).unwrap();
+        writeln!(pyt).unwrap();
+        writeln!(pyt, "class {}(object):", tool.name).unwrap();
+        writeln!(pyt, "    \"\"\"").unwrap();
+        writeln!(pyt, "    Wraps the Rust tool {}.", as_python_str(&tool.label)).unwrap();
+        writeln!(pyt, "    \"\"\"").unwrap();
+        writeln!(pyt).unwrap();
+        writeln!(pyt, "    def __init__(self):").unwrap();
+        writeln!(pyt, "        self._tool_index = rust_toolbox.tool_index_by_name({})", as_python_str(&tool.name)).unwrap();
+        writeln!(pyt, "        self.label = rust_toolbox.tool_label(self._tool_index)").unwrap();
+        writeln!(pyt, "        self.description = rust_toolbox.tool_description(self._tool_index)").unwrap();
+        if let Some(category) = &tool.category {
+            writeln!(pyt, "        self.category = {}", as_python_str(category)).unwrap();
+        }
+        writeln!(pyt).unwrap();
+        writeln!(pyt, "    def getParameterInfo(self):").unwrap();
+        writeln!(pyt, "        return rust_toolbox.tool_parameter_info(self._tool_index)").unwrap();
+        writeln!(pyt).unwrap();
+        writeln!(pyt, "    def isLicensed(self):").unwrap();
+        writeln!(pyt, "        return rust_toolbox.tool_is_licensed(self._tool_index)").unwrap();
+        writeln!(pyt).unwrap();
+        writeln!(pyt, "    def updateParameters(self, parameters):").unwrap();
+        writeln!(pyt, "        rust_toolbox.tool_update_parameters(self._tool_index, parameters)").unwrap();
+        writeln!(pyt).unwrap();
+        writeln!(pyt, "    def updateMessages(self, parameters):").unwrap();
+        writeln!(pyt, "        rust_toolbox.tool_update_messages(self._tool_index, parameters)").unwrap();
+        writeln!(pyt).unwrap();
+        writeln!(pyt, "    def execute(self, parameters, messages):").unwrap();
+        writeln!(pyt, "        rust_toolbox.tool_execute(self._tool_index, parameters, messages)").unwrap();
+    }
+
+    pyt
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::gp::api::as_class_name;
+
+    #[test]
+    fn class_names() {
+        assert_eq!("CopyFeatures", as_class_name("Copy features"), "Class name is wrong!");
+        assert_eq!("Tool3DBuffer", as_class_name("3D-buffer"), "Class name is wrong!");
+    }
+
+    #[test]
+    fn generate_tool_classes() {
+        let tool_definitions = vec!["Copy features", "Copy rows", "3D buffer"].into_iter().map(|label| PytToolDefinition {
+            name: as_class_name(label),
+            label: label.to_string(),
+            description: String::new(),
+            category: Some(String::from("Data Management"))
+        }).collect();
+        let definition = PytToolboxDefinition {
+            label: String::from("Rust's Tools"),
+            alias: String::from("rust_tools"),
+            module_name: String::from("copyfeatures"),
+            package_name: None,
+            tools: tool_definitions
+        };
+        let pyt = generate_pyt(&definition.with_package_name("arcrust"));
+
+        assert!(pyt.contains("from arcrust import copyfeatures as rust_module"), "Import is wrong!");
+        assert!(pyt.contains("rust_module.create_toolbox('Rust\\'s Tools', 'rust_tools')"), "Toolbox creation is wrong!");
+        assert!(pyt.contains("self.tools = [CopyFeatures, CopyRows, Tool3DBuffer]"), "Tool classes are wrong!");
+        assert!(pyt.contains("class Tool3DBuffer(object):"), "Tool class is missing!");
+        assert!(pyt.contains("rust_toolbox.tool_index_by_name('CopyFeatures')"), "Tool lookup is wrong!");
+        assert!(pyt.contains("rust_toolbox.tool_index_by_name('Tool3DBuffer')"), "Tool lookup is wrong!");
+        assert!(pyt.contains("self.category = 'Data Management'"), "Tool category is wrong!");
+    }
+}
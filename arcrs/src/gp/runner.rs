@@ -0,0 +1,479 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::api;
+use pyo3::prelude::*;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The Python code capturing the messages of a tool being executed outside the tool dialog.
+const MESSAGES_CODE: &str = r#"
+class Messages(object):
+
+    def __init__(self):
+        self.messages = []
+
+    def addMessage(self, message):
+        self.messages.append((0, str(message)))
+
+    def addWarningMessage(self, message):
+        self.messages.append((1, str(message)))
+
+    def addErrorMessage(self, message):
+        self.messages.append((2, str(message)))
+
+    def addIDMessage(self, message_type, message_id, add_argument1=None, add_argument2=None):
+        severity = {'ERROR': 2, 'WARNING': 1}.get(str(message_type).upper(), 0)
+        self.messages.append((severity, 'ID {}'.format(message_id)))
+
+    def addGPMessages(self):
+        pass
+"#;
+
+
+
+/// Represents a value of a parameter file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParameterFileValue {
+    Boolean(bool),
+    Integer(i64),
+    Double(f64),
+    Text(String)
+}
+
+impl ParameterFileValue {
+
+    /// Returns the numeric value.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            ParameterFileValue::Integer(value) => Some(*value as f64),
+            ParameterFileValue::Double(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Returns the value as text like arcpy does using valueAsText.
+    pub fn as_text(&self) -> String {
+        match self {
+            ParameterFileValue::Boolean(value) => value.to_string(),
+            ParameterFileValue::Integer(value) => value.to_string(),
+            ParameterFileValue::Double(value) => value.to_string(),
+            ParameterFileValue::Text(text) => text.to_string()
+        }
+    }
+
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            ParameterFileValue::Boolean(value) => value.to_object(py),
+            ParameterFileValue::Integer(value) => value.to_object(py),
+            ParameterFileValue::Double(value) => value.to_object(py),
+            ParameterFileValue::Text(text) => text.to_object(py)
+        }
+    }
+}
+
+
+
+/// Represents the parameter values of a tool being read from a JSON or TOML file.
+/// The file contains one flat object or table using the parameter names as keys.
+/// Null values are treated like missing values.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParameterFile {
+    pub values: Vec<(String, ParameterFileValue)>
+}
+
+impl ParameterFile {
+
+    /// Reads a parameter file using the file extension ".json" or ".toml".
+    pub fn load<P: AsRef<Path>>(file_path: P) -> Result<ParameterFile, String> {
+        let file_path = file_path.as_ref();
+        let content = std::fs::read_to_string(file_path)
+            .map_err(|err| format!("Parameter file '{}' cannot be read: {}", file_path.display(), err))?;
+        match file_path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => ParameterFile::from_json(&content),
+            Some("toml") => ParameterFile::from_toml(&content),
+            _ => Err(format!("Parameter file '{}' must be a JSON or TOML file!", file_path.display()))
+        }
+    }
+
+    /// Parses the parameter values from a JSON object.
+    pub fn from_json(content: &str) -> Result<ParameterFile, String> {
+        let json_value: serde_json::Value = serde_json::from_str(content)
+            .map_err(|err| format!("Parameter file is not valid JSON: {}", err))?;
+        let json_object = match json_value {
+            serde_json::Value::Object(json_object) => json_object,
+            _ => return Err(String::from("Parameter file must contain a JSON object!"))
+        };
+
+        let mut values = Vec::with_capacity(json_object.len());
+        for (name, json_value) in json_object {
+            let value = match json_value {
+                serde_json::Value::Null => continue,
+                serde_json::Value::Bool(value) => ParameterFileValue::Boolean(value),
+                serde_json::Value::Number(number) => match number.as_i64() {
+                    Some(value) => ParameterFileValue::Integer(value),
+                    None => ParameterFileValue::Double(number.as_f64().unwrap_or(f64::NAN))
+                },
+                serde_json::Value::String(text) => ParameterFileValue::Text(text),
+                _ => return Err(format!("Parameter '{}' must be a boolean, a number or a string!", name))
+            };
+            values.push((name, value));
+        }
+
+        Ok(ParameterFile {
+            values
+        })
+    }
+
+    /// Parses the parameter values from a TOML table.
+    pub fn from_toml(content: &str) -> Result<ParameterFile, String> {
+        let toml_table: toml::value::Table = toml::from_str(content)
+            .map_err(|err| format!("Parameter file is not valid TOML: {}", err))?;
+
+        let mut values = Vec::with_capacity(toml_table.len());
+        for (name, toml_value) in toml_table {
+            let value = match toml_value {
+                toml::Value::Boolean(value) => ParameterFileValue::Boolean(value),
+                toml::Value::Integer(value) => ParameterFileValue::Integer(value),
+                toml::Value::Float(value) => ParameterFileValue::Double(value),
+                toml::Value::String(text) => ParameterFileValue::Text(text),
+                _ => return Err(format!("Parameter '{}' must be a boolean, a number or a string!", name))
+            };
+            values.push((name, value));
+        }
+
+        Ok(ParameterFile {
+            values
+        })
+    }
+
+    /// Returns the value of the specified parameter.
+    pub fn value(&self, name: &str) -> Option<&ParameterFileValue> {
+        self.values.iter()
+            .find(|(value_name, _)| value_name == name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Validates the parameter values against the declared tool parameters.
+/// Returns a message for every invalid, missing or unknown parameter value.
+pub fn validate_parameter_file(gp_parameters: &[api::GpParameter], parameter_file: &ParameterFile) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (name, _) in &parameter_file.values {
+        if !gp_parameters.iter().any(|gp_parameter| gp_parameter.name() == name) {
+            errors.push(format!("Parameter '{}' does not exist!", name));
+        }
+    }
+
+    for gp_parameter in gp_parameters {
+        let name = gp_parameter.name();
+        let value = match (parameter_file.value(name), gp_parameter.parameter_type()) {
+            (Some(_), api::ParameterType::Derived) => {
+                errors.push(format!("Parameter '{}' is derived and must not have a value!", name));
+                continue;
+            },
+            (Some(value), _) => value,
+            (None, api::ParameterType::Required) if gp_parameter.default_value().is_none() => {
+                errors.push(format!("Parameter '{}' is required!", name));
+                continue;
+            },
+            (None, _) => continue
+        };
+
+        let is_valid_type = match (gp_parameter.data_type(), value) {
+            (api::DataType::GPLong, ParameterFileValue::Integer(_)) => true,
+            (api::DataType::GPDouble, ParameterFileValue::Integer(_)) |
+            (api::DataType::GPDouble, ParameterFileValue::Double(_)) => true,
+            (api::DataType::GPBoolean, ParameterFileValue::Boolean(_)) => true,
+            (api::DataType::GPLong, _) |
+            (api::DataType::GPDouble, _) |
+            (api::DataType::GPBoolean, _) => false,
+            (_, ParameterFileValue::Text(_)) => true,
+            _ => false
+        };
+        if !is_valid_type {
+            errors.push(format!("Parameter '{}' expects a value of data type {}!", name, gp_parameter.data_type().as_str()));
+            continue;
+        }
+
+        match gp_parameter.filter() {
+            Some(api::GpFilter::ValueList(values)) if !values.contains(&value.as_text()) => {
+                errors.push(format!("Parameter '{}' must be one of {}!", name, values.join(", ")));
+            },
+            Some(api::GpFilter::Range(min, max)) => {
+                if let Some(number) = value.as_f64() {
+                    if number < *min || *max < number {
+                        errors.push(format!("Parameter '{}' must be between {} and {}!", name, min, max));
+                    }
+                }
+            },
+            Some(api::GpFilter::File(extensions)) => {
+                let text = value.as_text();
+                let extension = Path::new(&text).extension().and_then(|extension| extension.to_str()).unwrap_or("");
+                if !extensions.iter().any(|known_extension| known_extension.eq_ignore_ascii_case(extension)) {
+                    errors.push(format!("Parameter '{}' must have one of the file extensions {}!", name, extensions.join(", ")));
+                }
+            },
+            _ => {}
+        }
+    }
+
+    errors
+}
+
+/// Creates a tool parameter from an arcpy parameter.
+fn gp_parameter_from_arcpy(py_parameter: &PyAny) -> PyResult<api::GpParameter> {
+    let data_type_as_text: String = py_parameter.getattr("datatype")?.extract()?;
+    let parameter_type_as_text: String = py_parameter.getattr("parameterType")?.extract()?;
+    let direction_as_text: String = py_parameter.getattr("direction")?.extract()?;
+    let mut gp_parameter_builder = api::GpParameterBuilder::new()
+        .with_display_name(&py_parameter.getattr("displayName")?.extract::<String>()?)
+        .with_name(&py_parameter.getattr("name")?.extract::<String>()?)
+        .with_data_type(api::DataType::from_str(&data_type_as_text)
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err(format!("Data type '{}' is not supported!", data_type_as_text)))?)
+        .with_parameter_type(api::ParameterType::from_str(&parameter_type_as_text)
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err(format!("Parameter type '{}' is not supported!", parameter_type_as_text)))?)
+        .with_direction(api::Direction::from_str(&direction_as_text)
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err(format!("Direction '{}' is not supported!", direction_as_text)))?);
+
+    let py_filter = py_parameter.getattr("filter")?;
+    let filter_type: Option<String> = py_filter.getattr("type")?.extract()?;
+    match filter_type.as_deref() {
+        Some("ValueList") => gp_parameter_builder = gp_parameter_builder.with_filter(api::GpFilter::ValueList(py_filter.getattr("list")?.extract()?)),
+        Some("File") => gp_parameter_builder = gp_parameter_builder.with_filter(api::GpFilter::File(py_filter.getattr("list")?.extract()?)),
+        Some("Range") => {
+            let range: Vec<f64> = py_filter.getattr("list")?.extract()?;
+            if 2 == range.len() {
+                gp_parameter_builder = gp_parameter_builder.with_filter(api::GpFilter::Range(range[0], range[1]));
+            }
+        },
+        _ => {}
+    }
+    if let Some(default_value) = py_parameter.getattr("valueAsText")?.extract::<Option<String>>()? {
+        gp_parameter_builder = gp_parameter_builder.with_default_value(&default_value);
+    }
+
+    Ok(gp_parameter_builder.build())
+}
+
+
+
+/// Represents a runner executing the tools of a toolbox outside the tool dialog.
+/// The runner only uses the Python interface of the toolbox, so that toolboxes of native modules are supported.
+pub struct ToolRunner<'py> {
+    py: Python<'py>,
+    py_toolbox: &'py PyAny
+}
+
+impl<'py> ToolRunner<'py> {
+
+    /// Creates a new runner using the specified toolbox.
+    pub fn new(py: Python<'py>, py_toolbox: &'py PyAny) -> ToolRunner<'py> {
+        ToolRunner {
+            py,
+            py_toolbox
+        }
+    }
+
+    /// Creates a new runner using the default toolbox of the specified native module.
+    pub fn from_module(py: Python<'py>, module_name: &str) -> PyResult<ToolRunner<'py>> {
+        let native_module = py.import(module_name)?;
+        let py_toolbox = native_module.call1("create_toolbox", (py.None(), py.None()))?;
+
+        Ok(ToolRunner::new(py, py_toolbox))
+    }
+
+    /// Returns the stable names of all tools.
+    pub fn tool_names(&self) -> PyResult<Vec<String>> {
+        self.py_toolbox.call_method0("tool_names")?.extract()
+    }
+
+    /// Returns the index of the tool having the specified stable name or label.
+    pub fn tool_index(&self, tool_name: &str) -> PyResult<usize> {
+        if self.py_toolbox.call_method1("has_tool_name", (tool_name,))?.extract()? {
+            return self.py_toolbox.call_method1("tool_index_by_name", (tool_name,))?.extract();
+        }
+
+        self.py_toolbox.call_method1("tool_index", (tool_name,))?.extract()
+    }
+
+    /// Returns the label of the specified tool.
+    pub fn tool_label(&self, tool_index: usize) -> PyResult<String> {
+        self.py_toolbox.call_method1("tool_label", (tool_index,))?.extract()
+    }
+
+    /// Returns the declared parameters of the specified tool.
+    pub fn parameters(&self, tool_index: usize) -> PyResult<Vec<api::GpParameter>> {
+        let py_parameters: Vec<&PyAny> = self.py_toolbox.call_method1("tool_parameter_info", (tool_index,))?.extract()?;
+        py_parameters.into_iter().map(gp_parameter_from_arcpy).collect()
+    }
+
+    /// Validates the parameter file against the declared parameters and the validation of the tool.
+    /// Returns a message for every invalid parameter value.
+    pub fn validate(&self, tool_index: usize, parameter_file: &ParameterFile) -> PyResult<Vec<String>> {
+        let errors = validate_parameter_file(&self.parameters(tool_index)?, parameter_file);
+        if !errors.is_empty() {
+            return Ok(errors);
+        }
+
+        let py_parameters = self.prepare_parameters(tool_index, parameter_file)?;
+        let mut errors = Vec::new();
+        for py_parameter in py_parameters {
+            let py_parameter = py_parameter.as_ref(self.py);
+            if py_parameter.call_method0("hasError")?.extract()? {
+                let name: String = py_parameter.getattr("name")?.extract()?;
+                let message: String = py_parameter.getattr("message")?.extract()?;
+                errors.push(format!("Parameter '{}' is not valid: {}", name, message));
+            }
+        }
+
+        Ok(errors)
+    }
+
+    /// Executes the specified tool using the parameter file.
+    /// The error of the tool execution is captured by the result.
+    pub fn execute(&self, tool_index: usize, parameter_file: &ParameterFile) -> PyResult<RunResult> {
+        let py_parameters = self.prepare_parameters(tool_index, parameter_file)?;
+        let messages_module = PyModule::from_code(self.py, MESSAGES_CODE, "arcrs_runner.py", "arcrs_runner")?;
+        let py_messages = messages_module.call0("Messages")?;
+        let error = self.py_toolbox.call_method1("tool_execute", (tool_index, py_parameters.iter().map(|py_parameter| py_parameter.clone_ref(self.py)).collect::<Vec<PyObject>>(), py_messages)).err();
+
+        let mut outputs = Vec::new();
+        for py_parameter in &py_parameters {
+            let py_parameter = py_parameter.as_ref(self.py);
+            let direction: String = py_parameter.getattr("direction")?.extract()?;
+            if "Output" == direction {
+                outputs.push((py_parameter.getattr("name")?.extract()?, py_parameter.getattr("valueAsText")?.extract()?));
+            }
+        }
+
+        Ok(RunResult {
+            messages: py_messages.getattr("messages")?.extract()?,
+            outputs,
+            error
+        })
+    }
+
+    /// Creates the arcpy parameters of the specified tool having the values of the parameter file.
+    /// The tool validation is invoked like the tool dialog does.
+    fn prepare_parameters(&self, tool_index: usize, parameter_file: &ParameterFile) -> PyResult<Vec<PyObject>> {
+        let py_parameters: Vec<PyObject> = self.py_toolbox.call_method1("tool_parameter_info", (tool_index,))?.extract()?;
+        for py_parameter in &py_parameters {
+            let py_parameter = py_parameter.as_ref(self.py);
+            let name: String = py_parameter.getattr("name")?.extract()?;
+            if let Some(value) = parameter_file.value(&name) {
+                py_parameter.setattr("value", value.to_object(self.py))?;
+            }
+        }
+
+        let clone_parameters = || py_parameters.iter().map(|py_parameter| py_parameter.clone_ref(self.py)).collect::<Vec<PyObject>>();
+        self.py_toolbox.call_method1("tool_update_parameters", (tool_index, clone_parameters()))?;
+        self.py_toolbox.call_method1("tool_update_messages", (tool_index, clone_parameters()))?;
+
+        Ok(py_parameters)
+    }
+}
+
+
+
+/// Represents the messages and outputs of a tool being executed by the runner.
+pub struct RunResult {
+    /// All messages as (severity, text) where 0 is an info, 1 is a warning and 2 is an error.
+    pub messages: Vec<(i32, String)>,
+    /// The name and the value as text of every output parameter.
+    pub outputs: Vec<(String, Option<String>)>,
+    /// The error being raised by the tool.
+    pub error: Option<PyErr>
+}
+
+impl RunResult {
+
+    /// Returns whether or not the tool succeeded without any error messages.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && !self.messages.iter().any(|(severity, _)| 2 == *severity)
+    }
+
+    /// Returns the value of the specified output parameter.
+    pub fn output(&self, name: &str) -> Option<&str> {
+        self.outputs.iter()
+            .find(|(output_name, _)| output_name == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+}
+
+/// Returns the display name of a message severity.
+pub fn severity_as_str(severity: i32) -> &'static str {
+    match severity {
+        2 => "ERROR",
+        1 => "WARNING",
+        _ => "INFO"
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn validate_parameter_files() {
+        let gp_parameters = vec![
+            api::GpParameterBuilder::new().with_name("in_features").with_data_type(api::DataType::GPFeatureLayer)
+                .with_parameter_type(api::ParameterType::Required).build(),
+            api::GpParameterBuilder::new().with_name("count").with_data_type(api::DataType::GPLong).build(),
+            api::GpParameterBuilder::new().with_name("mode").with_data_type(api::DataType::GPString).with_parameter_type(api::ParameterType::Required)
+                .with_filter(api::GpFilter::ValueList(vec![String::from("copy"), String::from("move")])).build(),
+            api::GpParameterBuilder::new().with_name("out_count").with_data_type(api::DataType::GPLong)
+                .with_parameter_type(api::ParameterType::Derived).with_direction(api::Direction::Output).build()
+        ];
+
+        let json_file = ParameterFile::from_json(r#"{"in_features": "memory/roads", "count": 5, "mode": "copy", "out_count": null}"#).unwrap();
+        let toml_file = ParameterFile::from_toml("in_features = \"memory/roads\"\ncount = 5\nmode = \"copy\"\n").unwrap();
+        assert_eq!(json_file.value("count"), toml_file.value("count"), "Parameter values are wrong!");
+        assert!(validate_parameter_file(&gp_parameters, &json_file).is_empty(), "JSON parameter file must be valid!");
+        assert!(validate_parameter_file(&gp_parameters, &toml_file).is_empty(), "TOML parameter file must be valid!");
+
+        let invalid_file = ParameterFile::from_json(r#"{"count": "five", "mode": "delete", "out_count": 1, "unknown": true}"#).unwrap();
+        let errors = validate_parameter_file(&gp_parameters, &invalid_file);
+        assert_eq!(errors, vec![
+            "Parameter 'unknown' does not exist!",
+            "Parameter 'in_features' is required!",
+            "Parameter 'count' expects a value of data type GPLong!",
+            "Parameter 'mode' must be one of copy, move!",
+            "Parameter 'out_count' is derived and must not have a value!"
+        ], "Validation errors are wrong!");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn parse_arcpy_parameters() {
+        use crate::gp::testing::MockArcpy;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let py_parameter = arcpy.module().call1("Parameter", ("in_features", "Input Features", "Input", "GPFeatureLayer", "Required")).unwrap();
+        assert_eq!("Feature Layer", py_parameter.getattr("datatype").unwrap().extract::<String>().unwrap(), "Display name is wrong!");
+        let gp_parameter = gp_parameter_from_arcpy(py_parameter).unwrap();
+        assert_eq!("GPFeatureLayer", gp_parameter.data_type().as_str(), "Data type is wrong!");
+
+        let py_parameter = arcpy.module().call1("Parameter", ("in_raster", "Input Raster", "Input", "Raster Layer", "Required")).unwrap();
+        assert!(gp_parameter_from_arcpy(py_parameter).is_err(), "Unknown data type must be rejected!");
+    }
+}
@@ -0,0 +1,645 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Offers a pure-Python stand-in for arcpy, so that geoprocessing tools can be tested without ArcGIS Pro.
+//! The stand-in is installed into the embedded Python interpreter as arcpy, arcpy.da and arcpy.management.
+//! All tables are held in memory and identified by their catalog path.
+//! The GpToolHarness executes a tool using declared parameter values and captures its messages and outputs.
+
+use super::api;
+use crate::data::{field_type_from_arcpy, split_catalog_path};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The source of the arcpy stand-in.
+const ARCPY_SOURCE: &str = include_str!("testing/arcpy.py");
+
+/// Represents the arcpy stand-in living in the embedded Python interpreter.
+/// All tests of a process share the same interpreter and the same tables.
+/// So that, every test should use its own catalog paths.
+pub struct MockArcpy<'a> {
+    py: Python<'a>,
+    arcpy: &'a PyModule
+}
+
+impl<'a> MockArcpy<'a> {
+
+    /// Installs the stand-in as arcpy, arcpy.da and arcpy.management.
+    /// An already installed stand-in is reused.
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let gil = Python::acquire_gil();
+    /// let arcpy = gp::testing::MockArcpy::install(gil.python())?;
+    /// arcpy.create_feature_class("memory/cities", gp::api::ShapeType::Point, 4326, &fields)?;
+    /// arcpy.insert_row("memory/cities", &["SHAPE@XY", "Name"], ((12.24555, 51.83864), "Dessau"))?;
+    /// ```
+    pub fn install(py: Python<'a>) -> PyResult<MockArcpy<'a>> {
+        let sys_modules = py.import("sys")?.getattr("modules")?;
+        let installed_module = sys_modules.call_method1("get", ("arcpy",))?;
+        if !installed_module.is_none() && installed_module.hasattr("__arcrs_mock__")? {
+            let arcpy = installed_module.downcast::<PyModule>()?;
+            return Ok(MockArcpy {
+                py,
+                arcpy
+            });
+        }
+
+        let arcpy = PyModule::from_code(py, ARCPY_SOURCE, "arcpy.py", "arcpy")?;
+        sys_modules.set_item("arcpy", arcpy)?;
+        sys_modules.set_item("arcpy.da", arcpy.getattr("da")?)?;
+        sys_modules.set_item("arcpy.management", arcpy.getattr("management")?)?;
+        if py.import("numpy").is_err() {
+            sys_modules.set_item("numpy", arcpy.getattr("numpy")?)?;
+        }
+
+        Ok(MockArcpy {
+            py,
+            arcpy
+        })
+    }
+
+    /// Returns the module of the stand-in.
+    pub fn module(&self) -> &'a PyModule {
+        self.arcpy
+    }
+
+    /// Drops all tables and messages.
+    pub fn reset(&self) -> PyResult<()> {
+        self.arcpy.call0("_reset")?;
+
+        Ok(())
+    }
+
+    /// Creates a new feature class having the OBJECTID, Shape and the specified fields.
+    pub fn create_feature_class(&self, catalog_path: &str, shape_type: api::ShapeType, wkid: i32, fields: &[api::GpField]) -> PyResult<()> {
+        let (out_path, out_name) = split_catalog_path(catalog_path);
+        self.arcpy.call1("CreateFeatureclass", (out_path, out_name, shape_type.as_str(), (), (), (), wkid))?;
+        self.add_fields(catalog_path, fields)
+    }
+
+    /// Creates a new table having the OBJECTID and the specified fields.
+    pub fn create_table(&self, catalog_path: &str, fields: &[api::GpField]) -> PyResult<()> {
+        let (out_path, out_name) = split_catalog_path(catalog_path);
+        self.arcpy.call1("CreateTable", (out_path, out_name))?;
+        self.add_fields(catalog_path, fields)
+    }
+
+    fn add_fields(&self, catalog_path: &str, fields: &[api::GpField]) -> PyResult<()> {
+        let field_description: Vec<Vec<&str>> = fields.iter().map(|field| vec![field.name.as_str(), field.field_type.as_gpstr()]).collect();
+        self.arcpy.call1("AddFields", (catalog_path, field_description))?;
+
+        Ok(())
+    }
+
+    /// Inserts a new row and returns its object ID.
+    /// The values must be a tuple or list matching the field names.
+    pub fn insert_row<T: ToPyObject>(&self, catalog_path: &str, field_names: &[&str], values: T) -> PyResult<i64> {
+        let insert_cursor = self.arcpy.getattr("da")?.call_method1("InsertCursor", (catalog_path, field_names.to_vec()))?;
+        let oid = insert_cursor.call_method1("insertRow", (values.to_object(self.py),))?.extract()?;
+
+        Ok(oid)
+    }
+
+    /// Reads all rows of the specified table.
+    /// Field names like "*", "OID@", "SHAPE@XY" and "SHAPE@JSON" are supported.
+    pub fn rows(&self, catalog_path: &str, field_names: &[&str]) -> PyResult<Vec<Vec<PyObject>>> {
+        let search_cursor = self.arcpy.getattr("da")?.call_method1("SearchCursor", (catalog_path, field_names.to_vec()))?;
+        let rows = PyList::new(self.py, search_cursor.iter()?.collect::<PyResult<Vec<&PyAny>>>()?);
+
+        rows.extract()
+    }
+
+    /// Returns the number of rows of the specified table.
+    pub fn row_count(&self, catalog_path: &str) -> PyResult<usize> {
+        let pyresult = self.arcpy.getattr("management")?.call_method1("GetCount", (catalog_path,))?;
+        let row_count_as_text: String = pyresult.call_method1("getOutput", (0,))?.extract()?;
+
+        row_count_as_text.parse()
+            .map_err(|_| PyValueError::new_err(format!("Row count '{}' is not valid!", row_count_as_text)))
+    }
+
+    /// Checks whether or not a table exists at the specified catalog path.
+    pub fn exists(&self, catalog_path: &str) -> PyResult<bool> {
+        self.arcpy.call1("Exists", (catalog_path,))?.extract()
+    }
+
+    /// Creates a new messages object capturing every message as (severity, text).
+    /// The severity is 0 for infos, 1 for warnings and 2 for errors.
+    pub fn create_messages(&self) -> PyResult<PyObject> {
+        Ok(self.arcpy.call0("Messages")?.to_object(self.py))
+    }
+
+    /// Returns all messages being captured by the specified messages object.
+    pub fn messages(&self, py_messages: &PyObject) -> PyResult<Vec<(i32, String)>> {
+        py_messages.getattr(self.py, "messages")?.extract(self.py)
+    }
+
+    /// Creates the specified table and inserts all of its rows.
+    pub fn create_test_table(&self, catalog_path: &str, test_table: &TestTable) -> PyResult<()> {
+        match test_table.shape_type {
+            Some(shape_type) => self.create_feature_class(catalog_path, shape_type, test_table.wkid, &test_table.fields)?,
+            None => self.create_table(catalog_path, &test_table.fields)?
+        }
+
+        let field_names = test_table.field_names();
+        let field_names: Vec<&str> = field_names.iter().map(|field_name| field_name.as_str()).collect();
+        for row in &test_table.rows {
+            if row.len() != field_names.len() {
+                return Err(PyValueError::new_err("Row does not match the fields!"));
+            }
+            let mut values = Vec::with_capacity(row.len());
+            for value in row {
+                values.push(value.to_object(self)?);
+            }
+            self.insert_row(catalog_path, &field_names, values)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the specified table including all of its rows.
+    pub fn read_test_table(&self, catalog_path: &str) -> PyResult<TestTable> {
+        let pydescribe = self.arcpy.call1("Describe", (catalog_path,))?;
+        let mut test_table = if pydescribe.hasattr("shapeType")? {
+            let shape_type_as_text: String = pydescribe.getattr("shapeType")?.extract()?;
+            let shape_type = api::ShapeType::from_str(&shape_type_as_text)
+                .map_err(|_| PyValueError::new_err(format!("Shape type '{}' is not supported!", shape_type_as_text)))?;
+            let wkid = pydescribe.getattr("spatialReference")?.getattr("factoryCode")?.extract()?;
+            TestTable::features(shape_type, wkid)
+        } else {
+            TestTable::table()
+        };
+
+        let pyfields: Vec<&PyAny> = pydescribe.getattr("fields")?.extract()?;
+        for pyfield in pyfields {
+            let field_name: String = pyfield.getattr("name")?.extract()?;
+            let field_type_as_text: String = pyfield.getattr("type")?.extract()?;
+            let field_type = match field_type_as_text.as_str() {
+                "OID" | "Geometry" => continue,
+                _ => field_type_from_arcpy(&field_type_as_text)
+                    .ok_or_else(|| PyValueError::new_err(format!("Field type '{}' is not supported!", field_type_as_text)))?
+            };
+            test_table = test_table.with_field(&field_name, field_type);
+        }
+
+        let field_names = test_table.field_names();
+        let field_names: Vec<&str> = field_names.iter().map(|field_name| field_name.as_str()).collect();
+        for row in self.rows(catalog_path, &field_names)? {
+            let mut values = Vec::with_capacity(row.len());
+            for value in row {
+                values.push(TestValue::from_object(value.as_ref(self.py))?);
+            }
+            test_table.rows.push(values);
+        }
+
+        Ok(test_table)
+    }
+}
+
+
+
+/// Represents a value being passed into a tool or being read from the outputs of a tool.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TestValue {
+    Null,
+    Text(String),
+    Integer(i64),
+    Double(f64),
+    Boolean(bool),
+    /// Represents a point geometry using x and y.
+    Point(f64, f64),
+    /// Represents any other geometry using Esri JSON.
+    Geometry(String),
+    /// Represents a table or feature class being created before the tool executes.
+    Table(TestTable)
+}
+
+impl TestValue {
+
+    /// Converts this value into a Python object using the arcpy stand-in.
+    fn to_object(&self, arcpy: &MockArcpy) -> PyResult<PyObject> {
+        let py = arcpy.py;
+        let py_value = match self {
+            TestValue::Null => py.None(),
+            TestValue::Text(text) => text.to_object(py),
+            TestValue::Integer(value) => value.to_object(py),
+            TestValue::Double(value) => value.to_object(py),
+            TestValue::Boolean(value) => value.to_object(py),
+            TestValue::Point(x, y) => {
+                let point = arcpy.arcpy.call1("Point", (*x, *y))?;
+                arcpy.arcpy.call1("PointGeometry", (point,))?.to_object(py)
+            },
+            TestValue::Geometry(json) => arcpy.arcpy.call1("AsShape", (json, true))?.to_object(py),
+            TestValue::Table(_) => return Err(PyValueError::new_err("Tables can only be passed as parameter values!"))
+        };
+
+        Ok(py_value)
+    }
+
+    /// Converts a Python object like a row value or a parameter value.
+    /// Values of other types like dates are not supported and return an error.
+    fn from_object(py_value: &PyAny) -> PyResult<TestValue> {
+        if py_value.is_none() {
+            return Ok(TestValue::Null);
+        }
+        if let Ok(value) = py_value.extract::<bool>() {
+            return Ok(TestValue::Boolean(value));
+        }
+        if let Ok(value) = py_value.extract::<i64>() {
+            return Ok(TestValue::Integer(value));
+        }
+        if let Ok(value) = py_value.extract::<f64>() {
+            return Ok(TestValue::Double(value));
+        }
+        if let Ok(text) = py_value.extract::<String>() {
+            return Ok(TestValue::Text(text));
+        }
+        if py_value.hasattr("JSON")? && py_value.hasattr("type")? {
+            let geometry_type: String = py_value.getattr("type")?.extract()?;
+            if "point" == geometry_type {
+                let first_point = py_value.getattr("firstPoint")?;
+                return Ok(TestValue::Point(first_point.getattr("X")?.extract()?, first_point.getattr("Y")?.extract()?));
+            }
+
+            return Ok(TestValue::Geometry(py_value.getattr("JSON")?.extract()?));
+        }
+
+        Err(PyValueError::new_err(format!("Value type '{}' is not supported!", py_value.get_type().name())))
+    }
+}
+
+impl From<&str> for TestValue {
+
+    fn from(text: &str) -> TestValue {
+        TestValue::Text(text.to_string())
+    }
+}
+
+impl From<String> for TestValue {
+
+    fn from(text: String) -> TestValue {
+        TestValue::Text(text)
+    }
+}
+
+impl From<i32> for TestValue {
+
+    fn from(value: i32) -> TestValue {
+        TestValue::Integer(value as i64)
+    }
+}
+
+impl From<i64> for TestValue {
+
+    fn from(value: i64) -> TestValue {
+        TestValue::Integer(value)
+    }
+}
+
+impl From<f64> for TestValue {
+
+    fn from(value: f64) -> TestValue {
+        TestValue::Double(value)
+    }
+}
+
+impl From<bool> for TestValue {
+
+    fn from(value: bool) -> TestValue {
+        TestValue::Boolean(value)
+    }
+}
+
+impl From<TestTable> for TestValue {
+
+    fn from(test_table: TestTable) -> TestValue {
+        TestValue::Table(test_table)
+    }
+}
+
+
+
+/// Represents a table or feature class using plain Rust values.
+/// The rows of a feature class start with the shape followed by the values of the fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestTable {
+    pub shape_type: Option<api::ShapeType>,
+    pub wkid: i32,
+    pub fields: Vec<api::GpField>,
+    pub rows: Vec<Vec<TestValue>>
+}
+
+impl TestTable {
+
+    /// Creates a new empty feature class.
+    pub fn features(shape_type: api::ShapeType, wkid: i32) -> TestTable {
+        TestTable {
+            shape_type: Some(shape_type),
+            wkid,
+            fields: Vec::new(),
+            rows: Vec::new()
+        }
+    }
+
+    /// Creates a new empty table without a shape.
+    pub fn table() -> TestTable {
+        TestTable {
+            shape_type: None,
+            wkid: 0,
+            fields: Vec::new(),
+            rows: Vec::new()
+        }
+    }
+
+    /// Adds a new field.
+    pub fn with_field(mut self, name: &str, field_type: api::FieldType) -> TestTable {
+        self.fields.push(api::GpField {
+            name: name.to_string(),
+            field_type
+        });
+        self
+    }
+
+    /// Adds a new row.
+    pub fn with_row(mut self, values: Vec<TestValue>) -> TestTable {
+        self.rows.push(values);
+        self
+    }
+
+    /// Returns the values of the specified field.
+    pub fn values(&self, field_name: &str) -> Option<Vec<&TestValue>> {
+        let field_index = self.field_names().iter().position(|known_name| known_name == field_name)?;
+
+        Some(self.rows.iter().filter_map(|row| row.get(field_index)).collect())
+    }
+
+    /// Returns the field names being used for reading and writing the rows.
+    fn field_names(&self) -> Vec<String> {
+        let mut field_names = Vec::with_capacity(self.fields.len() + 1);
+        if self.shape_type.is_some() {
+            field_names.push(api::FieldToken::Shape.into());
+        }
+        field_names.extend(self.fields.iter().map(|field| field.name.to_string()));
+
+        field_names
+    }
+}
+
+
+
+/// Every harness uses its own workspace, so that tests do not share tables.
+static NEXT_WORKSPACE_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// Represents a harness executing a geoprocessing tool using the arcpy stand-in.
+/// Tables being passed as parameter values are created in the workspace of the harness.
+/// Output feature classes not having a value are created in the workspace of the harness.
+/// # Examples
+///
+/// ```ignore
+/// let gil = Python::acquire_gil();
+/// let cities = TestTable::features(ShapeType::Point, 4326)
+///     .with_field("Name", FieldType::String)
+///     .with_row(vec![TestValue::Point(12.24555, 51.83864), "Dessau".into()]);
+/// let result = GpToolHarness::new(gil.python())?
+///     .with_value("in_features", cities)
+///     .execute(&CopyFeaturesTool::default())?;
+/// assert!(result.is_ok(), "Execution failed!");
+/// assert_eq!(1, result.output_table("out_features").unwrap().rows.len(), "Row count is wrong!");
+/// ```
+pub struct GpToolHarness<'a> {
+    arcpy: MockArcpy<'a>,
+    workspace: String,
+    values: Vec<(String, TestValue)>
+}
+
+impl<'a> GpToolHarness<'a> {
+
+    /// Creates a new harness and installs the arcpy stand-in.
+    pub fn new(py: Python<'a>) -> PyResult<GpToolHarness<'a>> {
+        let workspace_id = NEXT_WORKSPACE_ID.fetch_add(1, Ordering::SeqCst);
+
+        Ok(GpToolHarness {
+            arcpy: MockArcpy::install(py)?,
+            workspace: format!("memory/harness{}", workspace_id),
+            values: Vec::new()
+        })
+    }
+
+    /// Sets the value of the specified parameter.
+    pub fn with_value<V: Into<TestValue>>(mut self, name: &str, value: V) -> GpToolHarness<'a> {
+        self.values.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Returns the arcpy stand-in.
+    pub fn arcpy(&self) -> &MockArcpy<'a> {
+        &self.arcpy
+    }
+
+    /// Returns the workspace containing all tables of this harness.
+    pub fn workspace(&self) -> &str {
+        &self.workspace
+    }
+
+    /// Executes the specified tool and captures its messages and outputs.
+    /// An error is only returned if the parameters could not be prepared.
+    /// The error of the tool execution is captured by the result.
+    pub fn execute<T: api::GpTool + ?Sized>(&self, gp_tool: &T) -> PyResult<GpTestResult> {
+        let py = self.arcpy.py;
+        let gp_parameters = gp_tool.parameters();
+        let parameter_names: Vec<String> = gp_parameters.iter().map(|gp_parameter| gp_parameter.name().to_string()).collect();
+        let output_names: Vec<String> = gp_parameters.iter()
+            .filter(|gp_parameter| matches!(gp_parameter.direction(), api::Direction::Output))
+            .map(|gp_parameter| gp_parameter.name().to_string())
+            .collect();
+        let dataset_names: Vec<String> = gp_parameters.iter()
+            .filter(|gp_parameter| matches!(gp_parameter.data_type(), api::DataType::DEFeatureClass))
+            .map(|gp_parameter| gp_parameter.name().to_string())
+            .collect();
+        let py_parameters = super::create_arcpy_parameters(py, gp_parameters)?;
+
+        for (name, value) in &self.values {
+            let parameter_index = match parameter_names.iter().position(|parameter_name| parameter_name == name) {
+                Some(parameter_index) => parameter_index,
+                None => return Err(PyValueError::new_err(format!("Parameter '{}' does not exist!", name)))
+            };
+            let py_value = match value {
+                TestValue::Table(test_table) => {
+                    let catalog_path = format!("{}/{}", self.workspace, name);
+                    self.arcpy.create_test_table(&catalog_path, test_table)?;
+                    catalog_path.to_object(py)
+                },
+                _ => value.to_object(&self.arcpy)?
+            };
+            py_parameters[parameter_index].as_ref(py).setattr("value", py_value)?;
+        }
+        for output_name in &output_names {
+            let is_set = self.values.iter().any(|(name, _)| name == output_name);
+            if !is_set && dataset_names.contains(output_name) {
+                let parameter_index = parameter_names.iter().position(|parameter_name| parameter_name == output_name).unwrap();
+                py_parameters[parameter_index].as_ref(py).setattr("value", format!("{}/{}", self.workspace, output_name))?;
+            }
+        }
+
+        let py_messages = self.arcpy.create_messages()?;
+        let parameter_values = super::create_parameters_from_arcpy(&py, py_parameters.iter().map(|py_parameter| py_parameter.clone_ref(py)).collect())?;
+        let gp_messages = api::PyGpMessages {
+            py: &py,
+            py_messages: py_messages.clone_ref(py)
+        };
+        let error = gp_tool.execute(py, parameter_values, gp_messages).err();
+
+        let mut outputs = HashMap::with_capacity(output_names.len());
+        for output_name in output_names {
+            let parameter_index = parameter_names.iter().position(|parameter_name| *parameter_name == output_name).unwrap();
+            let py_parameter = py_parameters[parameter_index].as_ref(py);
+            let py_value = py_parameter.getattr("value")?;
+            let output_value = match py_parameter.getattr("valueAsText")?.extract::<Option<String>>()? {
+                Some(catalog_path) if self.arcpy.exists(&catalog_path)? => TestValue::Table(self.arcpy.read_test_table(&catalog_path)?),
+                _ => TestValue::from_object(py_value)?
+            };
+            outputs.insert(output_name, output_value);
+        }
+
+        Ok(GpTestResult {
+            messages: self.arcpy.messages(&py_messages)?,
+            outputs,
+            error
+        })
+    }
+}
+
+
+
+/// Represents the messages and outputs of a tool being executed by the harness.
+pub struct GpTestResult {
+    /// All messages as (severity, text) where 0 is an info, 1 is a warning and 2 is an error.
+    pub messages: Vec<(i32, String)>,
+    pub outputs: HashMap<String, TestValue>,
+    pub error: Option<PyErr>
+}
+
+impl GpTestResult {
+
+    /// Returns whether or not the tool executed without returning an error.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns all info messages.
+    pub fn infos(&self) -> Vec<&str> {
+        self.messages_by_severity(0)
+    }
+
+    /// Returns all warning messages.
+    pub fn warnings(&self) -> Vec<&str> {
+        self.messages_by_severity(1)
+    }
+
+    /// Returns all error messages.
+    pub fn errors(&self) -> Vec<&str> {
+        self.messages_by_severity(2)
+    }
+
+    fn messages_by_severity(&self, severity: i32) -> Vec<&str> {
+        self.messages.iter()
+            .filter(|(message_severity, _)| severity == *message_severity)
+            .map(|(_, message)| message.as_str())
+            .collect()
+    }
+
+    /// Returns the value of the specified output parameter.
+    pub fn output(&self, name: &str) -> Option<&TestValue> {
+        self.outputs.get(name)
+    }
+
+    /// Returns the table of the specified output parameter.
+    pub fn output_table(&self, name: &str) -> Option<&TestTable> {
+        match self.outputs.get(name) {
+            Some(TestValue::Table(test_table)) => Some(test_table),
+            _ => None
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn insert_and_read_rows() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let fields = vec![api::GpField {
+            name: String::from("Name"),
+            field_type: api::FieldType::String
+        }];
+        arcpy.create_feature_class("memory/testing_cities", api::ShapeType::Point, 4326, &fields).unwrap();
+        assert!(arcpy.exists("memory\\testing_cities").unwrap(), "Feature class must exist!");
+
+        let oid = arcpy.insert_row("memory/testing_cities", &["SHAPE@XY", "Name"], ((12.24555, 51.83864), "Dessau")).unwrap();
+        assert_eq!(1, oid, "Object ID is wrong!");
+        assert_eq!(1, arcpy.row_count("memory/testing_cities").unwrap(), "Row count is wrong!");
+
+        let rows = arcpy.rows("memory/testing_cities", &["OID@", "SHAPE@XY", "Name"]).unwrap();
+        let location: (f64, f64) = rows[0][1].extract(py).unwrap();
+        let name: String = rows[0][2].extract(py).unwrap();
+        assert_eq!((12.24555, 51.83864), location, "Location is wrong!");
+        assert_eq!("Dessau", name, "Name is wrong!");
+    }
+
+    #[test]
+    fn read_weighted_centroids() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        arcpy.create_test_table("memory/testing_parcels", &TestTable::features(api::ShapeType::Polygon, 3857)
+            .with_row(vec![TestValue::Geometry(String::from("{\"rings\": [[[0, 0], [0, 20], [10, 20], [10, 0], [0, 0]]]}"))])).unwrap();
+        arcpy.create_test_table("memory/testing_roads", &TestTable::features(api::ShapeType::Polyline, 3857)
+            .with_row(vec![TestValue::Geometry(String::from("{\"paths\": [[[0, 0], [10, 0], [10, 30]]]}"))])).unwrap();
+
+        let rows = arcpy.rows("memory/testing_parcels", &["SHAPE@XY"]).unwrap();
+        let centroid: (f64, f64) = rows[0][0].extract(py).unwrap();
+        assert_eq!((5.0, 10.0), centroid, "Polygon centroid is wrong!");
+        let rows = arcpy.rows("memory/testing_roads", &["SHAPE@XY"]).unwrap();
+        let centroid: (f64, f64) = rows[0][0].extract(py).unwrap();
+        assert_eq!((8.75, 11.25), centroid, "Polyline centroid is wrong!");
+    }
+
+    #[test]
+    fn convert_test_values() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let pyvalue = 42.to_object(py);
+        assert_eq!(TestValue::Integer(42), TestValue::from_object(pyvalue.as_ref(py)).unwrap(), "Integer is wrong!");
+        let pyvalue = "Dessau".to_object(py);
+        assert_eq!(TestValue::Text(String::from("Dessau")), TestValue::from_object(pyvalue.as_ref(py)).unwrap(), "Text is wrong!");
+        assert_eq!(TestValue::Null, TestValue::from_object(py.None().as_ref(py)).unwrap(), "Null is wrong!");
+
+        let pydate = PyModule::import(py, "datetime").unwrap().getattr("date").unwrap().call1((2021, 6, 1)).unwrap();
+        assert!(TestValue::from_object(pydate).is_err(), "Dates must not be converted into text!");
+    }
+}
@@ -0,0 +1,1420 @@
+# -*- coding: utf-8 -*-
+"""
+Represents a pure-Python stand-in for arcpy, arcpy.da and arcpy.management.
+All tables are held in memory and identified by their catalog path.
+Only the subset being used by arc-rs is offered, so that geoprocessing tools can be tested without ArcGIS Pro.
+"""
+import array
+import fnmatch
+import json
+import math
+import re
+import struct
+import types
+
+# Marks this module as stand-in, so that the installation can be reused
+__arcrs_mock__ = True
+
+# All tables by their normalized catalog path
+_tables = {}
+
+# All workspaces, feature datasets and relationship classes by their normalized catalog path
+_datasets = {}
+
+# All messages being added by AddMessage, AddWarning and AddError
+_messages = []
+
+# The field types being used by arcpy.management.AddField(s) and the field types being reported by Describe
+_FIELD_TYPES = {
+    'TEXT': 'String',
+    'STRING': 'String',
+    'FLOAT': 'Single',
+    'SINGLE': 'Single',
+    'DOUBLE': 'Double',
+    'SHORT': 'SmallInteger',
+    'SMALLINTEGER': 'SmallInteger',
+    'LONG': 'Integer',
+    'INTEGER': 'Integer',
+    'BIGINTEGER': 'BigInteger',
+    'DATE': 'Date',
+    'DATEONLY': 'DateOnly',
+    'TIMEONLY': 'TimeOnly',
+    'TIMESTAMPOFFSET': 'TimestampOffset',
+    'GUID': 'Guid',
+    'BLOB': 'Blob',
+    'RASTER': 'Raster',
+    'GLOBALID': 'GlobalID',
+    'OID': 'OID',
+    'GEOMETRY': 'Geometry'
+}
+
+# The display names being returned by Parameter.datatype like the English version of ArcGIS Pro does
+_DATA_TYPE_NAMES = {
+    'DEFeatureClass': 'Feature Class',
+    'GPFeatureLayer': 'Feature Layer',
+    'GPFeatureRecordSetLayer': 'Feature Set',
+    'GPString': 'String',
+    'GPDouble': 'Double',
+    'GPLong': 'Long',
+    'GPBoolean': 'Boolean'
+}
+
+
+
+def _normalize_path(catalog_path):
+    return str(catalog_path).replace('\\', '/').rstrip('/').lower()
+
+def _join_path(out_path, out_name):
+    return str(out_path).rstrip('/\\') + '/' + str(out_name)
+
+def _find_table(value):
+    catalog_path = _catalog_path(value)
+    table = _tables.get(_normalize_path(catalog_path))
+    if table is None:
+        raise OSError('"{}" does not exist'.format(catalog_path))
+
+    return table
+
+def _parent_path(catalog_path):
+    return _normalize_path(catalog_path).rpartition('/')[0]
+
+def _list_names(catalog_paths, wild_card, feature_dataset=None):
+    """
+    Returns the names of the catalog paths being direct children of the current workspace.
+    All catalog paths are listed if no workspace is set.
+    """
+    parent_path = env.workspace
+    if parent_path is not None and feature_dataset:
+        parent_path = _join_path(parent_path, feature_dataset)
+    names = []
+    for catalog_path in catalog_paths:
+        name = catalog_path.replace('\\', '/').rpartition('/')[2]
+        if parent_path is not None and _parent_path(catalog_path) != _normalize_path(parent_path):
+            continue
+        if wild_card and not fnmatch.fnmatch(name.lower(), wild_card.lower()):
+            continue
+        names.append(name)
+
+    return names
+
+def _catalog_path(value):
+    if isinstance(value, Parameter):
+        return _catalog_path(value.value)
+    if isinstance(value, (FeatureSet, RecordSet)):
+        return value.catalogPath
+    if isinstance(value, Result):
+        return value.getOutput(0)
+
+    return str(value)
+
+def _reset():
+    """
+    Drops all tables and messages and restores the default environment settings.
+    """
+    _tables.clear()
+    _datasets.clear()
+    del _messages[:]
+    env._reset()
+
+
+
+class _Environment(object):
+    """
+    Represents the geoprocessing environment settings being offered by arcpy.env.
+    """
+
+    def __init__(self):
+        self._reset()
+
+    def _reset(self):
+        self.workspace = None
+        self.scratchWorkspace = None
+        self.overwriteOutput = False
+        self.outputCoordinateSystem = None
+        self.parallelProcessingFactor = None
+
+env = _Environment()
+
+
+
+class Point(object):
+    """
+    Represents a simple point having x and y coordinates.
+    """
+
+    def __init__(self, X=0.0, Y=0.0, Z=None, M=None, ID=0):
+        self.X = X
+        self.Y = Y
+        self.Z = Z
+        self.M = M
+        self.ID = ID
+
+    def __eq__(self, other):
+        return isinstance(other, Point) and self.X == other.X and self.Y == other.Y
+
+    def __repr__(self):
+        return '{} {} NaN NaN'.format(self.X, self.Y)
+
+
+
+class Array(list):
+    """
+    Represents an array of points or parts.
+    """
+
+    def add(self, value):
+        self.append(value)
+
+    @property
+    def count(self):
+        return len(self)
+
+
+
+# The geographic coordinate systems of the supported projected coordinate systems
+_GEOGRAPHIC_WKIDS = {3857: 4326, 25832: 4258, 25833: 4258}
+
+# The geographic transformations between the supported geographic coordinate systems
+_TRANSFORMATIONS = {(4258, 4326): ['ETRS_1989_To_WGS_1984'], (4326, 4258): ['ETRS_1989_To_WGS_1984']}
+
+_EARTH_RADIUS = 6378137.0
+
+def _project_point(point, from_wkid, to_wkid):
+    if from_wkid == to_wkid:
+        return Point(point.X, point.Y)
+    if from_wkid in _GEOGRAPHIC_WKIDS or to_wkid in _GEOGRAPHIC_WKIDS:
+        if 3857 == from_wkid and _GEOGRAPHIC_WKIDS[from_wkid] == to_wkid:
+            return Point(math.degrees(point.X / _EARTH_RADIUS), math.degrees(2 * math.atan(math.exp(point.Y / _EARTH_RADIUS)) - math.pi / 2))
+        if 3857 == to_wkid and _GEOGRAPHIC_WKIDS[to_wkid] == from_wkid:
+            return Point(_EARTH_RADIUS * math.radians(point.X), _EARTH_RADIUS * math.log(math.tan(math.pi / 4 + math.radians(point.Y) / 2)))
+        raise ValueError('Projecting from {} to {} is not supported'.format(from_wkid, to_wkid))
+
+    # The geographic transformations of the stand-in keep the coordinates
+    return Point(point.X, point.Y)
+
+def ListTransformations(from_sr, to_sr, extent=None, vertical_change=False, first_only=False):
+    transformations = _TRANSFORMATIONS.get((from_sr.GCS.factoryCode, to_sr.GCS.factoryCode), [])
+
+    return transformations[:1] if first_only else list(transformations)
+
+class SpatialReference(object):
+    """
+    Represents a spatial reference identified by its well-known ID.
+    """
+
+    def __init__(self, item=0):
+        self.factoryCode = int(item) if item not in (None, '', ()) else 0
+        self.name = 'WKID {}'.format(self.factoryCode) if self.factoryCode else 'Unknown'
+        self.type = 'Projected' if self.factoryCode in _GEOGRAPHIC_WKIDS else 'Geographic'
+
+    @property
+    def GCS(self):
+        return SpatialReference(_GEOGRAPHIC_WKIDS.get(self.factoryCode, self.factoryCode))
+
+    def __eq__(self, other):
+        return isinstance(other, SpatialReference) and self.factoryCode == other.factoryCode
+
+
+
+def _as_parts(inputs):
+    if isinstance(inputs, Point):
+        return [[inputs]]
+    if isinstance(inputs, (tuple, list)) and inputs and isinstance(inputs[0], (int, float)):
+        return [[Point(inputs[0], inputs[1])]]
+    if inputs and all(isinstance(item, Point) for item in inputs):
+        return [list(inputs)]
+    if inputs and isinstance(inputs[0], (tuple, list)) and inputs[0] and isinstance(inputs[0][0], (int, float)):
+        return [[Point(item[0], item[1]) for item in inputs]]
+
+    return [[item if isinstance(item, Point) else Point(item[0], item[1]) for item in part] for part in (inputs or [])]
+
+def _segments(geometry):
+    for part in geometry._parts:
+        if 1 == len(part):
+            yield part[0], part[0]
+        for index in range(1, len(part)):
+            yield part[index - 1], part[index]
+
+def _segment_distance(point, start, end):
+    dx, dy = end.X - start.X, end.Y - start.Y
+    squared_length = dx * dx + dy * dy
+    ratio = 0.0 if 0 == squared_length else max(0.0, min(1.0, ((point.X - start.X) * dx + (point.Y - start.Y) * dy) / squared_length))
+
+    return math.hypot(point.X - start.X - ratio * dx, point.Y - start.Y - ratio * dy)
+
+def _inside(geometry, point):
+    if 'polygon' != geometry.type:
+        return False
+    inside = False
+    for start, end in _segments(geometry):
+        if (start.Y > point.Y) != (end.Y > point.Y) and point.X < (end.X - start.X) * (point.Y - start.Y) / (end.Y - start.Y) + start.X:
+            inside = not inside
+
+    return inside
+
+def _clip_ring(ring, clip_ring):
+    """
+    Clips a ring using a convex ring like an envelope.
+    """
+    orientation = sum(clip_ring[index - 1].X * point.Y - point.X * clip_ring[index - 1].Y for index, point in enumerate(clip_ring))
+    def is_left(start, end, point):
+        return 0 <= ((end.X - start.X) * (point.Y - start.Y) - (end.Y - start.Y) * (point.X - start.X)) * orientation
+    def intersection(start, end, clip_start, clip_end):
+        dx, dy = end.X - start.X, end.Y - start.Y
+        clip_dx, clip_dy = clip_end.X - clip_start.X, clip_end.Y - clip_start.Y
+        ratio = ((clip_start.X - start.X) * clip_dy - (clip_start.Y - start.Y) * clip_dx) / (dx * clip_dy - dy * clip_dx)
+        return Point(start.X + ratio * dx, start.Y + ratio * dy)
+
+    points = ring[:-1] if ring and ring[0] == ring[-1] else list(ring)
+    clip_points = clip_ring[:-1] if clip_ring[0] == clip_ring[-1] else list(clip_ring)
+    for clip_index, clip_start in enumerate(clip_points):
+        clip_end = clip_points[(clip_index + 1) % len(clip_points)]
+        clipped_points = []
+        for index, point in enumerate(points):
+            previous = points[index - 1]
+            if is_left(clip_start, clip_end, point):
+                if not is_left(clip_start, clip_end, previous):
+                    clipped_points.append(intersection(previous, point, clip_start, clip_end))
+                clipped_points.append(point)
+            elif is_left(clip_start, clip_end, previous):
+                clipped_points.append(intersection(previous, point, clip_start, clip_end))
+        points = clipped_points
+        if not points:
+            return []
+
+    return points + points[:1]
+
+class Geometry(object):
+    """
+    Represents a geometry having parts of points.
+    """
+
+    def __init__(self, geometry_type, inputs=None, spatial_reference=None):
+        self.type = geometry_type
+        self._parts = _as_parts(inputs)
+        self.spatialReference = spatial_reference
+
+    @property
+    def partCount(self):
+        return len(self._parts)
+
+    @property
+    def pointCount(self):
+        return sum(len(part) for part in self._parts)
+
+    @property
+    def isMultipart(self):
+        return 1 < len(self._parts)
+
+    @property
+    def firstPoint(self):
+        return self._parts[0][0] if self.pointCount else None
+
+    @property
+    def lastPoint(self):
+        return self._parts[-1][-1] if self.pointCount else None
+
+    @property
+    def centroid(self):
+        points = [point for part in self._parts for point in part]
+        if not points:
+            return None
+
+        if 'polygon' == self.type:
+            # The signed ring areas weight the ring centroids, so that holes are subtracted
+            area_sum = x_sum = y_sum = 0.0
+            for part in self._parts:
+                for index, point in enumerate(part):
+                    previous_point = part[index - 1]
+                    cross_product = previous_point.X * point.Y - point.X * previous_point.Y
+                    area_sum += cross_product
+                    x_sum += (previous_point.X + point.X) * cross_product
+                    y_sum += (previous_point.Y + point.Y) * cross_product
+            if 0.0 != area_sum:
+                return Point(x_sum / (3.0 * area_sum), y_sum / (3.0 * area_sum))
+        elif 'polyline' == self.type:
+            # The segment lengths weight the segment midpoints
+            length_sum = x_sum = y_sum = 0.0
+            for part in self._parts:
+                for previous_point, point in zip(part, part[1:]):
+                    segment_length = math.hypot(point.X - previous_point.X, point.Y - previous_point.Y)
+                    length_sum += segment_length
+                    x_sum += (previous_point.X + point.X) / 2.0 * segment_length
+                    y_sum += (previous_point.Y + point.Y) / 2.0 * segment_length
+            if 0.0 != length_sum:
+                return Point(x_sum / length_sum, y_sum / length_sum)
+
+        return Point(sum(point.X for point in points) / len(points), sum(point.Y for point in points) / len(points))
+
+    @property
+    def trueCentroid(self):
+        return self.centroid
+
+    @property
+    def area(self):
+        if 'polygon' != self.type:
+            return 0.0
+
+        return abs(sum(sum(part[index - 1].X * point.Y - point.X * part[index - 1].Y for index, point in enumerate(part)) for part in self._parts)) / 2.0
+
+    @property
+    def length(self):
+        if self.type in ('point', 'multipoint'):
+            return 0.0
+
+        return sum(sum(((point.X - part[index - 1].X) ** 2 + (point.Y - part[index - 1].Y) ** 2) ** 0.5 for index, point in enumerate(part) if 0 < index) for part in self._parts)
+
+    @property
+    def extent(self):
+        points = [point for part in self._parts for point in part]
+        if not points:
+            return None
+
+        return Extent(min(point.X for point in points), min(point.Y for point in points),
+            max(point.X for point in points), max(point.Y for point in points))
+
+    def getPart(self, index=None):
+        if index is None:
+            return Array([Array(part) for part in self._parts])
+
+        return Array(self._parts[index])
+
+    @property
+    def JSON(self):
+        geometry = {}
+        if 'point' == self.type:
+            if self.pointCount:
+                geometry['x'] = self.firstPoint.X
+                geometry['y'] = self.firstPoint.Y
+            else:
+                geometry['x'] = None
+                geometry['y'] = None
+        else:
+            coordinates = [[[point.X, point.Y] for point in part] for part in self._parts]
+            if 'multipoint' == self.type:
+                geometry['points'] = [coordinate for part in coordinates for coordinate in part]
+            elif 'polyline' == self.type:
+                geometry['paths'] = coordinates
+            else:
+                geometry['rings'] = coordinates
+        if self.spatialReference is not None:
+            geometry['spatialReference'] = {'wkid': self.spatialReference.factoryCode}
+
+        return json.dumps(geometry)
+
+    @property
+    def WKT(self):
+        def as_coordinates(points):
+            return ', '.join('{} {}'.format(point.X, point.Y) for point in points)
+
+        if 'point' == self.type:
+            return 'POINT ({} {})'.format(self.firstPoint.X, self.firstPoint.Y) if self.pointCount else 'POINT EMPTY'
+        if 'multipoint' == self.type:
+            return 'MULTIPOINT ({})'.format(', '.join('({} {})'.format(point.X, point.Y) for part in self._parts for point in part))
+        if 'polyline' == self.type:
+            return 'MULTILINESTRING ({})'.format(', '.join('({})'.format(as_coordinates(part)) for part in self._parts))
+
+        return 'MULTIPOLYGON (({}))'.format(', '.join('({})'.format(as_coordinates(part)) for part in self._parts))
+
+    @property
+    def WKB(self):
+        def as_points(points):
+            return struct.pack('<I', len(points)) + b''.join(struct.pack('<dd', point.X, point.Y) for point in points)
+
+        if 'point' == self.type:
+            point = self.firstPoint or Point(float('nan'), float('nan'))
+            return bytearray(struct.pack('<BIdd', 1, 1, point.X, point.Y))
+        if 'multipoint' == self.type:
+            points = [point for part in self._parts for point in part]
+            return bytearray(struct.pack('<BII', 1, 4, len(points)) + b''.join(struct.pack('<BIdd', 1, 1, point.X, point.Y) for point in points))
+        if 'polyline' == self.type:
+            return bytearray(struct.pack('<BII', 1, 5, len(self._parts)) + b''.join(struct.pack('<BI', 1, 2) + as_points(part) for part in self._parts))
+
+        return bytearray(struct.pack('<BIIBII', 1, 6, 1, 1, 3, len(self._parts)) + b''.join(as_points(part) for part in self._parts))
+
+    def buffer(self, distance):
+        if 'point' != self.type:
+            raise NotImplementedError('Only points can be buffered by this stand-in!')
+        center = self.firstPoint
+        ring = [Point(center.X + distance * math.cos(2 * math.pi * index / 64), center.Y + distance * math.sin(2 * math.pi * index / 64)) for index in range(64)]
+
+        return Polygon([ring + ring[:1]], self.spatialReference)
+
+    def distanceTo(self, other):
+        if any(_inside(self, point) for part in other._parts for point in part) or any(_inside(other, point) for part in self._parts for point in part):
+            return 0.0
+
+        return min(min(_segment_distance(start, other_start, other_end), _segment_distance(other_start, start, end))
+            for start, end in _segments(self) for other_start, other_end in _segments(other))
+
+    def contains(self, second_geometry, relation=None):
+        return all(_inside(self, point) for part in second_geometry._parts for point in part)
+
+    def within(self, second_geometry, relation=None):
+        return second_geometry.contains(self)
+
+    def disjoint(self, second_geometry):
+        return 0 < self.distanceTo(second_geometry)
+
+    def touches(self, second_geometry):
+        def strictly_inside(geometry, point):
+            return _inside(geometry, point) and all(0 < _segment_distance(point, start, end) for start, end in _segments(geometry))
+
+        return 0 == self.distanceTo(second_geometry) \
+            and not any(strictly_inside(second_geometry, point) for part in self._parts for point in part) \
+            and not any(strictly_inside(self, point) for part in second_geometry._parts for point in part)
+
+    def overlaps(self, second_geometry):
+        if 'polygon' != self.type or 'polygon' != second_geometry.type:
+            raise NotImplementedError('Only polygons can be overlapped by this stand-in!')
+
+        return 0 < self.intersect(second_geometry, 4).area and not self.contains(second_geometry) and not self.within(second_geometry)
+
+    def equals(self, second_geometry):
+        return self == second_geometry
+
+    def union(self, other):
+        if self.type != other.type:
+            raise ValueError('Geometries must have the same type!')
+        # The parts are not dissolved, so that only the union of disjoint geometries matches arcpy
+
+        return _GEOMETRY_TYPES[self.type.upper()](self._parts + other._parts, self.spatialReference)
+
+    def intersect(self, other, dimension):
+        if 'polygon' != self.type or 'polygon' != other.type or 4 != dimension:
+            raise NotImplementedError('Only polygons can be intersected by this stand-in!')
+        rings = [_clip_ring(ring, clip_ring) for ring in self._parts for clip_ring in other._parts]
+
+        return Polygon([ring for ring in rings if ring], self.spatialReference)
+
+    def clip(self, envelope):
+        corners = [Point(envelope.XMin, envelope.YMin), Point(envelope.XMin, envelope.YMax), Point(envelope.XMax, envelope.YMax), Point(envelope.XMax, envelope.YMin)]
+
+        return self.intersect(Polygon([corners + corners[:1]]), 4)
+
+    def projectAs(self, spatial_reference, transformation_name=None):
+        if not isinstance(spatial_reference, SpatialReference):
+            spatial_reference = SpatialReference(spatial_reference)
+        from_wkid = self.spatialReference.factoryCode if self.spatialReference is not None else 0
+        parts = [[_project_point(point, from_wkid or spatial_reference.factoryCode, spatial_reference.factoryCode) for point in part] for part in self._parts]
+
+        return _GEOMETRY_TYPES[self.type.upper()](parts, spatial_reference)
+
+    def __eq__(self, other):
+        return isinstance(other, Geometry) and self.type == other.type and self._parts == other._parts
+
+    def __repr__(self):
+        return '<{} object>'.format(type(self).__name__)
+
+class PointGeometry(Geometry):
+
+    def __init__(self, inputs=None, spatial_reference=None, has_z=False, has_m=False):
+        Geometry.__init__(self, 'point', inputs, spatial_reference)
+
+class Multipoint(Geometry):
+
+    def __init__(self, inputs=None, spatial_reference=None, has_z=False, has_m=False):
+        Geometry.__init__(self, 'multipoint', inputs, spatial_reference)
+
+class Polyline(Geometry):
+
+    def __init__(self, inputs=None, spatial_reference=None, has_z=False, has_m=False):
+        Geometry.__init__(self, 'polyline', inputs, spatial_reference)
+
+class Polygon(Geometry):
+
+    def __init__(self, inputs=None, spatial_reference=None, has_z=False, has_m=False):
+        Geometry.__init__(self, 'polygon', inputs, spatial_reference)
+
+def AsShape(geojson_struct, esri_json=False):
+    """
+    Creates a geometry from Esri JSON. GeoJSON is not supported by this stand-in.
+    """
+    if not esri_json:
+        raise NotImplementedError('Only Esri JSON is supported!')
+    geometry = json.loads(geojson_struct) if isinstance(geojson_struct, str) else geojson_struct
+    spatial_reference = SpatialReference(geometry['spatialReference'].get('wkid')) if 'spatialReference' in geometry else None
+    if 'x' in geometry:
+        return PointGeometry(Point(geometry['x'], geometry['y']), spatial_reference)
+    if 'points' in geometry:
+        return Multipoint(geometry['points'], spatial_reference)
+    if 'paths' in geometry:
+        return Polyline(geometry['paths'], spatial_reference)
+
+    return Polygon(geometry.get('rings', []), spatial_reference)
+
+def _read_wkb(wkb, offset):
+    byte_order, wkb_type = struct.unpack_from('<BI', wkb, offset)
+    prefix = '<' if 1 == byte_order else '>'
+    if '>' == prefix:
+        wkb_type = struct.unpack_from('>I', wkb, offset + 1)[0]
+    offset += 5
+
+    def read_points(offset):
+        point_count = struct.unpack_from(prefix + 'I', wkb, offset)[0]
+        offset += 4
+        points = [Point(*struct.unpack_from(prefix + 'dd', wkb, offset + 16 * index)) for index in range(point_count)]
+        return points, offset + 16 * point_count
+
+    if 1 == wkb_type:
+        return 'point', [[Point(*struct.unpack_from(prefix + 'dd', wkb, offset))]], offset + 16
+    if 2 == wkb_type:
+        points, offset = read_points(offset)
+        return 'polyline', [points], offset
+    if 3 == wkb_type:
+        ring_count = struct.unpack_from(prefix + 'I', wkb, offset)[0]
+        offset += 4
+        rings = []
+        for _ in range(ring_count):
+            ring, offset = read_points(offset)
+            rings.append(ring)
+        return 'polygon', rings, offset
+    if wkb_type in (4, 5, 6):
+        geometry_count = struct.unpack_from(prefix + 'I', wkb, offset)[0]
+        offset += 4
+        parts = []
+        for _ in range(geometry_count):
+            _, geometry_parts, offset = _read_wkb(wkb, offset)
+            parts.extend(geometry_parts)
+        if 4 == wkb_type:
+            parts = [[point for part in parts for point in part]]
+        return {4: 'multipoint', 5: 'polyline', 6: 'polygon'}[wkb_type], parts, offset
+
+    raise ValueError('WKB type {} is not supported'.format(wkb_type))
+
+def FromWKB(wkb, spatial_reference=None):
+    geometry_type, parts, _ = _read_wkb(bytes(wkb), 0)
+
+    return _GEOMETRY_TYPES[geometry_type.upper()](parts, spatial_reference)
+
+_GEOMETRY_TYPES = {
+    'POINT': PointGeometry,
+    'MULTIPOINT': Multipoint,
+    'POLYLINE': Polyline,
+    'POLYGON': Polygon
+}
+
+def _as_geometry(value, shape_type, spatial_reference):
+    if value is None:
+        return value
+    if isinstance(value, Geometry):
+        # Geometries without a spatial reference are stored using the spatial reference of the feature class
+        if value.spatialReference is None and spatial_reference is not None:
+            return _GEOMETRY_TYPES[value.type.upper()](value._parts, spatial_reference)
+        return value
+
+    return _GEOMETRY_TYPES[shape_type.upper()](value, spatial_reference)
+
+
+
+class Extent(object):
+
+    def __init__(self, XMin=None, YMin=None, XMax=None, YMax=None):
+        self.XMin = XMin
+        self.YMin = YMin
+        self.XMax = XMax
+        self.YMax = YMax
+
+
+
+class Field(object):
+    """
+    Represents a field of a table.
+    """
+
+    def __init__(self, name, field_type, alias_name=None, length=None):
+        self.name = name
+        self.aliasName = alias_name or name
+        self.baseName = name
+        self.type = field_type
+        self.length = length or (255 if 'String' == field_type else 8)
+        self.isNullable = field_type not in ('OID', 'Geometry')
+        self.editable = 'OID' != field_type
+        self.required = field_type in ('OID', 'Geometry')
+
+
+
+class _Table(object):
+    """
+    Represents an in-memory table or feature class.
+    """
+
+    def __init__(self, catalog_path, shape_type=None, spatial_reference=None):
+        self.catalog_path = catalog_path
+        self.shape_type = shape_type.capitalize() if shape_type else None
+        self.spatial_reference = spatial_reference or SpatialReference()
+        self.fields = [Field('OBJECTID', 'OID')]
+        if shape_type:
+            self.fields.append(Field('Shape', 'Geometry'))
+        self.rows = []
+        self.next_oid = 1
+
+    @property
+    def oid_field_name(self):
+        return 'OBJECTID'
+
+    @property
+    def shape_field_name(self):
+        return 'Shape' if self.shape_type else ''
+
+    def field_index(self, field_name):
+        for field_index, field in enumerate(self.fields):
+            if field.name.lower() == field_name.lower():
+                return field_index
+
+        raise RuntimeError('Cannot find field \'{}\''.format(field_name))
+
+    def add_field(self, field_name, field_type, alias_name=None, length=None):
+        field_type = _FIELD_TYPES.get(str(field_type).upper(), field_type)
+        self.fields.append(Field(field_name, field_type, alias_name, length))
+        for row in self.rows:
+            row.append(None)
+
+    def expand_field_names(self, field_names):
+        if isinstance(field_names, str):
+            field_names = [field_name.strip() for field_name in field_names.split(';')]
+        expanded_names = []
+        for field_name in field_names:
+            if '*' == field_name:
+                expanded_names.extend([field.name for field in self.fields])
+            else:
+                expanded_names.append(field_name)
+
+        return expanded_names
+
+    def read_value(self, row, field_name):
+        token = field_name.upper()
+        if 'OID@' == token:
+            return row[0]
+        if token.startswith('SHAPE@'):
+            shape = row[self.field_index(self.shape_field_name)]
+            if 'SHAPE@' == token or shape is None:
+                return shape
+            if token in ('SHAPE@XY', 'SHAPE@TRUECENTROID'):
+                centroid = shape.centroid
+                return (centroid.X, centroid.Y)
+            if 'SHAPE@AREA' == token:
+                return shape.area
+            if 'SHAPE@LENGTH' == token:
+                return shape.length
+            if 'SHAPE@X' == token:
+                return shape.centroid.X
+            if 'SHAPE@Y' == token:
+                return shape.centroid.Y
+            if 'SHAPE@JSON' == token:
+                return shape.JSON
+            if 'SHAPE@WKT' == token:
+                return shape.WKT
+            if 'SHAPE@WKB' == token:
+                return shape.WKB
+            raise RuntimeError('Unsupported token \'{}\''.format(field_name))
+
+        return row[self.field_index(field_name)]
+
+    def write_value(self, row, field_name, value):
+        token = field_name.upper()
+        if 'OID@' == token or self.oid_field_name.upper() == token:
+            return
+        if 'SHAPE@' == token or 'SHAPE@XY' == token or (self.shape_field_name and self.shape_field_name.upper() == token):
+            row[self.field_index(self.shape_field_name)] = _as_geometry(value, self.shape_type, self.spatial_reference)
+            return
+        if 'SHAPE@WKB' == token:
+            row[self.field_index(self.shape_field_name)] = None if value is None else FromWKB(value, self.spatial_reference)
+            return
+        if 'SHAPE@JSON' == token:
+            geometry = json.loads(value)
+            if 'x' in geometry:
+                shape = PointGeometry(Point(geometry['x'], geometry['y']))
+            else:
+                parts = geometry.get('rings') or geometry.get('paths') or [geometry.get('points', [])]
+                shape = _as_geometry(parts, self.shape_type, self.spatial_reference)
+            row[self.field_index(self.shape_field_name)] = shape
+            return
+
+        row[self.field_index(field_name)] = value
+
+    def matches(self, row, where_clause):
+        return _WhereClause(where_clause).matches(self, row)
+
+
+
+class _WhereClause(object):
+    """
+    Represents a where clause like "field = value" combining simple comparisons by AND.
+    Empty where clauses and "1=1" match every row.
+    """
+
+    _COMPARISON = re.compile(r'^\s*(\w+)\s*(=|<>|!=|<=|>=|<|>|IS NOT|IS)\s*(.+?)\s*$', re.IGNORECASE)
+
+    def __init__(self, where_clause):
+        self._comparisons = []
+        if not where_clause:
+            return
+
+        for condition in re.split(r'\s+AND\s+', where_clause, flags=re.IGNORECASE):
+            if re.match(r'^\s*1\s*=\s*1\s*$', condition):
+                continue
+            match = self._COMPARISON.match(condition)
+            if match is None:
+                raise RuntimeError('Unsupported where clause \'{}\''.format(where_clause))
+            self._comparisons.append((match.group(1), match.group(2).upper(), self._literal(match.group(3))))
+
+    @staticmethod
+    def _literal(text):
+        if 'NULL' == text.upper():
+            return None
+        if text.startswith('\'') and text.endswith('\''):
+            return text[1:-1].replace('\'\'', '\'')
+        try:
+            return int(text)
+        except ValueError:
+            return float(text)
+
+    def matches(self, table, row):
+        for field_name, operator, literal in self._comparisons:
+            value = row[table.field_index(field_name)]
+            if 'IS' == operator:
+                matches = value is None
+            elif 'IS NOT' == operator:
+                matches = value is not None
+            elif value is None:
+                matches = False
+            elif '=' == operator:
+                matches = value == literal
+            elif operator in ('<>', '!='):
+                matches = value != literal
+            elif '<' == operator:
+                matches = value < literal
+            elif '>' == operator:
+                matches = value > literal
+            elif '<=' == operator:
+                matches = value <= literal
+            else:
+                matches = value >= literal
+            if not matches:
+                return False
+
+        return True
+
+
+
+class Parameter(object):
+    """
+    Represents a geoprocessing parameter.
+    """
+
+    def __init__(self, name=None, displayName=None, direction='Input', datatype='GPString', parameterType='Required', enabled=True, category=None, symbology=None, multiValue=False):
+        self.name = name
+        self.displayName = displayName
+        self.direction = direction
+        self._datatype = datatype
+        self.parameterType = parameterType
+        self.enabled = enabled
+        self.category = category
+        self.symbology = symbology
+        self.multiValue = multiValue
+        self.filter = Filter()
+        self.value = None
+        self.altered = False
+        self.hasBeenValidated = False
+        self.message = ''
+        self._severity = 0
+
+    @property
+    def datatype(self):
+        return _DATA_TYPE_NAMES.get(self._datatype, self._datatype)
+
+    @datatype.setter
+    def datatype(self, datatype):
+        self._datatype = datatype
+
+    @property
+    def valueAsText(self):
+        if self.value is None:
+            return None
+        if isinstance(self.value, (FeatureSet, RecordSet)):
+            return self.value.catalogPath
+        if isinstance(self.value, bool):
+            return 'true' if self.value else 'false'
+
+        return str(self.value)
+
+    def setWarningMessage(self, message):
+        self.message = message
+        self._severity = 1
+
+    def setErrorMessage(self, message):
+        self.message = message
+        self._severity = 2
+
+    def clearMessage(self):
+        self.message = ''
+        self._severity = 0
+
+    def hasWarning(self):
+        return 1 == self._severity
+
+    def hasError(self):
+        return 2 == self._severity
+
+
+
+class Filter(object):
+
+    def __init__(self):
+        self.type = None
+        self.list = []
+
+
+
+class FeatureSet(object):
+    """
+    Represents a feature set referencing a table by its catalog path.
+    """
+
+    def __init__(self, table=None):
+        self.catalogPath = _catalog_path(table) if table is not None else None
+
+    def load(self, table):
+        self.catalogPath = _catalog_path(table)
+
+class RecordSet(FeatureSet):
+    pass
+
+
+
+class Result(list):
+    """
+    Represents the result of a geoprocessing tool.
+    """
+
+    @property
+    def outputCount(self):
+        return len(self)
+
+    @property
+    def status(self):
+        return 4
+
+    def getOutput(self, index):
+        return self[index]
+
+    def getMessages(self, severity=0):
+        return ''
+
+
+
+class Messages(object):
+    """
+    Represents the messages object being passed into the execute method of a Python toolbox.
+    Every message is captured as (severity, text) where 0 is an info, 1 is a warning and 2 is an error.
+    """
+
+    def __init__(self):
+        self.messages = []
+
+    def addMessage(self, message):
+        self.messages.append((0, str(message)))
+
+    def addWarningMessage(self, message):
+        self.messages.append((1, str(message)))
+
+    def addErrorMessage(self, message):
+        self.messages.append((2, str(message)))
+
+    def addIDMessage(self, message_type, message_id, add_argument1=None, add_argument2=None):
+        severity = {'ERROR': 2, 'WARNING': 1}.get(str(message_type).upper(), 0)
+        self.messages.append((severity, 'ID {}'.format(message_id)))
+
+    def addGPMessages(self):
+        self.messages.extend(_messages)
+
+def AddMessage(message):
+    _messages.append((0, str(message)))
+
+def AddWarning(message):
+    _messages.append((1, str(message)))
+
+def AddError(message):
+    _messages.append((2, str(message)))
+
+
+
+class _Describe(object):
+    """
+    Represents the properties being returned by arcpy.Describe.
+    """
+
+    def __init__(self, catalog_path, table=None):
+        self.catalogPath = catalog_path
+        self.path, _, self.baseName = catalog_path.replace('\\', '/').rpartition('/')
+        self.name = self.baseName
+        dataset = _datasets.get(_normalize_path(catalog_path))
+        if dataset is not None:
+            self.dataType = dataset['dataType']
+            if 'Workspace' == self.dataType:
+                self.workspaceType = 'RemoteDatabase' if catalog_path.lower().endswith('.sde') else 'LocalDatabase'
+            if 'RelationshipClass' == self.dataType:
+                self.originClassNames = [dataset['origin']]
+                self.destinationClassNames = [dataset['destination']]
+            self.children = [_Describe(table.catalog_path, table) for table in _tables.values() if _parent_path(table.catalog_path) == _normalize_path(catalog_path)] \
+                + [_Describe(child['catalogPath']) for child in _datasets.values() if _parent_path(child['catalogPath']) == _normalize_path(catalog_path)]
+        if table is not None:
+            self.dataType = 'FeatureClass' if table.shape_type else 'Table'
+            self.fields = list(table.fields)
+            self.OIDFieldName = table.oid_field_name
+            self.hasOID = True
+            global_id_field_names = [field.name for field in table.fields if 'GlobalID' == field.type]
+            self.hasGlobalID = 0 < len(global_id_field_names)
+            self.globalIDFieldName = global_id_field_names[0] if global_id_field_names else ''
+            self.editorTrackingEnabled = False
+            self.relationshipClassNames = []
+            if table.shape_type:
+                self.shapeFieldName = table.shape_field_name
+                self.shapeType = table.shape_type
+                self.hasZ = False
+                self.hasM = False
+                self.spatialReference = table.spatial_reference
+                shape_index = table.field_index(table.shape_field_name)
+                extents = [row[shape_index].extent for row in table.rows if row[shape_index] is not None and row[shape_index].extent is not None]
+                if extents:
+                    self.extent = Extent(min(extent.XMin for extent in extents), min(extent.YMin for extent in extents),
+                        max(extent.XMax for extent in extents), max(extent.YMax for extent in extents))
+                else:
+                    self.extent = Extent(math.nan, math.nan, math.nan, math.nan)
+
+def Describe(value, datatype=None):
+    catalog_path = _catalog_path(value)
+    table = _tables.get(_normalize_path(catalog_path))
+    if table is None and not isinstance(value, Parameter) and _normalize_path(catalog_path) not in _datasets:
+        raise OSError('"{}" does not exist'.format(catalog_path))
+
+    return _Describe(catalog_path, table)
+
+def Exists(dataset):
+    catalog_path = _normalize_path(_catalog_path(dataset))
+    return catalog_path in _tables or catalog_path in _datasets
+
+def ListFeatureClasses(wild_card=None, feature_type=None, feature_dataset=None):
+    return _list_names([table.catalog_path for table in _tables.values() if table.shape_type], wild_card, feature_dataset)
+
+def ListTables(wild_card=None, table_type=None):
+    return _list_names([table.catalog_path for table in _tables.values() if not table.shape_type], wild_card)
+
+def ListDatasets(wild_card=None, feature_type=None):
+    return _list_names([dataset['catalogPath'] for dataset in _datasets.values() if 'FeatureDataset' == dataset['dataType']], wild_card)
+
+def ListRasters(wild_card=None, raster_type=None):
+    return []
+
+def ListFields(dataset, wild_card=None, field_type=None):
+    return list(_find_table(dataset).fields)
+
+
+
+class _Cursor(object):
+
+    def __init__(self, in_table, field_names):
+        self._table = _find_table(in_table)
+        self.fields = tuple(self._table.expand_field_names(field_names))
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        return False
+
+class SearchCursor(_Cursor):
+    """
+    Represents a forward-only cursor reading the rows of a table.
+    """
+
+    def __init__(self, in_table, field_names, where_clause=None, spatial_reference=None, explode_to_points=False, sql_clause=(None, None)):
+        _Cursor.__init__(self, in_table, field_names)
+        self._rows = [row for row in self._table.rows if self._table.matches(row, where_clause)]
+        self._index = 0
+
+    def __iter__(self):
+        return self
+
+    def __next__(self):
+        return self.next()
+
+    def next(self):
+        if len(self._rows) <= self._index:
+            raise StopIteration
+        row = self._rows[self._index]
+        self._index += 1
+
+        return tuple(self._table.read_value(row, field_name) for field_name in self.fields)
+
+    def reset(self):
+        self._index = 0
+
+class InsertCursor(_Cursor):
+    """
+    Represents a cursor inserting rows into a table.
+    """
+
+    def __init__(self, in_table, field_names, datum_transformation=None):
+        _Cursor.__init__(self, in_table, field_names)
+
+    def insertRow(self, row):
+        if len(row) != len(self.fields):
+            raise TypeError('sequence size must match size of the row')
+        new_row = [None] * len(self._table.fields)
+        new_row[0] = self._table.next_oid
+        for field_name, value in zip(self.fields, row):
+            self._table.write_value(new_row, field_name, value)
+        self._table.rows.append(new_row)
+        self._table.next_oid += 1
+
+        return new_row[0]
+
+class UpdateCursor(SearchCursor):
+    """
+    Represents a cursor updating or deleting the rows of a table.
+    """
+
+    def updateRow(self, row):
+        current_row = self._rows[self._index - 1]
+        for field_name, value in zip(self.fields, row):
+            self._table.write_value(current_row, field_name, value)
+
+    def deleteRow(self):
+        self._table.rows.remove(self._rows[self._index - 1])
+
+class Editor(object):
+    """
+    Represents an edit session of a workspace.
+    The rows of all tables are restored if the edits are not saved or the operation is aborted.
+    """
+
+    def __init__(self, workspace):
+        self.workspace = workspace
+        self.isEditing = False
+        self._session_rows = None
+        self._operation_rows = None
+
+    def __enter__(self):
+        self.startEditing()
+        self.startOperation()
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        if exc_type is None:
+            self.stopOperation()
+            self.stopEditing(True)
+        else:
+            self.abortOperation()
+            self.stopEditing(False)
+        return False
+
+    def startEditing(self, with_undo=True, multiuser_mode=True):
+        if self.isEditing:
+            raise RuntimeError('start edit session')
+        self.isEditing = True
+        self._session_rows = _snapshot_rows()
+
+    def stopEditing(self, save_changes):
+        if not self.isEditing:
+            raise RuntimeError('stop edit session')
+        if not save_changes:
+            _restore_rows(self._session_rows)
+        self.isEditing = False
+        self._session_rows = None
+
+    def startOperation(self):
+        if not self.isEditing:
+            raise RuntimeError('start operation')
+        self._operation_rows = _snapshot_rows()
+
+    def stopOperation(self):
+        self._operation_rows = None
+
+    def abortOperation(self):
+        if self._operation_rows is not None:
+            _restore_rows(self._operation_rows)
+        self._operation_rows = None
+
+    def undoOperation(self):
+        self.abortOperation()
+
+def _snapshot_rows():
+    return dict((table_path, ([list(row) for row in table.rows], table.next_oid)) for table_path, table in _tables.items())
+
+def _restore_rows(snapshot):
+    for table_path, (rows, next_oid) in snapshot.items():
+        table = _tables.get(table_path)
+        if table is not None:
+            table.rows = rows
+            table.next_oid = next_oid
+
+class _DType(object):
+    """
+    Represents the data type of a structured array offering the field names and the field types.
+    """
+
+    def __init__(self, descr):
+        self.descr = [tuple(field) for field in descr]
+        self.names = tuple(field[0] for field in self.descr)
+
+    @property
+    def fields(self):
+        """
+        Returns the field type and the byte offset of every field like numpy.dtype.fields.
+        """
+        fields = {}
+        offset = 0
+        for field in self.descr:
+            fields[field[0]] = (field[1], offset)
+            item_count = field[2][0] if 2 < len(field) else 1
+            offset += item_count * _item_size(field[1])
+
+        return fields
+
+def _item_size(numpy_type):
+    numpy_type = str(numpy_type).lstrip('<>=|')
+    if numpy_type.startswith('U'):
+        return 4 * int(numpy_type[1:])
+
+    return int(numpy_type[1:])
+
+class _Column(list):
+    """
+    Represents the values of a field of a structured array.
+    """
+
+    _TYPE_CODES = {'float64': 'd', 'f8': 'd', '<f8': 'd', 'float32': 'f', 'f4': 'f', '<f4': 'f',
+        'int32': 'i', 'i4': 'i', '<i4': 'i', 'int64': 'q', 'i8': 'q', '<i8': 'q'}
+
+    # The type codes every type code can be cast into without losing values
+    _SAFE_CASTS = {'d': 'd', 'f': 'fd', 'i': 'iqd', 'q': 'qd'}
+
+    def __init__(self, values, dtype):
+        list.__init__(self, values)
+        self.dtype = dtype
+
+    def astype(self, dtype, casting='unsafe'):
+        type_code = self._TYPE_CODES[str(dtype)]
+        if 'safe' == casting and type_code not in self._SAFE_CASTS.get(self._TYPE_CODES.get(str(self.dtype)), ''):
+            raise TypeError("Cannot cast array data from dtype('{}') to dtype('{}') according to the rule 'safe'".format(self.dtype, dtype))
+        cast = float if type_code in ('d', 'f') else int
+        values = []
+        for value in self:
+            if isinstance(value, (tuple, list)):
+                values.extend(cast(coordinate) for coordinate in value)
+            else:
+                values.append(cast(value))
+
+        return array.array(type_code, values)
+
+    def tolist(self):
+        return list(self)
+
+class _StructuredArray(object):
+    """
+    Represents a NumPy structured array holding the rows as tuples.
+    """
+
+    def __init__(self, rows, dtype):
+        self.dtype = dtype if isinstance(dtype, _DType) else _DType(dtype)
+        self._rows = [tuple(row) for row in rows]
+
+    def __len__(self):
+        return len(self._rows)
+
+    def __iter__(self):
+        return iter(self._rows)
+
+    def __getitem__(self, key):
+        if isinstance(key, str):
+            field_index = self.dtype.names.index(key)
+            return _Column((row[field_index] for row in self._rows), self.dtype.fields[key][0])
+
+        return self._rows[key]
+
+    @property
+    def shape(self):
+        return (len(self._rows),)
+
+def _numpy_type(table, field_name, value):
+    token = field_name.upper()
+    if 'SHAPE@XY' == token:
+        return ('<f8', (2,))
+    if token in ('SHAPE@X', 'SHAPE@Y'):
+        return ('<f8',)
+    if 'OID@' == token:
+        return ('<i4',)
+    field_type = table.fields[table.field_index(field_name)].type
+    if field_type in ('OID', 'Integer', 'SmallInteger'):
+        return ('<i4',)
+    if field_type in ('Double', 'Single'):
+        return ('<f8',)
+
+    return ('<U{}'.format(max(1, len(str(value or '')))),)
+
+def FeatureClassToNumPyArray(in_table, field_names, where_clause=None, spatial_reference=None, explode_to_points=False, skip_nulls=False, null_value=None):
+    table = _find_table(in_table)
+    field_names = table.expand_field_names(field_names)
+    rows = []
+    for row in table.rows:
+        if not table.matches(row, where_clause):
+            continue
+        values = tuple(table.read_value(row, field_name) for field_name in field_names)
+        if None in values:
+            if skip_nulls:
+                continue
+            if null_value is None:
+                raise RuntimeError('cannot convert null values')
+            values = tuple(null_value if value is None else value for value in values)
+        rows.append(values)
+
+    descr = []
+    for field_index, field_name in enumerate(field_names):
+        longest_value = max((row[field_index] for row in rows), key=lambda value: len(str(value)), default=None)
+        descr.append((field_name,) + _numpy_type(table, field_name, longest_value))
+
+    return _StructuredArray(rows, descr)
+
+def TableToNumPyArray(in_table, field_names, where_clause=None, skip_nulls=False, null_value=None):
+    return FeatureClassToNumPyArray(in_table, field_names, where_clause, skip_nulls=skip_nulls, null_value=null_value)
+
+def _field_type_of(numpy_type):
+    numpy_type = str(numpy_type)
+    if 'f' in numpy_type:
+        return 'DOUBLE'
+    if 'i' in numpy_type:
+        return 'LONG'
+
+    return 'TEXT'
+
+def NumPyArrayToFeatureClass(in_array, out_table, shape_fields, spatial_reference=None):
+    if isinstance(shape_fields, str):
+        shape_fields = [shape_fields]
+    out_path, _, out_name = str(out_table).replace('\\', '/').rpartition('/')
+    management.CreateFeatureclass(out_path, out_name, 'POINT', spatial_reference=spatial_reference)
+    attribute_names = [name for name in in_array.dtype.names if name not in shape_fields]
+    for attribute_name in attribute_names:
+        management.AddField(out_table, attribute_name, _field_type_of(in_array.dtype.fields[attribute_name][0]))
+
+    shape_indices = [in_array.dtype.names.index(name) for name in shape_fields]
+    attribute_indices = [in_array.dtype.names.index(name) for name in attribute_names]
+    cursor = InsertCursor(out_table, ['SHAPE@XY'] + attribute_names)
+    for row in in_array:
+        xy = row[shape_indices[0]] if 1 == len(shape_indices) else (row[shape_indices[0]], row[shape_indices[1]])
+        cursor.insertRow([tuple(xy)] + [row[attribute_index] for attribute_index in attribute_indices])
+
+def ExtendTable(in_table, table_match_field, in_array, array_match_field, append_only=True):
+    table = _find_table(in_table)
+    match_index = in_array.dtype.names.index(array_match_field)
+    extended_names = [name for name in in_array.dtype.names if name != array_match_field]
+    known_names = [field.name.lower() for field in table.fields]
+    for extended_name in extended_names:
+        if extended_name.lower() in known_names:
+            if append_only:
+                raise RuntimeError('field {} already exists'.format(extended_name))
+            continue
+        table.add_field(extended_name, _field_type_of(in_array.dtype.fields[extended_name][0]))
+
+    array_rows = dict((array_row[match_index], array_row) for array_row in in_array)
+    for row in table.rows:
+        array_row = array_rows.get(table.read_value(row, table_match_field))
+        if array_row is None:
+            continue
+        for extended_name in extended_names:
+            table.write_value(row, extended_name, array_row[in_array.dtype.names.index(extended_name)])
+
+# Replaces numpy if it is not installed, so that the NumPy functions of arcpy.da can be used
+numpy = types.ModuleType('numpy')
+numpy.__arcrs_mock__ = True
+numpy.array = lambda rows, dtype=None: _StructuredArray(rows, dtype)
+
+da = types.ModuleType('arcpy.da')
+da.Editor = Editor
+da.FeatureClassToNumPyArray = FeatureClassToNumPyArray
+da.TableToNumPyArray = TableToNumPyArray
+da.NumPyArrayToFeatureClass = NumPyArrayToFeatureClass
+da.ExtendTable = ExtendTable
+da.SearchCursor = SearchCursor
+da.InsertCursor = InsertCursor
+da.UpdateCursor = UpdateCursor
+
+
+
+def CreateFeatureclass(out_path, out_name, geometry_type='POLYGON', template=None, has_m=None, has_z=None, spatial_reference=None, *args, **kwargs):
+    catalog_path = _join_path(out_path, out_name)
+    if Exists(catalog_path):
+        raise RuntimeError('ERROR 000258: Output {} already exists'.format(catalog_path))
+    if not isinstance(spatial_reference, SpatialReference):
+        spatial_reference = SpatialReference(spatial_reference)
+    _tables[_normalize_path(catalog_path)] = _Table(catalog_path, geometry_type or 'POLYGON', spatial_reference)
+
+    return Result([catalog_path])
+
+def CreateTable(out_path, out_name, template=None, *args, **kwargs):
+    catalog_path = _join_path(out_path, out_name)
+    if Exists(catalog_path):
+        raise RuntimeError('ERROR 000258: Output {} already exists'.format(catalog_path))
+    _tables[_normalize_path(catalog_path)] = _Table(catalog_path)
+
+    return Result([catalog_path])
+
+def AddField(in_table, field_name, field_type, field_precision=None, field_scale=None, field_length=None, field_alias=None, *args, **kwargs):
+    _find_table(in_table).add_field(field_name, field_type, field_alias, field_length)
+
+    return Result([_catalog_path(in_table)])
+
+def AddFields(in_table, field_description, template=None):
+    table = _find_table(in_table)
+    for field_values in field_description:
+        field_alias = field_values[2] if 2 < len(field_values) else None
+        field_length = field_values[3] if 3 < len(field_values) else None
+        table.add_field(field_values[0], field_values[1], field_alias, field_length)
+
+    return Result([_catalog_path(in_table)])
+
+def Delete(in_data, data_type=None):
+    catalog_path = _normalize_path(_catalog_path(in_data))
+    if catalog_path not in _tables and catalog_path not in _datasets:
+        raise RuntimeError('ERROR 000732: Input Data Element: Dataset {} does not exist'.format(_catalog_path(in_data)))
+    for table_path in [table_path for table_path in _tables if table_path == catalog_path or table_path.startswith(catalog_path + '/')]:
+        del _tables[table_path]
+    for dataset_path in [dataset_path for dataset_path in _datasets if dataset_path == catalog_path or dataset_path.startswith(catalog_path + '/')]:
+        del _datasets[dataset_path]
+
+    return Result([True])
+
+def _create_dataset(catalog_path, data_type, **properties):
+    if Exists(catalog_path):
+        raise RuntimeError('ERROR 000258: Output {} already exists'.format(catalog_path))
+    properties.update(catalogPath=catalog_path, dataType=data_type)
+    _datasets[_normalize_path(catalog_path)] = properties
+
+    return Result([catalog_path])
+
+def CreateFileGDB(out_folder_path, out_name, out_version=None):
+    if not str(out_name).lower().endswith('.gdb'):
+        out_name = str(out_name) + '.gdb'
+
+    return _create_dataset(_join_path(out_folder_path, out_name), 'Workspace')
+
+def CreateFeatureDataset(out_dataset_path, out_name, spatial_reference=None):
+    return _create_dataset(_join_path(out_dataset_path, out_name), 'FeatureDataset', spatialReference=spatial_reference)
+
+def CreateRelationshipClass(origin_table, destination_table, out_relationship_class, *args, **kwargs):
+    return _create_dataset(str(out_relationship_class), 'RelationshipClass', origin=_find_table(origin_table).catalog_path, destination=_find_table(destination_table).catalog_path)
+
+def GetCount(in_rows):
+    return Result([str(len(_find_table(in_rows).rows))])
+
+management = types.ModuleType('arcpy.management')
+management.CreateFeatureclass = CreateFeatureclass
+management.CreateTable = CreateTable
+management.AddField = AddField
+management.AddFields = AddFields
+management.Delete = Delete
+management.CreateFileGDB = CreateFileGDB
+management.CreateFeatureDataset = CreateFeatureDataset
+management.CreateRelationshipClass = CreateRelationshipClass
+management.GetCount = GetCount
@@ -66,7 +66,7 @@ impl PyGpResult<'_> {
 /// , the tool name like "CreateFeatureClass" and the arguments in.
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// let pyresult = gp::tools::execute_tool(py, "arcpy", "ListFeatureClasses", ())?;
 /// let results_as_text = pyresult.as_vecstr();
 /// for result_as_text in results_as_text {
@@ -0,0 +1,283 @@
+//   This file is part of arc-rs and enables the development of Geoprocessing Tools using Rust.
+//   Copyright (C) 2021 Esri Deutschland GmbH
+//   Contact: Jan Tschada (j.tschada@esri.de)
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU Lesser General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU Lesser General Public License for more details.
+//
+//   You should have received a copy of the GNU Lesser General Public License
+//   along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Lists and manages the datasets of workspaces like file geodatabases, enterprise geodatabases,
+//! folders and the memory workspace.
+//! The arcpy list functions only work on arcpy.env.workspace, so that every listing temporarily
+//! sets the environment and restores the previous value afterwards.
+
+use super::describe::DatasetDescription;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+const MEMORY_WORKSPACE: &str = "memory";
+
+
+
+/// Represents all known workspace types.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WorkspaceType {
+    FileGeodatabase,
+    /// An enterprise geodatabase being accessed by using a connection file (.sde).
+    EnterpriseGeodatabase,
+    Folder,
+    Memory
+}
+
+impl WorkspaceType {
+
+    /// Derives the workspace type from the path.
+    pub fn from_path(path: &str) -> WorkspaceType {
+        let path = path.trim_end_matches(['/', '\\']).to_lowercase();
+        if path.ends_with(".gdb") {
+            WorkspaceType::FileGeodatabase
+        } else if path.ends_with(".sde") {
+            WorkspaceType::EnterpriseGeodatabase
+        } else if MEMORY_WORKSPACE == path || "in_memory" == path {
+            WorkspaceType::Memory
+        } else {
+            WorkspaceType::Folder
+        }
+    }
+}
+
+
+
+/// Represents a workspace containing tables, feature classes, feature datasets, rasters and relationship classes.
+/// # Examples
+///
+/// ```ignore
+/// let workspace = workspace::Workspace::create_file_gdb(py, "c:/data", "results")?;
+/// let feature_dataset_path = workspace.create_feature_dataset(py, "transport", 4326)?;
+/// for feature_class_name in workspace.list_feature_classes(py, Some("road*"))? {
+///     messages.add_message(&workspace.join(&feature_class_name))?;
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Workspace {
+    path: String,
+    workspace_type: WorkspaceType
+}
+
+impl Workspace {
+
+    /// Creates a new workspace and derives its type from the path.
+    pub fn new(path: &str) -> Workspace {
+        Workspace {
+            path: path.trim_end_matches(['/', '\\']).to_string(),
+            workspace_type: WorkspaceType::from_path(path)
+        }
+    }
+
+    /// Creates the memory workspace.
+    pub fn memory() -> Workspace {
+        Workspace::new(MEMORY_WORKSPACE)
+    }
+
+    /// Creates a new file geodatabase in the specified folder.
+    /// The extension .gdb is appended if the name does not have it.
+    pub fn create_file_gdb(py: Python, out_folder_path: &str, out_name: &str) -> PyResult<Workspace> {
+        let arcpy_management = PyModule::import(py, "arcpy.management")?;
+        let pyresult = arcpy_management.call1("CreateFileGDB", (out_folder_path, out_name))?;
+        let catalog_path: String = pyresult.call_method1("getOutput", (0,))?.extract()?;
+
+        Ok(Workspace::new(&catalog_path))
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn workspace_type(&self) -> WorkspaceType {
+        self.workspace_type
+    }
+
+    /// Returns the catalog path of the dataset being part of this workspace.
+    pub fn join(&self, name: &str) -> String {
+        format!("{}/{}", self.path, name)
+    }
+
+    /// Lists the names of the feature classes not being part of a feature dataset.
+    /// The wild card supports * like "road*".
+    pub fn list_feature_classes(&self, py: Python, wild_card: Option<&str>) -> PyResult<Vec<String>> {
+        self.list(py, |arcpy| arcpy.call1("ListFeatureClasses", (wild_card,)))
+    }
+
+    /// Lists the names of the feature classes being part of the specified feature dataset.
+    pub fn list_feature_classes_in(&self, py: Python, feature_dataset: &str, wild_card: Option<&str>) -> PyResult<Vec<String>> {
+        self.list(py, |arcpy| arcpy.call1("ListFeatureClasses", (wild_card, "", feature_dataset)))
+    }
+
+    pub fn list_tables(&self, py: Python, wild_card: Option<&str>) -> PyResult<Vec<String>> {
+        self.list(py, |arcpy| arcpy.call1("ListTables", (wild_card,)))
+    }
+
+    pub fn list_feature_datasets(&self, py: Python, wild_card: Option<&str>) -> PyResult<Vec<String>> {
+        self.list(py, |arcpy| arcpy.call1("ListDatasets", (wild_card, "Feature")))
+    }
+
+    pub fn list_rasters(&self, py: Python, wild_card: Option<&str>) -> PyResult<Vec<String>> {
+        self.list(py, |arcpy| arcpy.call1("ListRasters", (wild_card,)))
+    }
+
+    /// Lists the names of the relationship classes using the children of arcpy.Describe.
+    /// arcpy does not offer a list function for relationship classes.
+    pub fn list_relationship_classes(&self, py: Python) -> PyResult<Vec<String>> {
+        let arcpy = PyModule::import(py, "arcpy")?;
+        let pydescribe = arcpy.call1("Describe", (&self.path,))?;
+        let pychildren: Vec<&PyAny> = pydescribe.getattr("children")?.extract()?;
+        let mut relationship_class_names = Vec::new();
+        for pychild in pychildren {
+            let data_type: String = pychild.getattr("dataType")?.extract()?;
+            if "RelationshipClass" == data_type {
+                relationship_class_names.push(pychild.getattr("name")?.extract()?);
+            }
+        }
+
+        Ok(relationship_class_names)
+    }
+
+    /// Checks whether or not this workspace exists.
+    pub fn exists(&self, py: Python) -> PyResult<bool> {
+        if WorkspaceType::Memory == self.workspace_type {
+            return Ok(true);
+        }
+
+        exists(py, &self.path)
+    }
+
+    /// Checks whether or not the dataset exists in this workspace.
+    pub fn contains(&self, py: Python, name: &str) -> PyResult<bool> {
+        exists(py, &self.join(name))
+    }
+
+    /// Describes the dataset being part of this workspace.
+    pub fn describe(&self, py: Python, name: &str) -> PyResult<DatasetDescription> {
+        let pycatalog_path = self.join(name).to_object(py);
+
+        DatasetDescription::new(py, pycatalog_path.as_ref(py))
+    }
+
+    /// Deletes the dataset being part of this workspace.
+    /// Deleting a feature dataset also deletes all of its feature classes.
+    pub fn delete(&self, py: Python, name: &str) -> PyResult<()> {
+        let catalog_path = self.join(name);
+        if !exists(py, &catalog_path)? {
+            return Err(PyValueError::new_err(format!("Dataset '{}' does not exist!", catalog_path)));
+        }
+
+        let arcpy_management = PyModule::import(py, "arcpy.management")?;
+        arcpy_management.call1("Delete", (catalog_path,))?;
+
+        Ok(())
+    }
+
+    /// Creates a new feature dataset and returns its catalog path.
+    /// Only geodatabases support feature datasets.
+    pub fn create_feature_dataset(&self, py: Python, out_name: &str, wkid: i32) -> PyResult<String> {
+        match self.workspace_type {
+            WorkspaceType::FileGeodatabase | WorkspaceType::EnterpriseGeodatabase => {
+                let arcpy = PyModule::import(py, "arcpy")?;
+                let pyspatial_reference = arcpy.call1("SpatialReference", (wkid,))?;
+                let pyresult = arcpy.getattr("management")?.call_method1("CreateFeatureDataset", (&self.path, out_name, pyspatial_reference))?;
+
+                pyresult.call_method1("getOutput", (0,))?.extract()
+            },
+            _ => Err(PyValueError::new_err("Feature datasets are only supported by geodatabases!"))
+        }
+    }
+
+    /// Calls the list function using this workspace as arcpy.env.workspace.
+    /// The previous workspace is restored even if the list function fails.
+    fn list<'py>(&self, py: Python<'py>, list_function: impl FnOnce(&'py PyModule) -> PyResult<&'py PyAny>) -> PyResult<Vec<String>> {
+        let arcpy = PyModule::import(py, "arcpy")?;
+        let pyenv = arcpy.getattr("env")?;
+        let pyprevious_workspace = pyenv.getattr("workspace")?;
+        pyenv.setattr("workspace", &self.path)?;
+        let names = list_function(arcpy).and_then(|pynames| {
+            if pynames.is_none() {
+                Ok(Vec::new())
+            } else {
+                pynames.extract()
+            }
+        });
+        pyenv.setattr("workspace", pyprevious_workspace)?;
+
+        names
+    }
+}
+
+fn exists(py: Python, catalog_path: &str) -> PyResult<bool> {
+    let arcpy = PyModule::import(py, "arcpy")?;
+
+    arcpy.call1("Exists", (catalog_path,))?.extract()
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn derive_workspace_types() {
+        assert_eq!(WorkspaceType::FileGeodatabase, Workspace::new("c:/data/results.gdb/").workspace_type(), "File geodatabase is wrong!");
+        assert_eq!(WorkspaceType::EnterpriseGeodatabase, Workspace::new("c:\\connections\\prod.sde").workspace_type(), "Enterprise geodatabase is wrong!");
+        assert_eq!(WorkspaceType::Memory, Workspace::memory().workspace_type(), "Memory workspace is wrong!");
+        assert_eq!(WorkspaceType::Folder, Workspace::new("c:/data").workspace_type(), "Folder is wrong!");
+        assert_eq!("c:/data/results.gdb/roads", Workspace::new("c:/data/results.gdb/").join("roads"), "Catalog path is wrong!");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn list_and_manage_datasets() {
+        use crate::gp::api::{FieldType, ShapeType};
+        use crate::gp::testing::{MockArcpy, TestTable};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        let workspace = Workspace::create_file_gdb(py, "c:/data", "results").unwrap();
+        assert_eq!("c:/data/results.gdb", workspace.path(), "Workspace path is wrong!");
+        assert!(workspace.exists(py).unwrap(), "Workspace must exist!");
+
+        let feature_dataset_path = workspace.create_feature_dataset(py, "transport", 4326).unwrap();
+        arcpy.create_test_table(&workspace.join("roads"), &TestTable::features(ShapeType::Polyline, 4326)).unwrap();
+        arcpy.create_test_table(&workspace.join("rivers"), &TestTable::features(ShapeType::Polyline, 4326)).unwrap();
+        arcpy.create_test_table(&format!("{}/railways", feature_dataset_path), &TestTable::features(ShapeType::Polyline, 4326)).unwrap();
+        arcpy.create_test_table(&workspace.join("owners"), &TestTable::table().with_field("Name", FieldType::String)).unwrap();
+        arcpy.create_test_table("memory/elsewhere", &TestTable::table()).unwrap();
+        arcpy.module().getattr("management").unwrap()
+            .call_method1("CreateRelationshipClass", (workspace.join("roads"), workspace.join("owners"), workspace.join("roads_owners"))).unwrap();
+
+        assert_eq!(vec!["roads", "rivers"], workspace.list_feature_classes(py, None).unwrap(), "Feature classes are wrong!");
+        assert_eq!(vec!["roads"], workspace.list_feature_classes(py, Some("ROAD*")).unwrap(), "Filtered feature classes are wrong!");
+        assert_eq!(vec!["railways"], workspace.list_feature_classes_in(py, "transport", None).unwrap(), "Feature classes of the feature dataset are wrong!");
+        assert_eq!(vec!["owners"], workspace.list_tables(py, None).unwrap(), "Tables are wrong!");
+        assert_eq!(vec!["transport"], workspace.list_feature_datasets(py, None).unwrap(), "Feature datasets are wrong!");
+        assert!(workspace.list_rasters(py, None).unwrap().is_empty(), "Rasters must be empty!");
+        assert_eq!(vec!["roads_owners"], workspace.list_relationship_classes(py).unwrap(), "Relationship classes are wrong!");
+        assert!(arcpy.module().getattr("env").unwrap().getattr("workspace").unwrap().is_none(), "Workspace environment must be restored!");
+        assert_eq!(Some(ShapeType::Polyline), workspace.describe(py, "roads").unwrap().shape_type, "Description is wrong!");
+
+        workspace.delete(py, "transport").unwrap();
+        assert!(!workspace.contains(py, "transport/railways").unwrap(), "Feature class of the feature dataset must be deleted!");
+        assert!(workspace.delete(py, "transport").is_err(), "Deleting a missing dataset must fail!");
+        assert!(Workspace::memory().create_feature_dataset(py, "transport", 4326).is_err(), "Memory workspace must not support feature datasets!");
+    }
+}
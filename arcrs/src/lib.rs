@@ -15,4 +15,11 @@
 //   You should have received a copy of the GNU Lesser General Public License
 //   along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-pub mod gp;
\ No newline at end of file
+pub mod data;
+pub mod gp;
+
+/// The version of arc-rs being used by a native module.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Generated code refers to pyo3 through this crate.
+pub use pyo3;
\ No newline at end of file
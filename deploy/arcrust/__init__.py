@@ -1,11 +1,24 @@
+import importlib
 import os
 import sys
+import warnings
+
+# The directory of this package is the default module directory
+_PACKAGE_DIR = os.path.dirname(__file__)
+
+# Environment variable listing additional module directories separated by os.pathsep
+MODULE_PATH_VARIABLE = 'ARCRUST_MODULE_PATH'
+
+# Environment variable listing additional module names separated by comma
+MODULES_VARIABLE = 'ARCRUST_MODULES'
+
+# Entry point group for Python packages shipping native tool modules
+ENTRY_POINT_GROUP = 'arcrust.toolboxes'
 
 # Just for integration testing
-sys.path.append(os.path.dirname(__file__))
+sys.path.append(_PACKAGE_DIR)
+
 
-# Import the native Python modules
-import copyfeatures
 
 class Tool(object):
     """
@@ -16,6 +29,20 @@ class Tool(object):
         self._toolbox = toolbox
         self._tool_index = tool_index
 
+    @property
+    def name(self):
+        """
+        The stable name of this tool.
+        """
+        return self._toolbox.tool_name(self._tool_index)
+
+    @property
+    def category(self):
+        """
+        The category of this tool or None.
+        """
+        return self._toolbox.tool_category(self._tool_index)
+
     @property
     def label(self):
         """
@@ -44,18 +71,110 @@ class Tool(object):
 
 
 
+def _is_native_module(file_name):
+    """
+    Returns whether or not the specified file is a native Python module.
+    """
+    return file_name.endswith('.pyd') or file_name.endswith('.so')
+
+def _module_name(file_name):
+    """
+    Returns the module name of a native module file like 'copyfeatures.cp37-win_amd64.pyd'.
+    """
+    module_name = file_name.split('.')[0]
+    if module_name.startswith('lib') and file_name.endswith('.so'):
+        return module_name[3:]
+
+    return module_name
+
+def _entry_point_modules():
+    """
+    Returns the module names being registered by installed Python packages.
+    """
+    try:
+        from importlib import metadata
+        entry_points = metadata.entry_points()
+        if hasattr(entry_points, 'select'):
+            group_entry_points = entry_points.select(group=ENTRY_POINT_GROUP)
+        else:
+            group_entry_points = entry_points.get(ENTRY_POINT_GROUP, [])
+        return [entry_point.value for entry_point in group_entry_points]
+    except ImportError:
+        try:
+            import pkg_resources
+            return [entry_point.module_name for entry_point in pkg_resources.iter_entry_points(ENTRY_POINT_GROUP)]
+        except ImportError:
+            return []
+
+
+
 class ToolRegistry(object):
     """
     Manages all available custom Rust tools.
+    Every native module being declared by arcrs::toolbox_module! offers an arcrs_manifest function.
+    The modules are discovered in the module directories, by module names and by the 'arcrust.toolboxes' entry points.
     """
 
-    def __init__(self):
+    def __init__(self, module_dirs=None, module_names=None):
         self._tool_labels = []
+        self._tool_names = []
         self._tools = []
-        copy_toolbox = copyfeatures.create_toolbox('Rust Copy Tools', 'rust_copy_tools')
-        self._tool_labels.extend(copy_toolbox.tools())
-        self._tools.extend([Tool(copy_toolbox, tool_index) for tool_index in range(0, len(self._tool_labels))])
-    
+        self._modules = {}
+        self._load_errors = {}
+
+        if module_dirs is None:
+            module_dirs = [_PACKAGE_DIR]
+            module_dirs.extend([module_dir for module_dir in os.environ.get(MODULE_PATH_VARIABLE, '').split(os.pathsep) if module_dir])
+        if module_names is None:
+            module_names = [module_name.strip() for module_name in os.environ.get(MODULES_VARIABLE, '').split(',') if module_name.strip()]
+            module_names.extend(_entry_point_modules())
+
+        for module_dir in module_dirs:
+            self.load_directory(module_dir)
+        for module_name in module_names:
+            self.load_module(module_name)
+
+    def load_directory(self, module_dir):
+        """
+        Loads all native tool modules from the specified directory.
+        Modules failing to load are reported as warnings and listed by list_load_errors.
+        """
+        if not os.path.isdir(module_dir):
+            return
+
+        if module_dir not in sys.path:
+            sys.path.append(module_dir)
+        for file_name in sorted(os.listdir(module_dir)):
+            if _is_native_module(file_name):
+                module_name = _module_name(file_name)
+                try:
+                    self.load_module(module_name, ignore_unknown=True)
+                except Exception as error:
+                    # A broken module must not hide the tools of all other modules
+                    self._load_errors[module_name] = error
+                    warnings.warn('Module {} could not be loaded: {}'.format(module_name, error))
+
+    def load_module(self, module_name, ignore_unknown=False):
+        """
+        Loads the toolbox of the specified native tool module.
+        Modules not offering an arcrs_manifest function are ignored or raise a ValueError.
+        """
+        if module_name in self._modules:
+            return
+
+        module = importlib.import_module(module_name)
+        if not hasattr(module, 'arcrs_manifest'):
+            if ignore_unknown:
+                return
+            raise ValueError('Module {} is not a Rust tool module!'.format(module_name))
+
+        toolbox = module.create_toolbox()
+        self._modules[module_name] = module
+        tool_labels = toolbox.tools()
+        self._tool_labels.extend(tool_labels)
+        self._tool_names.extend(toolbox.tool_names())
+        self._tools.extend([Tool(toolbox, tool_index) for tool_index in range(0, len(tool_labels))])
+
     def find_tool(self, tool_label):
         """
         Returns the tool having the specified label otherwise None is returned.
@@ -67,9 +186,35 @@ class ToolRegistry(object):
 
         return None
 
+    def find_tool_by_name(self, tool_name):
+        """
+        Returns the tool having the specified stable name otherwise None is returned.
+        """
+        if tool_name in self._tool_names:
+            return self._tools[self._tool_names.index(tool_name)]
+
+        return None
+
     def list_tools(self):
         """
-        Returns all tool names/labels being registered.
+        Returns all tool labels being registered.
         """
         return self._tool_labels
-        
\ No newline at end of file
+
+    def list_tool_names(self):
+        """
+        Returns all stable tool names being registered.
+        """
+        return self._tool_names
+
+    def list_modules(self):
+        """
+        Returns the manifests of all native tool modules being loaded.
+        """
+        return [module.arcrs_manifest() for module in self._modules.values()]
+
+    def list_load_errors(self):
+        """
+        Returns the errors of all native tool modules which could not be loaded by their module name.
+        """
+        return dict(self._load_errors)
@@ -0,0 +1,102 @@
+import os
+import sys
+import tempfile
+import types
+import unittest
+import warnings
+
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import arcrust
+
+
+
+class FakeToolbox(object):
+    """
+    Represents a toolbox like the one being created by a native tool module.
+    """
+
+    def __init__(self, tools):
+        self._tools = tools
+
+    def tools(self):
+        return [label for (name, label) in self._tools]
+
+    def tool_names(self):
+        return [name for (name, label) in self._tools]
+
+    def tool_name(self, tool_index):
+        return self._tools[tool_index][0]
+
+    def tool_label(self, tool_index):
+        return self._tools[tool_index][1]
+
+
+
+def _install_module(module_name, tools):
+    """
+    Installs a fake native tool module offering the specified (name, label) tools.
+    """
+    module = types.ModuleType(module_name)
+    module.arcrs_manifest = lambda: {'module': module_name}
+    module.create_toolbox = lambda: FakeToolbox(tools)
+    sys.modules[module_name] = module
+    return module
+
+
+
+class ToolRegistryTest(unittest.TestCase):
+
+    def setUp(self):
+        self._module_names = []
+
+    def tearDown(self):
+        for module_name in self._module_names:
+            sys.modules.pop(module_name, None)
+
+    def install_module(self, module_name, tools):
+        self._module_names.append(module_name)
+        return _install_module(module_name, tools)
+
+    def test_load_modules(self):
+        self.install_module('fake_copy', [('CopyFeatures', 'Copy features')])
+        self.install_module('fake_buffer', [('Buffer', 'Buffer features'), ('Dissolve', 'Dissolve features')])
+
+        registry = arcrust.ToolRegistry(module_dirs=[], module_names=['fake_copy', 'fake_buffer', 'fake_copy'])
+        self.assertEqual(['Copy features', 'Buffer features', 'Dissolve features'], registry.list_tools(), 'Tool labels are wrong!')
+        self.assertEqual(['CopyFeatures', 'Buffer', 'Dissolve'], registry.list_tool_names(), 'Tool names are wrong!')
+        self.assertEqual([{'module': 'fake_copy'}, {'module': 'fake_buffer'}], registry.list_modules(), 'Manifests are wrong!')
+        self.assertEqual('Dissolve', registry.find_tool('Dissolve features').name, 'Tool found by label is wrong!')
+        self.assertEqual('Buffer features', registry.find_tool_by_name('Buffer').label, 'Tool found by name is wrong!')
+        self.assertIsNone(registry.find_tool('Clip features'), 'Unknown label must not be found!')
+        self.assertIsNone(registry.find_tool_by_name('Clip'), 'Unknown name must not be found!')
+
+    def test_reject_unknown_module(self):
+        self.install_module('fake_plain', [])
+        del sys.modules['fake_plain'].arcrs_manifest
+
+        with self.assertRaises(ValueError):
+            arcrust.ToolRegistry(module_dirs=[], module_names=['fake_plain'])
+
+    def test_report_broken_modules(self):
+        self.install_module('fake_valid', [('CopyFeatures', 'Copy features')])
+        with tempfile.TemporaryDirectory() as module_dir:
+            for file_name in ['fake_broken.cp311-win_amd64.pyd', 'libfake_valid.so', 'readme.txt']:
+                with open(os.path.join(module_dir, file_name), 'w') as module_file:
+                    module_file.write('no native module')
+
+            with warnings.catch_warnings(record=True) as caught_warnings:
+                warnings.simplefilter('always')
+                registry = arcrust.ToolRegistry(module_dirs=[module_dir], module_names=[])
+
+            sys.path.remove(module_dir)
+
+        self.assertEqual(['CopyFeatures'], registry.list_tool_names(), 'Valid modules must be loaded!')
+        self.assertEqual(['fake_broken'], list(registry.list_load_errors().keys()), 'Load errors are wrong!')
+        self.assertIsInstance(registry.list_load_errors()['fake_broken'], ImportError, 'Load error is wrong!')
+        self.assertTrue(any('fake_broken' in str(caught_warning.message) for caught_warning in caught_warnings), 'Load error must be reported!')
+
+
+
+if __name__ == '__main__':
+    unittest.main()
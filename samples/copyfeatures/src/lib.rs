@@ -1,122 +1,39 @@
 extern crate arcrs;
 
+use arcrs::data;
 use arcrs::gp;
+use arcrs::gp::api::GpTool;
 
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::wrap_pyfunction;
 
 use std::path::Path;
 
-/// Copy features Tool
+/// Copies all features including the attributes from the input into a new feature class.
+#[derive(Default, GpTool)]
+#[gptool(name = "CopyFeatures", label = "Copy features", category = "Features", description = "Copies features from one feature class into another.",
+    usage = "The output feature class must not exist.",
+    usage = "The output feature class has the same shape type and spatial reference like the input.",
+    tags("copy", "features", "rust"),
+    example(title = "CopyFeatures example (Python window)", description = "Copies the features into a new feature class.",
+        code = "arcpy.arcrust_integration.CopyFeatures('roads', r'c:\\data\\roads.gdb\\roads_copy')"))]
 pub struct CopyFeaturesTool {
+    /// The features being copied.
+    #[gpparam(display_name = "Input Features", data_type = "GPFeatureRecordSetLayer")]
+    in_features: gp::api::CatalogPath,
 
+    /// The feature class being created and containing the copied features.
+    #[gpparam(display_name = "Output Features", data_type = "DEFeatureClass", direction = "Output")]
+    out_features: gp::api::CatalogPath
 }
 
-impl gp::api::GpTool for CopyFeaturesTool {
+impl gp::api::GpToolRun for CopyFeaturesTool {
     
-    fn label(&self) -> &str { "Copy features" }
-    
-    fn description(&self) -> &str { "Copies features from one feature class into another." }
-
-    fn parameters(&self) -> Vec<gp::api::GpParameter> { 
-        vec![gp::api::GpParameter{
-            display_name: String::from("Input Features"),
-            name: String::from("in_features"),
-            data_type: gp::api::DataType::GPFeatureRecordSetLayer,
-            parameter_type: gp::api::ParameterType::Required,
-            direction: gp::api::Direction::Input
-        }, gp::api::GpParameter{
-            display_name: String::from("Output Features"),
-            name: String::from("out_features"),
-            data_type: gp::api::DataType::DEFeatureClass,
-            parameter_type: gp::api::ParameterType::Required,
-            direction: gp::api::Direction::Output
-        }]
-    }
-    
-    fn execute(&self, py: Python, parameters: Vec<gp::api::PyParameterValue>, messages: gp::api::PyGpMessages) -> PyResult<()> {
+    fn run(&self, py: Python, _parameters: &gp::api::PyParameterValues, messages: &gp::api::PyGpMessages) -> PyResult<()> {
         messages.add_message("Copy features...")?;
 
-        let input_param: &gp::api::PyParameterValue = &parameters[0];
-        
-        // Try to access the fields
-        let fields = input_param.fields()?;
-        let mut attribute_fields = Vec::with_capacity(fields.len());
-        let mut input_field_names = vec!["SHAPE@".to_string()];
-        let mut output_field_names = vec!["SHAPE@".to_string()];
-        for field in &fields {
-            if input_param.oid_field_name()? != field.name 
-            && input_param.shape_field_name()? != field.name {
-                let attribute_field = gp::api::GpField {
-                    name: field.name.to_string(),
-                    field_type: field.field_type
-                };
-                attribute_fields.push(attribute_field);
-                
-                input_field_names.push(field.name.to_string());
-                output_field_names.push(field.name.to_string());
-            }
-        }
-
-        let output_param = &parameters[1];
-
-        // Get the output path
-        let output_path = output_param.catalog_path()?;
-        let file_path = Path::new(&output_path);
-        let gdb_path = file_path.parent().unwrap().to_str().unwrap().to_string();
-        let table_name = file_path.file_name().unwrap().to_str().unwrap().to_string();
-        // Create a new feature class
-        use gp::tools::GpToolExecute;
-        let shape_type = input_param.shape_type()?;
-        let spatial_reference = input_param.spatial_reference()?;
-        let wkid = spatial_reference.wkid;
-        let create_tool = gp::tools::GpCreateFeatureClassTool::new(gdb_path, table_name, shape_type, wkid);
-        match create_tool.execute(py) {
-            Ok(gp_result) => {
-                // Try to access the catalog path from the geoprocessing result
-                let catalog_path = gp_result.first_as_str(py)?;
-                let fields_tool = gp::tools::GpAddFieldsTool::new(catalog_path, attribute_fields);
-                match fields_tool.execute(py) {
-                    Ok(_) => {
-                        // Try to access the features
-                        // The API traits must be in the current scope
-                        use gp::api::IntoCursor;
-                        let where_clause = "1=1";
-                        let search_cursor = input_param.into_search_cursor(input_field_names, where_clause)?;
-                        let field_count = output_field_names.len();
-                        let insert_cursor = output_param.into_insert_cursor(output_field_names)?;
-                        loop {
-                            match search_cursor.next_row() {
-                                Ok(next_row) => {
-                                    // Insert the row
-                                    insert_cursor.insert_row(next_row)?;
-                                },
-                                Err (_) => break
-                            }
-
-                            /*
-                            match search_cursor.next() {
-                                Ok(next_row) => {
-                                    // Fill the feature buffer
-                                    let mut feature_buffer = gp::api::InsertBuffer::new(field_count);
-                                    for field_index in 0..next_row.value_count() {
-                                        let row_value: PyObject = next_row.value(field_index)?;
-                                        feature_buffer.add_value(py, &row_value);
-                                    }
-                                        
-                                    insert_cursor.insert(feature_buffer)?;
-                                },
-                                Err(_) => break
-                            }
-                            */
-                        }
-                    }
-                    Err(py_err) => Err(py_err)?
-                }
-            }
-            Err(py_err) => Err(py_err)?
-        }
+        let data_source = data::arcpy::ArcpyDataSource::new(py);
+        copy_features(&data_source, &self.in_features.path, &self.out_features.path)?;
 
         messages.add_message("Copy features done.")?;
 
@@ -124,6 +41,27 @@ impl gp::api::GpTool for CopyFeaturesTool {
     }
 }
 
+/// Copies all features including the attributes from the input into a new table.
+/// The same logic runs inside ArcGIS Pro and against any other data source.
+/// The features are streamed from the search cursor into the insert cursor.
+/// Returns the number of copied features.
+pub fn copy_features(data_source: &dyn data::DataSource, in_table: &str, out_table: &str) -> data::DataResult<usize> {
+    let schema = data_source.describe(in_table)?;
+    data_source.create_table(out_table, &schema)?;
+
+    let field_names = schema.field_names();
+    let field_names: Vec<&str> = field_names.iter().map(|field_name| field_name.as_str()).collect();
+    let mut inserter = data_source.insert_cursor(out_table, &field_names)?;
+    let mut copy_count = 0;
+    for row in data_source.search(in_table, &field_names, "1=1")? {
+        inserter.insert_row(row?)?;
+        copy_count += 1;
+    }
+    inserter.finish()?;
+
+    Ok(copy_count)
+}
+
 
 
 
@@ -135,6 +73,10 @@ pub struct DummyGpTool {
 
 impl gp::api::GpTool for DummyGpTool {
 
+    fn name(&self) -> String {
+        String::from("DummyTool")
+    }
+
     fn label(&self) -> &str {
         "Dummy Tool"
     }
@@ -149,17 +91,21 @@ impl gp::api::GpTool for DummyGpTool {
             name: String::from("in_features"),
             data_type: gp::api::DataType::GPFeatureRecordSetLayer,
             parameter_type: gp::api::ParameterType::Required,
-            direction: gp::api::Direction::Input
+            direction: gp::api::Direction::Input,
+            filter: None,
+            default_value: None
         }, gp::api::GpParameter{
             display_name: String::from("Output Features"),
             name: String::from("out_features"),
             data_type: gp::api::DataType::DEFeatureClass,
             parameter_type: gp::api::ParameterType::Required,
-            direction: gp::api::Direction::Output
+            direction: gp::api::Direction::Output,
+            filter: None,
+            default_value: None
         }]
     }
 
-    fn execute(&self, py: Python, parameters: Vec<gp::api::PyParameterValue>, messages: gp::api::PyGpMessages) -> PyResult<()> {
+    fn execute(&self, py: Python, parameters: gp::api::PyParameterValues, messages: gp::api::PyGpMessages) -> PyResult<()> {
         // The API traits must be in the current scope
         use gp::api::{GeometryFromValues, IntoCursor};
 
@@ -187,37 +133,41 @@ impl gp::api::GpTool for DummyGpTool {
 
                     // Check whether the dataset exists
                     if gp_parameter.path_exists()? {
+                        // Describe the dataset only once
+                        let description = gp_parameter.describe()?;
+
                         // OID field name
-                        let oid_field_name = gp_parameter.oid_field_name()?;
-                        messages.add_message(&oid_field_name)?;
+                        if let Some(oid_field_name) = &description.oid_field_name {
+                            messages.add_message(oid_field_name)?;
+                        }
 
                         // Shape field name
-                        let shape_field_name = gp_parameter.shape_field_name()?;
-                        messages.add_message(&shape_field_name)?;
+                        if let Some(shape_field_name) = &description.shape_field_name {
+                            messages.add_message(shape_field_name)?;
+                        }
 
                         // Shape type
-                        let shape_type = gp_parameter.shape_type()?;
-                        messages.add_message(shape_type.as_str())?;
+                        if let Some(shape_type) = description.shape_type {
+                            messages.add_message(shape_type.as_str())?;
+                        }
 
                         // Try to access the spatial reference
-                        let spatial_reference = gp_parameter.spatial_reference()?;
-                        messages.add_message(&spatial_reference.wkid.to_string())?;
+                        if let Some(spatial_reference) = &description.spatial_reference {
+                            messages.add_message(&spatial_reference.wkid.to_string())?;
+                        }
 
                         // Try to access the fields
-                        let fields = gp_parameter.fields()?;
-                        let mut attribute_field_names = Vec::with_capacity(fields.len());
-                        for field in fields {
+                        let mut attribute_field_names = Vec::with_capacity(description.fields.len());
+                        for field in &description.fields {
                             messages.add_message(&field.name)?;
                             messages.add_message(field.field_type.as_str())?;
-
-                            if oid_field_name != field.name 
-                            && shape_field_name != field.name {
-                                attribute_field_names.push(field.name);
-                            }
+                        }
+                        for field in description.attribute_fields() {
+                            attribute_field_names.push(field.name.to_string());
                         }
 
                         // Try to access the features
-                        let mut field_names = vec![oid_field_name, "SHAPE@".to_string()];
+                        let mut field_names = vec![gp::api::FieldToken::OID.into(), gp::api::FieldToken::Shape.into()];
                         field_names.append(&mut attribute_field_names);
                         let where_clause = "1=1";
                         let search_cursor = gp_parameter.into_search_cursor(field_names, where_clause)?;
@@ -225,7 +175,7 @@ impl gp::api::GpTool for DummyGpTool {
                             match search_cursor.next() {
                                 Ok(next_row) => {
                                     // Try to access OID
-                                    let oid: i32 = next_row.as_intvalue(0)?;
+                                    let oid = next_row.as_oidvalue(0)?;
                                     messages.add_message(&oid.to_string())?;
 
                                     // Try to access the geometry instance
@@ -251,6 +201,12 @@ impl gp::api::GpTool for DummyGpTool {
                         // Check for output parameter type
                         out_features_parmeter = Some(gp_parameter);
                     }
+                },
+                _ => {
+                    // Simple values like text or numbers
+                    if gp_parameter.has_value()? {
+                        messages.add_message(&gp_parameter.value_as_text()?)?;
+                    }
                 }
             }
         }
@@ -289,7 +245,7 @@ impl gp::api::GpTool for DummyGpTool {
                             Ok(_) => {
 
                                 // Bump some features into it
-                                let field_names = vec![String::from("SHAPE@"), String::from("Description")];
+                                let field_names = vec![gp::api::FieldToken::Shape.into(), String::from("Description")];
                                 let dessau_location = gp::api::Point {
                                     x: 12.24555,
                                     y: 51.83864
@@ -323,34 +279,20 @@ impl gp::api::GpTool for DummyGpTool {
 
 
 
-/// Creates a new toolbox
-#[pyfunction]
-fn create_toolbox(label: &str, alias: &str) -> PyResult<gp::PyToolbox> {
-    let copy_features = CopyFeaturesTool {
-    };
-
-    let pytoolbox_factory = gp::PyToolboxFactory {
-    };
-
-    let py_toolbox = pytoolbox_factory.create_toolbox(label, alias, vec![copy_features])?;
-
-    Ok(py_toolbox)
+/// Adds all tools of this module to the toolbox.
+fn build_toolbox(toolbox_builder: gp::PyToolboxBuilder) -> gp::PyToolboxBuilder {
+    toolbox_builder
+        .add_tool(CopyFeaturesTool::default())
+        .add_tool(DummyGpTool {})
 }
 
-/// This module allows the implementation of Geoprocessing Tools using Rust.
-#[pymodule]
-fn copyfeatures(_py: Python, module: &PyModule) -> PyResult<()> {
-    module.add_class::<gp::PyToolbox>()?;
-    module.add_function(wrap_pyfunction!(create_toolbox, module)?)?;
-
-    Ok(())
-}
+arcrs::toolbox_module!(copyfeatures, "Rust Copy Tools", "rust_copy_tools", build_toolbox);
 
 #[cfg(test)]
 mod tests {
 
     use super::gp;
-    use super::DummyGpTool;
+    use super::{CopyFeaturesTool, DummyGpTool};
 
     #[test]
     fn create_toolbox() {
@@ -358,12 +300,10 @@ mod tests {
         use gp::api::GpTool;
         let dummy_tool = DummyGpTool {
         };
+        assert_eq!("Dummy Tool", dummy_tool.label(), "Label is wrong!");
 
-        let py_tool = gp::PyTool {
-            label: dummy_tool.label().to_string(),
-            description: dummy_tool.description().to_string(),
-            tool_impl: Box::new(dummy_tool)
-        };
+        let py_tool = gp::PyTool::new(dummy_tool);
+        assert_eq!("DummyTool", py_tool.name, "Name is wrong!");
 
         let toolbox = gp::PyToolbox {
             label: String::from("Test Toolbox"),
@@ -373,4 +313,141 @@ mod tests {
 
         assert_eq!("Test Toolbox", toolbox.label, "Label is wrong!");
     }
+
+    #[test]
+    fn derive_parameters() {
+        use gp::api::GpTool;
+        let copy_tool = CopyFeaturesTool::default();
+        assert_eq!("Copy features", copy_tool.label(), "Label is wrong!");
+
+        let parameters = copy_tool.parameters();
+        assert_eq!(2, parameters.len(), "Parameter count is wrong!");
+        assert_eq!("in_features", parameters[0].name(), "Name is wrong!");
+        assert_eq!("Input Features", parameters[0].display_name(), "Display name is wrong!");
+        assert_eq!("GPFeatureRecordSetLayer", parameters[0].data_type().as_str(), "Data type is wrong!");
+        assert_eq!("out_features", parameters[1].name(), "Name is wrong!");
+        assert_eq!("Output", parameters[1].direction().as_str(), "Direction is wrong!");
+        assert_eq!("Required", parameters[1].parameter_type().as_str(), "Parameter type is wrong!");
+
+        let metadata = copy_tool.metadata();
+        assert_eq!(2, metadata.usage.len(), "Usage count is wrong!");
+        assert_eq!(Some("The features being copied."), metadata.parameter_explanation("in_features"), "Explanation is wrong!");
+        assert!(metadata.summary.starts_with("Copies all features"), "Summary is wrong!");
+    }
+
+    #[test]
+    fn create_heterogeneous_toolbox() {
+        let toolbox = gp::PyToolboxBuilder::new("Test Toolbox", "test_rust")
+            .add_tool(CopyFeaturesTool::default())
+            .add_boxed_tool(Box::new(DummyGpTool {}))
+            .build()
+            .unwrap();
+
+        assert_eq!(2, toolbox.py_tools.len(), "Tool count is wrong!");
+        assert_eq!(Some(1), toolbox.find_tool_index("Dummy Tool"), "Tool index is wrong!");
+        assert!(toolbox.find_tool("Unknown Tool").is_none(), "Tool must not exist!");
+        assert_eq!(Some(0), toolbox.find_tool_index_by_name("CopyFeatures"), "Tool index is wrong!");
+        assert_eq!(Some("Features"), toolbox.py_tools[0].category.as_deref(), "Category is wrong!");
+    }
+
+    #[test]
+    fn execute_copy_features() {
+        use pyo3::prelude::*;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = gp::testing::MockArcpy::install(py).unwrap();
+        let fields = vec![gp::api::GpField {
+            name: String::from("Name"),
+            field_type: gp::api::FieldType::String
+        }];
+        arcpy.create_feature_class("memory/cities", gp::api::ShapeType::Point, 4326, &fields).unwrap();
+        arcpy.insert_row("memory/cities", &["SHAPE@XY", "Name"], ((12.24555, 51.83864), "Dessau")).unwrap();
+        arcpy.insert_row("memory/cities", &["SHAPE@XY", "Name"], ((13.40495, 52.52001), "Berlin")).unwrap();
+
+        let toolbox = gp::PyToolboxBuilder::new("Test Toolbox", "test_rust")
+            .add_tool(CopyFeaturesTool::default())
+            .build()
+            .unwrap();
+        let py_toolbox = Py::new(py, toolbox).unwrap();
+        let py_parameters = py_toolbox.call_method1(py, "tool_parameter_info", (0,)).unwrap();
+        let parameters: Vec<&PyAny> = py_parameters.extract(py).unwrap();
+        parameters[0].setattr("value", "memory/cities").unwrap();
+        parameters[1].setattr("value", "memory/cities_copy").unwrap();
+        let py_messages = arcpy.create_messages().unwrap();
+        py_toolbox.call_method1(py, "tool_execute", (0, py_parameters, &py_messages)).unwrap();
+
+        assert_eq!(2, arcpy.row_count("memory/cities_copy").unwrap(), "Row count is wrong!");
+        let rows = arcpy.rows("memory/cities_copy", &["SHAPE@XY", "Name"]).unwrap();
+        let name: String = rows[1][1].extract(py).unwrap();
+        assert_eq!("Berlin", name, "Name is wrong!");
+        let messages = arcpy.messages(&py_messages).unwrap();
+        assert_eq!((0, String::from("Copy features done.")), messages[messages.len() - 1], "Message is wrong!");
+    }
+
+    #[test]
+    fn execute_copy_features_using_harness() {
+        use gp::testing::{GpToolHarness, TestTable, TestValue};
+        use pyo3::prelude::*;
+
+        let gil = Python::acquire_gil();
+        let cities = TestTable::features(gp::api::ShapeType::Point, 4326)
+            .with_field("Name", gp::api::FieldType::String)
+            .with_row(vec![TestValue::Point(12.24555, 51.83864), "Dessau".into()])
+            .with_row(vec![TestValue::Point(13.40495, 52.52001), "Berlin".into()]);
+        let result = GpToolHarness::new(gil.python()).unwrap()
+            .with_value("in_features", cities.clone())
+            .execute(&CopyFeaturesTool::default())
+            .unwrap();
+
+        assert!(result.is_ok(), "Execution failed!");
+        assert_eq!(vec!["Copy features...", "Copy features done."], result.infos(), "Messages are wrong!");
+        assert!(result.warnings().is_empty(), "Warnings are not expected!");
+        assert_eq!(Some(&cities), result.output_table("out_features"), "Output features are wrong!");
+    }
+
+    #[test]
+    fn run_copy_features_using_parameter_file() {
+        use gp::runner::{ParameterFile, ToolRunner};
+        use gp::testing::MockArcpy;
+        use pyo3::prelude::*;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let arcpy = MockArcpy::install(py).unwrap();
+        arcpy.create_feature_class("memory/run_cities", gp::api::ShapeType::Point, 4326, &[]).unwrap();
+        arcpy.insert_row("memory/run_cities", &["SHAPE@XY"], ((12.24555, 51.83864),)).unwrap();
+
+        let py_toolbox = Py::new(py, gp::PyToolboxBuilder::new("Test Toolbox", "test_rust").add_tool(CopyFeaturesTool::default()).build().unwrap()).unwrap();
+        let tool_runner = ToolRunner::new(py, py_toolbox.as_ref(py));
+        let tool_index = tool_runner.tool_index("CopyFeatures").unwrap();
+        let parameter_file = ParameterFile::from_json(r#"{"in_features": "memory/run_cities", "out_features": "memory/run_cities_copy"}"#).unwrap();
+        assert!(tool_runner.validate(tool_index, &parameter_file).unwrap().is_empty(), "Parameter file must be valid!");
+
+        let run_result = tool_runner.execute(tool_index, &parameter_file).unwrap();
+        assert!(run_result.is_ok(), "Execution failed!");
+        assert_eq!(Some("memory/run_cities_copy"), run_result.output("out_features"), "Output is wrong!");
+        assert_eq!(1, arcpy.row_count("memory/run_cities_copy").unwrap(), "Row count is wrong!");
+    }
+
+    #[test]
+    fn copy_geojson_features() {
+        use super::{copy_features, data};
+        use data::DataSource;
+
+        let directory = std::env::temp_dir().join(format!("copyfeatures_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let data_source = data::geojson::GeoJsonDataSource::new(&directory);
+        let schema = data::TableSchema::features(gp::api::ShapeType::Point, 4326)
+            .with_field("Name", gp::api::FieldType::String);
+        data_source.create_table("cities", &schema).unwrap();
+        data_source.insert("cities", &["SHAPE@", "Name"], vec![
+            vec![data::Geometry::Point(12.24555, 51.83864).into(), "Dessau".into()]
+        ]).unwrap();
+
+        let copy_count = copy_features(&data_source, "cities", "cities_copy").unwrap();
+        assert_eq!(1, copy_count, "Copy count is wrong!");
+        assert_eq!(schema, data_source.describe("cities_copy").unwrap(), "Schema is wrong!");
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
 }